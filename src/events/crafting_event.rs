@@ -0,0 +1,9 @@
+use bevy::prelude::Event;
+
+use rose_game_common::messages::ClientEntityId;
+
+#[derive(Event)]
+pub enum CraftingEvent {
+    OpenUpgradeFromClientEntity { client_entity_id: ClientEntityId },
+    OpenSeparateFromClientEntity { client_entity_id: ClientEntityId },
+}
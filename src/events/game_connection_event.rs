@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use bevy::prelude::Event;
 
 use rose_data::ZoneId;
@@ -5,4 +7,7 @@ use rose_data::ZoneId;
 #[derive(Event)]
 pub enum GameConnectionEvent {
     Connected(ZoneId),
+    LogoutSuccess,
+    LogoutFailed { wait_duration: Duration },
+    ReturnToCharacterSelect,
 }
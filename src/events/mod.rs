@@ -4,6 +4,7 @@ mod chatbox_event;
 mod clan_dialog_event;
 mod client_entity_event;
 mod conversation_dialog_event;
+mod crafting_event;
 mod game_connection_event;
 mod hit_event;
 mod login_event;
@@ -29,6 +30,7 @@ pub use chatbox_event::ChatboxEvent;
 pub use clan_dialog_event::ClanDialogEvent;
 pub use client_entity_event::ClientEntityEvent;
 pub use conversation_dialog_event::ConversationDialogEvent;
+pub use crafting_event::CraftingEvent;
 pub use game_connection_event::GameConnectionEvent;
 pub use hit_event::HitEvent;
 pub use login_event::LoginEvent;
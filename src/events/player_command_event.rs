@@ -23,4 +23,6 @@ pub enum PlayerCommandEvent {
     DropMoney(usize),
     BankDepositItem(ItemSlot),
     BankWithdrawItem(usize),
+    InsertGem(EquipmentIndex, ItemSlot),
+    RepairItem(ItemSlot),
 }
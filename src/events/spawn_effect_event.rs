@@ -46,6 +46,5 @@ pub enum SpawnEffectEvent {
     OnEntity(Entity, Option<usize>, SpawnEffectData),
 
     // Spawns an effect with the given transform
-    #[allow(dead_code)]
     WithTransform(Transform, SpawnEffectData),
 }
@@ -20,4 +20,5 @@ pub enum WorldConnectionEvent {
     DeleteCharacterError {
         name: String,
     },
+    ReturnToCharacterSelect,
 }
@@ -10,7 +10,7 @@ use bevy::{
         apply_deferred, in_state, AddAsset, App, AssetServer, Assets, Camera, Camera3dBundle,
         Color, Commands, IntoSystemConfigs, IntoSystemSetConfigs, Msaa, OnEnter, OnExit,
         PluginGroup, PostStartup, PostUpdate, PreUpdate, Quat, Res, ResMut, Startup, State,
-        SystemSet, Transform, Update, Vec3,
+        SystemSet, Transform, Update, Vec2, Vec3,
     },
     render::{render_resource::WgpuFeatures, settings::WgpuSettings},
     transform::TransformSystem,
@@ -22,8 +22,10 @@ use enum_map::enum_map;
 use exe_resource_loader::{ExeResourceCursor, ExeResourceLoader};
 use serde::Deserialize;
 use std::{
+    collections::HashMap,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use rose_data::{CharacterMotionDatabaseOptions, NpcDatabaseOptions, ZoneId};
@@ -53,70 +55,79 @@ pub mod zone_loader;
 use audio::OddioPlugin;
 use events::{
     BankEvent, CharacterSelectEvent, ChatboxEvent, ClanDialogEvent, ClientEntityEvent,
-    ConversationDialogEvent, GameConnectionEvent, HitEvent, LoadZoneEvent, LoginEvent,
-    MessageBoxEvent, MoveDestinationEffectEvent, NetworkEvent, NpcStoreEvent,
+    ConversationDialogEvent, CraftingEvent, GameConnectionEvent, HitEvent, LoadZoneEvent,
+    LoginEvent, MessageBoxEvent, MoveDestinationEffectEvent, NetworkEvent, NpcStoreEvent,
     NumberInputDialogEvent, PartyEvent, PersonalStoreEvent, PlayerCommandEvent, QuestTriggerEvent,
     SpawnEffectEvent, SpawnProjectileEvent, SystemFuncEvent, UseItemEvent, WorldConnectionEvent,
     ZoneEvent,
 };
 use model_loader::ModelLoader;
+use protocol::{PacketInspector, ProtocolRegistry, SessionRecorder, SessionReplay};
 use render::{DamageDigitMaterial, RoseRenderPlugin};
 use resources::{
     load_ui_resources, run_network_thread, ui_requested_cursor_apply_system, update_ui_resources,
-    AppState, ClientEntityList, DamageDigitsSpawner, DebugRenderConfig, GameData, NameTagSettings,
-    NetworkThread, NetworkThreadMessage, RenderConfiguration, SelectedTarget, ServerConfiguration,
-    SoundCache, SoundSettings, SpecularTexture, VfsResource, WorldTime, ZoneTime,
+    AppState, ChatIgnoreList, ClientEntityList, ConnectionState, ConversationTrace,
+    DamageDigitsSpawner, DebugRenderConfig, FriendList, GameData, GameplaySettings, HelpIndex,
+    KeyBindings, LogoutState, NameTagSettings, NetworkSession, NetworkThread, NetworkThreadMessage,
+    QuestObjectiveTable, ReconnectSettings, RenderConfiguration, RepairState, SelectedTarget,
+    ServerConfiguration, SettingsFile, SoundCache, SoundSettings, SpecularTexture, SystemFuncTable,
+    VfsResource, WorldTime, ZoneTime,
 };
 use scripting::RoseScriptingPlugin;
 use systems::{
     ability_values_system, animation_effect_system, animation_sound_system, auto_login_system,
-    background_music_system, character_model_add_collider_system, character_model_blink_system,
-    character_model_update_system, character_select_enter_system, character_select_event_system,
-    character_select_exit_system, character_select_input_system, character_select_models_system,
-    character_select_system, clan_system, client_entity_event_system, collision_height_only_system,
-    collision_player_system, collision_player_system_join_zoin, command_system,
+    auto_pickup_system, background_music_system, character_model_add_collider_system,
+    character_model_blink_system, character_model_update_system, character_select_enter_system,
+    character_select_event_system, character_select_exit_system, character_select_input_system,
+    character_select_models_system, character_select_system, clan_system,
+    client_entity_event_system, collision_height_only_system, collision_player_system,
+    collision_player_system_join_zoin, command_system, community_system, connection_state_system,
     conversation_dialog_system, cooldown_system, damage_digit_render_system,
     debug_render_collider_system, debug_render_directional_light_system,
     debug_render_skeleton_system, directional_light_system, effect_system, facing_direction_system,
     free_camera_system, game_connection_system, game_mouse_input_system, game_state_enter_system,
-    game_zone_change_system, hit_event_system, item_drop_model_add_collider_system,
-    item_drop_model_system, login_connection_system, login_event_system, login_state_enter_system,
-    login_state_exit_system, login_system, model_viewer_enter_system, model_viewer_exit_system,
-    model_viewer_system, move_destination_effect_system, name_tag_system,
-    name_tag_update_color_system, name_tag_update_healthbar_system, name_tag_visibility_system,
-    network_thread_system, npc_idle_sound_system, npc_model_add_collider_system,
-    npc_model_update_system, orbit_camera_system, particle_sequence_system,
-    passive_recovery_system, pending_damage_system, pending_skill_effect_system,
-    personal_store_model_add_collider_system, personal_store_model_system, player_command_system,
-    projectile_system, quest_trigger_system, spawn_effect_system, spawn_projectile_system,
-    status_effect_system, system_func_event_system, update_position_system, use_item_event_system,
-    vehicle_model_system, vehicle_sound_system, visible_status_effects_system,
-    world_connection_system, world_time_system, zone_time_system, zone_viewer_enter_system,
-    DebugInspectorPlugin,
+    game_state_exit_system, game_zone_change_system, hit_event_system,
+    item_drop_model_add_collider_system, item_drop_model_system, login_connection_system,
+    login_event_system, login_state_enter_system, login_state_exit_system, login_system,
+    logout_system, model_viewer_enter_system, model_viewer_exit_system, model_viewer_system,
+    move_destination_effect_system, name_tag_system, name_tag_update_color_system,
+    name_tag_update_healthbar_system, name_tag_visibility_system, network_thread_system,
+    npc_idle_sound_system, npc_model_add_collider_system, npc_model_update_system,
+    orbit_camera_system, particle_sequence_system, passive_recovery_system, pending_damage_system,
+    pending_skill_effect_system, personal_store_model_add_collider_system,
+    personal_store_model_system, player_command_system, projectile_system, quest_trigger_system,
+    settings_save_system, spawn_effect_system, spawn_projectile_system, status_effect_system,
+    system_func_event_system, update_position_system, use_item_event_system, vehicle_model_system,
+    vehicle_sound_system, visible_status_effects_system, world_connection_system,
+    world_time_system, zone_time_system, zone_viewer_enter_system, DebugInspectorPlugin,
+    NetworkDiagnosticsPlugin,
 };
 use ui::{
     load_dialog_sprites_system, ui_bank_system, ui_character_create_system,
     ui_character_info_system, ui_character_select_name_tag_system, ui_character_select_system,
-    ui_chatbox_system, ui_clan_system, ui_create_clan_system, ui_debug_camera_info_system,
-    ui_debug_client_entity_list_system, ui_debug_command_viewer_system,
+    ui_chatbox_system, ui_clan_system, ui_community_system, ui_create_clan_system,
+    ui_debug_camera_info_system, ui_debug_client_entity_list_system,
+    ui_debug_command_viewer_system, ui_debug_conversation_trace_system,
     ui_debug_diagnostics_system, ui_debug_dialog_list_system, ui_debug_effect_list_system,
     ui_debug_entity_inspector_system, ui_debug_item_list_system, ui_debug_menu_system,
-    ui_debug_npc_list_system, ui_debug_physics_system, ui_debug_render_system,
-    ui_debug_skill_list_system, ui_debug_zone_lighting_system, ui_debug_zone_list_system,
-    ui_debug_zone_time_system, ui_drag_and_drop_system, ui_game_menu_system, ui_hotbar_system,
-    ui_inventory_system, ui_item_drop_name_system, ui_login_system, ui_message_box_system,
-    ui_minimap_system, ui_npc_store_system, ui_number_input_dialog_system, ui_party_option_system,
-    ui_party_system, ui_personal_store_system, ui_player_info_system, ui_quest_list_system,
-    ui_respawn_system, ui_selected_target_system, ui_server_select_system, ui_settings_system,
+    ui_debug_npc_list_system, ui_debug_packet_inspector_system, ui_debug_physics_system,
+    ui_debug_quest_trigger_system, ui_debug_render_system, ui_debug_skill_list_system,
+    ui_debug_zone_lighting_system, ui_debug_zone_list_system, ui_debug_zone_time_system,
+    ui_drag_and_drop_system, ui_exit_system, ui_game_menu_system, ui_help_system, ui_hotbar_system,
+    ui_info_system, ui_inventory_system, ui_item_drop_name_system, ui_login_system,
+    ui_message_box_system, ui_minimap_system, ui_npc_store_system, ui_number_input_dialog_system,
+    ui_party_option_system, ui_party_system, ui_personal_store_system, ui_player_info_system,
+    ui_quest_list_system, ui_quest_tracker_system, ui_repair_system, ui_respawn_system,
+    ui_selected_target_system, ui_separate_system, ui_server_select_system, ui_settings_system,
     ui_skill_list_system, ui_skill_tree_system, ui_sound_event_system, ui_status_effects_system,
-    ui_window_sound_system, widgets::Dialog, DialogLoader, UiSoundEvent, UiStateDebugWindows,
-    UiStateDragAndDrop, UiStateWindows,
+    ui_upgrade_system, ui_window_sound_system, widgets::Dialog, ChatCommands, DialogLoader,
+    UiSoundEvent, UiStateDebugWindows, UiStateDragAndDrop, UiStateQuestTracker, UiStateWindows,
 };
 use vfs_asset_io::VfsAssetIo;
 use zms_asset_loader::{ZmsAssetLoader, ZmsMaterialNumFaces, ZmsNoSkinAssetLoader};
 use zone_loader::{zone_loader_system, ZoneLoader, ZoneLoaderAsset};
 
-use crate::components::SoundCategory;
+use crate::components::{NameTagType, SoundCategory};
 
 #[derive(Default, Deserialize)]
 #[serde(default)]
@@ -256,6 +267,33 @@ impl Default for ServerConfig {
     }
 }
 
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct ReconnectConfig {
+    pub enabled: bool,
+    pub max_attempts: u32,
+    pub initial_delay_seconds: f32,
+    pub max_delay_seconds: f32,
+}
+
+impl Default for ReconnectConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_attempts: 5,
+            initial_delay_seconds: 2.0,
+            max_delay_seconds: 30.0,
+        }
+    }
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct SessionConfig {
+    pub record_path: Option<String>,
+    pub replay_path: Option<String>,
+}
+
 #[derive(Deserialize)]
 #[serde(default)]
 pub struct GameConfig {
@@ -290,6 +328,8 @@ pub struct GraphicsConfig {
     pub passthrough_terrain_textures: bool,
     pub trail_effect_duration_multiplier: f32,
     pub disable_vsync: bool,
+    pub shadow_distance: f32,
+    pub fog_distance_multiplier: f32,
 }
 
 impl Default for GraphicsConfig {
@@ -302,6 +342,63 @@ impl Default for GraphicsConfig {
             passthrough_terrain_textures: false,
             trail_effect_duration_multiplier: 1.0,
             disable_vsync: false,
+            shadow_distance: 40.0,
+            fog_distance_multiplier: 1.0,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct NameTagConfig {
+    pub show_all: bool,
+    pub font_size: f32,
+}
+
+impl Default for NameTagConfig {
+    fn default() -> Self {
+        Self {
+            show_all: true,
+            font_size: 16.0,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct InterfaceConfig {
+    pub ui_scale: f32,
+    pub character_name_tags: NameTagConfig,
+    pub npc_name_tags: NameTagConfig,
+    pub monster_name_tags: NameTagConfig,
+}
+
+impl Default for InterfaceConfig {
+    fn default() -> Self {
+        Self {
+            ui_scale: 1.0,
+            character_name_tags: NameTagConfig::default(),
+            npc_name_tags: NameTagConfig::default(),
+            monster_name_tags: NameTagConfig {
+                show_all: false,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct GameplayConfig {
+    pub auto_pickup: bool,
+    pub camera_sensitivity: f32,
+}
+
+impl Default for GameplayConfig {
+    fn default() -> Self {
+        Self {
+            auto_pickup: false,
+            camera_sensitivity: 1.0,
         }
     }
 }
@@ -357,14 +454,43 @@ pub struct Config {
     pub auto_login: AutoLoginConfig,
     pub filesystem: FilesystemConfig,
     pub game: GameConfig,
+    pub gameplay: GameplayConfig,
     pub graphics: GraphicsConfig,
+    pub interface: InterfaceConfig,
+    /// Maps action names to a list of bindings, e.g. `toggle_inventory = ["Alt+I", "Alt+V"]`
+    pub keybindings: HashMap<String, Vec<String>>,
+    pub reconnect: ReconnectConfig,
     pub server: ServerConfig,
+    pub session: SessionConfig,
     pub sound: SoundConfig,
+
+    /// Where the config was loaded from, settings changed in game are saved back here
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+/// The per-user config.toml used when no --config is given
+pub fn default_config_path() -> Option<PathBuf> {
+    directories::ProjectDirs::from("", "", "rose-offline-client")
+        .map(|project_dirs| project_dirs.config_dir().join("config.toml"))
 }
 
+/// Loads the config from `path`, settings changed in game are only saved back
+/// to `path` when it was read successfully or does not exist yet, so that a
+/// config file with errors is never overwritten with default values.
 pub fn load_config(path: &Path) -> Config {
     let toml_str = match std::fs::read_to_string(path) {
         Ok(toml_str) => toml_str,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            println!(
+                "Configuration {} not found, it will be created when settings are changed",
+                path.to_string_lossy()
+            );
+            return Config {
+                path: Some(path.into()),
+                ..Default::default()
+            };
+        }
         Err(error) => {
             println!(
                 "Failed to load configuration from {} with error: {}",
@@ -375,14 +501,15 @@ pub fn load_config(path: &Path) -> Config {
         }
     };
 
-    match toml::from_str(&toml_str) {
-        Ok(config) => {
+    match toml::from_str::<Config>(&toml_str) {
+        Ok(mut config) => {
             println!("Read configuration from {}", path.to_string_lossy());
+            config.path = Some(path.into());
             config
         }
         Err(error) => {
             println!(
-                "Failed to load configuration from {} with error: {}",
+                "Failed to load configuration from {} with error: {}, settings will not be saved",
                 path.to_string_lossy(),
                 error
             );
@@ -422,6 +549,59 @@ pub fn run_zone_viewer(config: &Config, zone_id: Option<ZoneId>) {
     );
 }
 
+/// Prints the disassembly of a .CON or compiled lua script from the VFS, along
+/// with anything `Lua4VM` is not able to run.
+/// Returns false if the script could not be disassembled or uses unsupported instructions.
+pub fn run_lua_disassembler(config: &Config, path: &str) -> bool {
+    let Some(virtual_filesystem) = config.filesystem.create_virtual_filesystem() else {
+        eprintln!("No filesystem devices");
+        return false;
+    };
+
+    match scripting::disassemble_script_file(&virtual_filesystem, path) {
+        Ok(disassembly) => {
+            println!("{}", disassembly.text);
+
+            println!(
+                "Globals read: {}",
+                disassembly
+                    .globals_read
+                    .iter()
+                    .map(|name| name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            println!(
+                "Globals written: {}",
+                disassembly
+                    .globals_written
+                    .iter()
+                    .map(|name| name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+
+            if disassembly.issues.is_empty() {
+                println!("No unsupported instructions found");
+                true
+            } else {
+                eprintln!("Found {} issues:", disassembly.issues.len());
+                for issue in disassembly.issues.iter() {
+                    eprintln!(
+                        "  function line {} pc {:03}: {}",
+                        issue.function_line, issue.pc, issue.message
+                    );
+                }
+                false
+            }
+        }
+        Err(error) => {
+            eprintln!("Failed to disassemble {} with error: {}", path, error);
+            false
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, SystemSet)]
 enum GameStages {
     ZoneChange,
@@ -521,6 +701,10 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
         ));
 
     // Initialise 3rd party bevy plugins
+    app.insert_resource(bevy_egui::EguiSettings {
+        scale_factor: config.interface.ui_scale as f64,
+        ..Default::default()
+    });
     app.insert_resource(bevy_rapier3d::prelude::RapierConfiguration {
         physics_pipeline_active: false,
         query_pipeline_active: true,
@@ -549,6 +733,11 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
         .insert_resource(RenderConfiguration {
             passthrough_terrain_textures: config.graphics.passthrough_terrain_textures,
             trail_effect_duration_multiplier: config.graphics.trail_effect_duration_multiplier,
+            fullscreen: matches!(config.graphics.mode, GraphicsModeConfig::Fullscreen),
+            window_size: Vec2::new(window_width, window_height),
+            disable_vsync: config.graphics.disable_vsync,
+            shadow_distance: config.graphics.shadow_distance,
+            fog_distance_multiplier: config.graphics.fog_distance_multiplier,
         })
         .insert_resource(ServerConfiguration {
             ip: config.server.ip.clone(),
@@ -560,6 +749,29 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
             preset_character_name: config.auto_login.character_name.clone(),
             auto_login: config.auto_login.enabled,
         })
+        .insert_resource(ReconnectSettings {
+            enabled: config.reconnect.enabled,
+            max_attempts: config.reconnect.max_attempts,
+            initial_delay: Duration::from_secs_f32(config.reconnect.initial_delay_seconds),
+            max_delay: Duration::from_secs_f32(config.reconnect.max_delay_seconds),
+        })
+        .insert_resource(KeyBindings::from_config(&config.keybindings))
+        .insert_resource(NameTagSettings {
+            show_all: enum_map! {
+                NameTagType::Character => config.interface.character_name_tags.show_all,
+                NameTagType::Npc => config.interface.npc_name_tags.show_all,
+                NameTagType::Monster => config.interface.monster_name_tags.show_all,
+            },
+            font_size: enum_map! {
+                NameTagType::Character => config.interface.character_name_tags.font_size,
+                NameTagType::Npc => config.interface.npc_name_tags.font_size,
+                NameTagType::Monster => config.interface.monster_name_tags.font_size,
+            },
+        })
+        .insert_resource(GameplaySettings {
+            auto_pickup: config.gameplay.auto_pickup,
+            camera_sensitivity: config.gameplay.camera_sensitivity,
+        })
         .insert_resource(SoundSettings {
             enabled: config.sound.enabled,
             global_gain: config.sound.volume.global,
@@ -578,8 +790,18 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
             RoseRenderPlugin,
             RoseScriptingPlugin,
             DebugInspectorPlugin,
+            NetworkDiagnosticsPlugin,
         ));
 
+    // The model viewer changes settings such as name tags for its own use, so do not save them
+    if let Some(path) = config
+        .path
+        .clone()
+        .filter(|_| !matches!(app_state, AppState::ModelViewer))
+    {
+        app.insert_resource(SettingsFile::new(path));
+    }
+
     // Setup state
     app.add_state::<AppState>()
         .insert_resource(State::new(app_state));
@@ -590,6 +812,7 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
         .add_event::<ClanDialogEvent>()
         .add_event::<ClientEntityEvent>()
         .add_event::<ConversationDialogEvent>()
+        .add_event::<CraftingEvent>()
         .add_event::<GameConnectionEvent>()
         .add_event::<HitEvent>()
         .add_event::<LoginEvent>()
@@ -682,6 +905,7 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
                 load_dialog_sprites_system,
                 zone_time_system.after(world_time_system),
                 directional_light_system,
+                settings_save_system,
             ),
         ),
     );
@@ -719,12 +943,15 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
             ui_debug_camera_info_system,
             ui_debug_client_entity_list_system,
             ui_debug_command_viewer_system,
+            ui_debug_conversation_trace_system,
             ui_debug_dialog_list_system,
             ui_debug_effect_list_system,
             ui_debug_entity_inspector_system,
             ui_debug_item_list_system,
             ui_debug_npc_list_system,
+            ui_debug_packet_inspector_system,
             ui_debug_physics_system,
+            ui_debug_quest_trigger_system,
             ui_debug_render_system,
             ui_debug_skill_list_system,
             ui_debug_zone_lighting_system,
@@ -843,13 +1070,24 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
         .init_resource::<UiStateWindows>()
         .init_resource::<UiStateDebugWindows>()
         .init_resource::<ClientEntityList>()
+        .init_resource::<ConnectionState>()
+        .init_resource::<LogoutState>()
+        .init_resource::<RepairState>()
+        .init_resource::<ChatIgnoreList>()
+        .init_resource::<FriendList>()
+        .init_resource::<ChatCommands>()
+        .init_resource::<ConversationTrace>()
         .init_resource::<DebugRenderConfig>()
         .init_resource::<WorldTime>()
         .init_resource::<ZoneTime>()
         .init_resource::<SelectedTarget>()
-        .init_resource::<NameTagSettings>();
+        .init_resource::<SystemFuncTable>()
+        .init_resource::<QuestObjectiveTable>()
+        .init_resource::<HelpIndex>()
+        .init_resource::<UiStateQuestTracker>();
 
-    app.add_systems(OnEnter(AppState::Game), game_state_enter_system);
+    app.add_systems(OnEnter(AppState::Game), game_state_enter_system)
+        .add_systems(OnExit(AppState::Game), game_state_exit_system);
 
     app.add_systems(
         Update,
@@ -874,6 +1112,8 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
             passive_recovery_system,
             quest_trigger_system,
             game_mouse_input_system.after(GameSystemSets::Ui),
+            auto_pickup_system.before(player_command_system),
+            community_system,
         )
             .run_if(in_state(AppState::Game)),
     );
@@ -899,12 +1139,20 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
             ),
             (
                 ui_quest_list_system,
+                ui_quest_tracker_system,
+                ui_exit_system,
+                ui_community_system,
+                ui_help_system,
+                ui_info_system,
+                ui_repair_system,
                 ui_respawn_system,
                 ui_selected_target_system,
                 ui_skill_list_system,
                 ui_skill_tree_system,
                 ui_settings_system,
                 ui_status_effects_system,
+                ui_separate_system,
+                ui_upgrade_system,
                 conversation_dialog_system,
             ),
         )
@@ -930,6 +1178,42 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
     let network_thread = std::thread::spawn(move || run_network_thread(network_thread_rx));
     app.insert_resource(NetworkThread::new(network_thread_tx.clone()));
 
+    let network_session = if let Some(replay_path) = config.session.replay_path.as_ref() {
+        match SessionReplay::load(Path::new(replay_path)) {
+            Ok(session_replay) => {
+                log::info!("Replaying network session from {}", replay_path);
+                NetworkSession::Replay(session_replay)
+            }
+            Err(error) => {
+                log::error!(
+                    "Failed to load network session replay {} with error: {}",
+                    replay_path,
+                    error
+                );
+                NetworkSession::Live
+            }
+        }
+    } else if let Some(record_path) = config.session.record_path.as_ref() {
+        match SessionRecorder::create(Path::new(record_path)) {
+            Ok(session_recorder) => {
+                log::info!("Recording network session to {}", record_path);
+                NetworkSession::Record(session_recorder)
+            }
+            Err(error) => {
+                log::error!(
+                    "Failed to create network session recording {} with error: {}",
+                    record_path,
+                    error
+                );
+                NetworkSession::Live
+            }
+        }
+    } else {
+        NetworkSession::Live
+    };
+    app.insert_resource(network_session);
+    app.init_resource::<PacketInspector>();
+
     // Run network systems before Update, so we can add/remove entities
     app.add_systems(
         PreUpdate,
@@ -937,21 +1221,43 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
             login_connection_system,
             world_connection_system,
             game_connection_system,
+            connection_state_system
+                .after(login_connection_system)
+                .after(world_connection_system)
+                .after(game_connection_system),
+            logout_system
+                .after(world_connection_system)
+                .after(game_connection_system),
         ),
     );
 
     app.add_systems(PostStartup, load_common_game_data);
 
+    let mut protocol_registry = ProtocolRegistry::default();
+    protocol_registry.register("irose", protocol::irose::network_protocol());
+    app.insert_resource(protocol_registry);
+
     if let Some(app_builder) = systems_config.add_custom_systems.take() {
         app_builder(&mut app);
     }
 
     match config.game.network_version.as_str() {
-        "irose" => {
+        "custom" => {}
+        network_version => {
+            let mut protocol_registry = app.world.resource_mut::<ProtocolRegistry>();
+            if !protocol_registry.select(network_version) {
+                panic!(
+                    "Unknown game network version {}, expected one of: {}",
+                    network_version,
+                    protocol_registry
+                        .network_versions()
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+
             app.add_systems(PostUpdate, network_thread_system);
         }
-        "custom" => {}
-        unknown => panic!("Unknown game network version {}", unknown),
     };
 
     match config.game.ui_version.as_str() {
@@ -1017,21 +1323,19 @@ fn run_client(config: &Config, app_state: AppState, mut systems_config: SystemsC
     network_thread.join().ok();
 }
 
-fn load_game_data_irose(
-    mut commands: Commands,
-    vfs_resource: Res<VfsResource>,
-    asset_server: Res<AssetServer>,
-) {
-    let string_database = rose_data_irose::get_string_database(&vfs_resource.vfs, 1)
-        .expect("Failed to load string database");
+/// Loads the irose game data databases from the VFS, also used by tests which
+/// run against real game data.
+pub fn get_game_data_irose(vfs: &VirtualFilesystem) -> GameData {
+    let string_database =
+        rose_data_irose::get_string_database(vfs, 1).expect("Failed to load string database");
 
     let items = Arc::new(
-        rose_data_irose::get_item_database(&vfs_resource.vfs, string_database.clone())
+        rose_data_irose::get_item_database(vfs, string_database.clone())
             .expect("Failed to load item database"),
     );
     let npcs = Arc::new(
         rose_data_irose::get_npc_database(
-            &vfs_resource.vfs,
+            vfs,
             string_database.clone(),
             &NpcDatabaseOptions {
                 load_frame_data: false,
@@ -1040,12 +1344,12 @@ fn load_game_data_irose(
         .expect("Failed to load npc database"),
     );
     let skills = Arc::new(
-        rose_data_irose::get_skill_database(&vfs_resource.vfs, string_database.clone())
+        rose_data_irose::get_skill_database(vfs, string_database.clone())
             .expect("Failed to load skill database"),
     );
     let character_motion_database = Arc::new(
         rose_data_irose::get_character_motion_database(
-            &vfs_resource.vfs,
+            vfs,
             &CharacterMotionDatabaseOptions {
                 load_frame_data: false,
             },
@@ -1053,19 +1357,12 @@ fn load_game_data_irose(
         .expect("Failed to load character motion list"),
     );
     let zone_list = Arc::new(
-        rose_data_irose::get_zone_list(&vfs_resource.vfs, string_database.clone())
+        rose_data_irose::get_zone_list(vfs, string_database.clone())
             .expect("Failed to load zone list"),
     );
-    let sounds = rose_data_irose::get_sound_database(&vfs_resource.vfs)
-        .expect("Failed to load sound database");
+    let sounds = rose_data_irose::get_sound_database(vfs).expect("Failed to load sound database");
 
-    asset_server.add_loader(ZoneLoader {
-        zone_list: zone_list.clone(),
-    });
-
-    commands.insert_resource(SoundCache::new(sounds.len()));
-
-    commands.insert_resource(GameData {
+    GameData {
         ability_value_calculator: rose_game_irose::data::get_ability_value_calculator(
             items.clone(),
             skills.clone(),
@@ -1076,42 +1373,37 @@ fn load_game_data_irose(
         client_strings: rose_data_irose::get_client_strings(string_database.clone())
             .expect("Failed to load client strings"),
         data_decoder: rose_data_irose::get_data_decoder(),
-        effect_database: rose_data_irose::get_effect_database(&vfs_resource.vfs)
+        effect_database: rose_data_irose::get_effect_database(vfs)
             .expect("Failed to load effect database"),
         items,
         job_class: Arc::new(
-            rose_data_irose::get_job_class_database(&vfs_resource.vfs, string_database.clone())
+            rose_data_irose::get_job_class_database(vfs, string_database.clone())
                 .expect("Failed to load job class database"),
         ),
         npcs,
         quests: Arc::new(
-            rose_data_irose::get_quest_database(&vfs_resource.vfs, string_database.clone())
+            rose_data_irose::get_quest_database(vfs, string_database.clone())
                 .expect("Failed to load quest database"),
         ),
         skills,
-        skybox: rose_data_irose::get_skybox_database(&vfs_resource.vfs)
-            .expect("Failed to load skybox database"),
+        skybox: rose_data_irose::get_skybox_database(vfs).expect("Failed to load skybox database"),
         sounds,
         status_effects: Arc::new(
-            rose_data_irose::get_status_effect_database(&vfs_resource.vfs, string_database.clone())
+            rose_data_irose::get_status_effect_database(vfs, string_database.clone())
                 .expect("Failed to load status effect database"),
         ),
         string_database,
         zone_list,
-        ltb_event: vfs_resource
-            .vfs
+        ltb_event: vfs
             .read_file::<LtbFile, _>("3DDATA/EVENT/ULNGTB_CON.LTB")
             .expect("Failed to load event language file"),
-        zsc_event_object: vfs_resource
-            .vfs
+        zsc_event_object: vfs
             .read_file::<ZscFile, _>("3DDATA/SPECIAL/EVENT_OBJECT.ZSC")
             .expect("Failed to load 3DDATA/SPECIAL/EVENT_OBJECT.ZSC"),
-        zsc_special_object: vfs_resource
-            .vfs
+        zsc_special_object: vfs
             .read_file::<ZscFile, _>("3DDATA/SPECIAL/LIST_DECO_SPECIAL.ZSC")
             .expect("Failed to load 3DDATA/SPECIAL/LIST_DECO_SPECIAL.ZSC"),
-        stb_morph_object: vfs_resource
-            .vfs
+        stb_morph_object: vfs
             .read_file::<StbFile, _>("3DDATA/STB/LIST_MORPH_OBJECT.STB")
             .expect("Failed to load 3DDATA/STB/LIST_MORPH_OBJECT.STB"),
         character_select_positions: vec![
@@ -1131,7 +1423,25 @@ fn load_game_data_irose(
                 .with_rotation(Quat::from_xyzw(0.0, 1.0, 0.0, 0.0))
                 .with_scale(Vec3::new(1.5, 1.5, 1.5)),
         ],
+    }
+}
+
+fn load_game_data_irose(
+    mut commands: Commands,
+    vfs_resource: Res<VfsResource>,
+    asset_server: Res<AssetServer>,
+) {
+    let game_data = get_game_data_irose(&vfs_resource.vfs);
+
+    asset_server.add_loader(ZoneLoader {
+        zone_list: game_data.zone_list.clone(),
     });
+
+    commands.insert_resource(SoundCache::new(game_data.sounds.len()));
+    commands.insert_resource(SystemFuncTable::load(&vfs_resource.vfs));
+    commands.insert_resource(QuestObjectiveTable::load(&vfs_resource.vfs, &game_data));
+    commands.insert_resource(HelpIndex::load(&vfs_resource.vfs));
+    commands.insert_resource(game_data);
 }
 
 fn load_common_game_data(
@@ -2,8 +2,8 @@ use std::path::Path;
 
 use rose_data::ZoneId;
 use rose_offline_client::{
-    load_config, run_game, run_model_viewer, run_zone_viewer, Config, FilesystemDeviceConfig,
-    SystemsConfig,
+    default_config_path, load_config, run_game, run_lua_disassembler, run_model_viewer,
+    run_zone_viewer, Config, FilesystemDeviceConfig, SystemsConfig,
 };
 
 fn main() {
@@ -108,6 +108,18 @@ fn main() {
                 .help("If --auto-login is set, this will also auto login to the given character")
                 .takes_value(true),
         )
+        .arg(
+            clap::Arg::new("record-session")
+                .long("record-session")
+                .help("Record all packets received from the server to the given session file")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("replay-session")
+                .long("replay-session")
+                .help("Replay a recorded session file instead of connecting to a server")
+                .takes_value(true),
+        )
         .arg(
             clap::Arg::new("auto-login")
                 .long("auto-login")
@@ -134,8 +146,7 @@ fn main() {
             clap::Arg::new("network-version")
             .long("network-version")
             .takes_value(true)
-                .value_parser(["irose"])
-                .help("Select which game version to use for network."),
+                .help("Select which game version to use for network, must match a protocol in the protocol registry such as irose."),
         )
         .arg(
             clap::Arg::new("ui-version")
@@ -143,13 +154,34 @@ fn main() {
             .takes_value(true)
                 .value_parser(["irose"])
                 .help("Select which game version to use for ui."),
+        )
+        .subcommand(
+            clap::Command::new("disassemble-lua")
+                .about("Print the disassembly of a .CON or compiled lua script from the VFS")
+                .arg(
+                    clap::Arg::new("path")
+                        .help("VFS path of the script, e.g. 3DDATA/EVENT/OBJECT001.CON")
+                        .required(true)
+                        .takes_value(true),
+                ),
         );
     let matches = command.get_matches();
 
-    let mut config = matches
-        .value_of("config")
-        .map(Path::new)
-        .map_or_else(Config::default, load_config);
+    let mut config = if let Some(path) = matches.value_of("config") {
+        load_config(Path::new(path))
+    } else if let Some(path) = default_config_path() {
+        if path.exists() {
+            load_config(&path)
+        } else {
+            // Settings changed in game will create the file
+            Config {
+                path: Some(path),
+                ..Default::default()
+            }
+        }
+    } else {
+        Config::default()
+    };
 
     if let Some(ip) = matches.value_of("ip") {
         config.server.ip = ip.into();
@@ -189,6 +221,14 @@ fn main() {
         config.auto_login.character_name = Some(character_name.into());
     }
 
+    if let Some(path) = matches.value_of("record-session") {
+        config.session.record_path = Some(path.into());
+    }
+
+    if let Some(path) = matches.value_of("replay-session") {
+        config.session.replay_path = Some(path.into());
+    }
+
     if matches.is_present("disable-vsync") {
         config.graphics.disable_vsync = true;
     }
@@ -255,7 +295,13 @@ fn main() {
             .push(FilesystemDeviceConfig::Vfs("data.idx".into()));
     }
 
-    if matches.is_present("model-viewer") {
+    if let Some(disassemble_matches) = matches.subcommand_matches("disassemble-lua") {
+        if let Some(path) = disassemble_matches.value_of("path") {
+            if !run_lua_disassembler(&config, path) {
+                std::process::exit(1);
+            }
+        }
+    } else if matches.is_present("model-viewer") {
         run_model_viewer(&config);
     } else if matches.is_present("zone-viewer") {
         run_zone_viewer(
@@ -0,0 +1,12 @@
+/// Chat sent to a channel other than local chat.
+///
+/// `ClientMessage` only has `Chat` for local chat, so these are sent to the
+/// game client using `GameConnection::chat_message_tx`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChatMessage {
+    Shout { text: String },
+    Whisper { target: String, text: String },
+    Party { text: String },
+    Clan { text: String },
+    Allied { text: String },
+}
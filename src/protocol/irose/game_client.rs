@@ -7,7 +7,7 @@ use rose_data::{QuestTriggerHash, SkillId};
 use rose_game_common::{
     components::MoveMode,
     messages::{
-        client::ClientMessage,
+        client::{ClientMessage, LogoutRequest},
         server::{
             CharacterData, CharacterDataItems, ConnectionRequestError, ServerMessage,
             SpawnEntityCharacter,
@@ -23,14 +23,15 @@ use rose_network_irose::{
         PacketClientChangeVehiclePart, PacketClientChat, PacketClientClanCommand,
         PacketClientConnectRequest, PacketClientCraftItem, PacketClientDropItemFromInventory,
         PacketClientEmote, PacketClientIncreaseBasicStat, PacketClientJoinZone,
-        PacketClientLevelUpSkill, PacketClientMove, PacketClientMoveCollision,
-        PacketClientMoveToggle, PacketClientMoveToggleType, PacketClientNpcStoreTransaction,
-        PacketClientPartyReply, PacketClientPartyRequest, PacketClientPartyUpdateRules,
-        PacketClientPersonalStoreBuyItem, PacketClientPersonalStoreListItems,
-        PacketClientPickupItemDrop, PacketClientQuestRequest, PacketClientQuestRequestType,
-        PacketClientRepairItemUsingItem, PacketClientRepairItemUsingNpc, PacketClientReviveRequest,
-        PacketClientSetHotbarSlot, PacketClientSetReviveZone, PacketClientUseItem,
-        PacketClientWarpGateRequest,
+        PacketClientLevelUpSkill, PacketClientLogoutRequest, PacketClientMove,
+        PacketClientMoveCollision, PacketClientMoveToggle, PacketClientMoveToggleType,
+        PacketClientNpcStoreTransaction, PacketClientPartyReply, PacketClientPartyRequest,
+        PacketClientPartyUpdateRules, PacketClientPersonalStoreBuyItem,
+        PacketClientPersonalStoreListItems, PacketClientPickupItemDrop, PacketClientQuestRequest,
+        PacketClientQuestRequestType, PacketClientRepairItemUsingItem,
+        PacketClientRepairItemUsingNpc, PacketClientReturnToCharacterSelect,
+        PacketClientReviveRequest, PacketClientSetHotbarSlot, PacketClientSetReviveZone,
+        PacketClientUseItem, PacketClientWarpGateRequest,
     },
     game_server_packets::{
         ConnectResult, PacketConnectionReply, PacketServerAdjustPosition, PacketServerAnnounceChat,
@@ -65,13 +66,27 @@ use rose_network_irose::{
     ClientPacketCodec, IROSE_112_TABLE,
 };
 
-use crate::protocol::{ProtocolClient, ProtocolClientError};
+use crate::protocol::{
+    irose::{
+        game_client_packets::{
+            PacketClientAlliedChat, PacketClientClanChat, PacketClientPartyChat,
+            PacketClientShoutChat, PacketClientWhisper,
+        },
+        game_server_packets::PacketServerRepairedItemUsingItem,
+    },
+    ChatMessage, InspectedConnection, NetworkStatistics, PacketInspectorConnection, ProtocolClient,
+    ProtocolClientError, ServerMessageSender, SessionConnectionRecorder,
+};
 
 pub struct GameClient {
     server_address: SocketAddr,
     client_message_rx: tokio::sync::mpsc::UnboundedReceiver<ClientMessage>,
-    server_message_tx: crossbeam_channel::Sender<ServerMessage>,
+    chat_message_rx: tokio::sync::mpsc::UnboundedReceiver<ChatMessage>,
+    server_message_tx: ServerMessageSender,
     packet_codec: Box<dyn PacketCodec + Send + Sync>,
+    session_recorder: Option<SessionConnectionRecorder>,
+    packet_inspector: Option<PacketInspectorConnection>,
+    network_statistics: Option<NetworkStatistics>,
 }
 
 impl GameClient {
@@ -79,13 +94,18 @@ impl GameClient {
         server_address: SocketAddr,
         packet_codec_seed: u32,
         client_message_rx: tokio::sync::mpsc::UnboundedReceiver<ClientMessage>,
+        chat_message_rx: tokio::sync::mpsc::UnboundedReceiver<ChatMessage>,
         server_message_tx: crossbeam_channel::Sender<ServerMessage>,
     ) -> Self {
         Self {
             server_address,
             client_message_rx,
-            server_message_tx,
+            chat_message_rx,
+            server_message_tx: ServerMessageSender::new(server_message_tx),
             packet_codec: Box::new(ClientPacketCodec::init(&IROSE_112_TABLE, packet_codec_seed)),
+            session_recorder: None,
+            packet_inspector: None,
+            network_statistics: None,
         }
     }
 
@@ -1104,10 +1124,10 @@ impl GameClient {
                 }
             }
             Some(ServerPackets::RepairedItemUsingItem) => {
-                log::info!(
-                    "Unimplemented ServerPackets::RepairedItemUsingItem {:?}",
-                    packet
-                );
+                let PacketServerRepairedItemUsingItem { items } = packet.try_into()?;
+                self.server_message_tx
+                    .send(ServerMessage::UpdateInventory { items, money: None })
+                    .ok();
             }
             None => log::info!("Unhandled GameClient packet {:?}", packet),
         }
@@ -1117,7 +1137,7 @@ impl GameClient {
 
     async fn handle_client_message(
         &self,
-        connection: &mut Connection<'_>,
+        connection: &mut InspectedConnection<'_>,
         message: ClientMessage,
     ) -> Result<(), anyhow::Error> {
         match message {
@@ -1580,12 +1600,41 @@ impl GameClient {
                     }))
                     .await?;
             }
+            ClientMessage::LogoutRequest(LogoutRequest::Logout) => {
+                connection
+                    .write_packet(Packet::from(&PacketClientLogoutRequest))
+                    .await?;
+            }
+            ClientMessage::LogoutRequest(LogoutRequest::ReturnToCharacterSelect) => {
+                connection
+                    .write_packet(Packet::from(&PacketClientReturnToCharacterSelect))
+                    .await?;
+            }
             unimplemented => {
                 log::info!("Unimplemented GameClient ClientMessage {:?}", unimplemented);
             }
         }
         Ok(())
     }
+
+    async fn handle_chat_message(
+        &self,
+        connection: &mut InspectedConnection<'_>,
+        message: ChatMessage,
+    ) -> Result<(), anyhow::Error> {
+        let packet = match message {
+            ChatMessage::Shout { ref text } => Packet::from(&PacketClientShoutChat { text }),
+            ChatMessage::Whisper {
+                ref target,
+                ref text,
+            } => Packet::from(&PacketClientWhisper { target, text }),
+            ChatMessage::Party { ref text } => Packet::from(&PacketClientPartyChat { text }),
+            ChatMessage::Clan { ref text } => Packet::from(&PacketClientClanChat { text }),
+            ChatMessage::Allied { ref text } => Packet::from(&PacketClientAlliedChat { text }),
+        };
+        connection.write_packet(packet).await?;
+        Ok(())
+    }
 }
 
-implement_protocol_client! { GameClient }
+implement_protocol_client! { GameClient, chat_message_rx }
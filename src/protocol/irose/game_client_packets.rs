@@ -0,0 +1,73 @@
+use rose_network_common::{Packet, PacketWriter};
+
+// Chat packets which are not provided by rose_network_irose
+#[derive(Copy, Clone)]
+enum ClientChatPackets {
+    Whisper = 0x784,
+    ShoutChat = 0x785,
+    PartyChat = 0x786,
+    ClanChat = 0x787,
+    AlliedChat = 0x788,
+}
+
+pub struct PacketClientWhisper<'a> {
+    pub target: &'a str,
+    pub text: &'a str,
+}
+
+impl<'a> From<&'a PacketClientWhisper<'a>> for Packet {
+    fn from(packet: &'a PacketClientWhisper<'a>) -> Self {
+        let mut writer = PacketWriter::new(ClientChatPackets::Whisper as u16);
+        writer.add_null_terminated_utf8(packet.target);
+        writer.add_null_terminated_utf8(packet.text);
+        writer.into()
+    }
+}
+
+pub struct PacketClientShoutChat<'a> {
+    pub text: &'a str,
+}
+
+impl<'a> From<&'a PacketClientShoutChat<'a>> for Packet {
+    fn from(packet: &'a PacketClientShoutChat<'a>) -> Self {
+        let mut writer = PacketWriter::new(ClientChatPackets::ShoutChat as u16);
+        writer.add_null_terminated_utf8(packet.text);
+        writer.into()
+    }
+}
+
+pub struct PacketClientPartyChat<'a> {
+    pub text: &'a str,
+}
+
+impl<'a> From<&'a PacketClientPartyChat<'a>> for Packet {
+    fn from(packet: &'a PacketClientPartyChat<'a>) -> Self {
+        let mut writer = PacketWriter::new(ClientChatPackets::PartyChat as u16);
+        writer.add_null_terminated_utf8(packet.text);
+        writer.into()
+    }
+}
+
+pub struct PacketClientClanChat<'a> {
+    pub text: &'a str,
+}
+
+impl<'a> From<&'a PacketClientClanChat<'a>> for Packet {
+    fn from(packet: &'a PacketClientClanChat<'a>) -> Self {
+        let mut writer = PacketWriter::new(ClientChatPackets::ClanChat as u16);
+        writer.add_null_terminated_utf8(packet.text);
+        writer.into()
+    }
+}
+
+pub struct PacketClientAlliedChat<'a> {
+    pub text: &'a str,
+}
+
+impl<'a> From<&'a PacketClientAlliedChat<'a>> for Packet {
+    fn from(packet: &'a PacketClientAlliedChat<'a>) -> Self {
+        let mut writer = PacketWriter::new(ClientChatPackets::AlliedChat as u16);
+        writer.add_null_terminated_utf8(packet.text);
+        writer.into()
+    }
+}
@@ -0,0 +1,33 @@
+use rose_data::Item;
+use rose_game_common::components::ItemSlot;
+use rose_network_common::{Packet, PacketError, PacketReader};
+use rose_network_irose::{
+    common_packets::{decode_item_slot, PacketReadItems},
+    game_server_packets::ServerPackets,
+};
+
+pub struct PacketServerRepairedItemUsingItem {
+    pub items: Vec<(ItemSlot, Option<Item>)>,
+}
+
+impl TryFrom<&Packet> for PacketServerRepairedItemUsingItem {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, Self::Error> {
+        if packet.command != ServerPackets::RepairedItemUsingItem as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        let num_items = reader.read_u8()? as usize;
+        let mut items = Vec::with_capacity(num_items);
+        for _ in 0..num_items {
+            let item_slot =
+                decode_item_slot(reader.read_u8()? as usize).ok_or(PacketError::InvalidPacket)?;
+            let item = reader.read_item_full()?;
+            items.push((item_slot, item));
+        }
+
+        Ok(Self { items })
+    }
+}
@@ -22,13 +22,19 @@ use rose_network_irose::{
     ClientPacketCodec, IROSE_112_TABLE,
 };
 
-use crate::protocol::{ProtocolClient, ProtocolClientError};
+use crate::protocol::{
+    InspectedConnection, NetworkStatistics, PacketInspectorConnection, ProtocolClient,
+    ProtocolClientError, ServerMessageSender, SessionConnectionRecorder,
+};
 
 pub struct LoginClient {
     server_address: SocketAddr,
     client_message_rx: tokio::sync::mpsc::UnboundedReceiver<ClientMessage>,
-    server_message_tx: crossbeam_channel::Sender<ServerMessage>,
+    server_message_tx: ServerMessageSender,
     packet_codec: Box<dyn PacketCodec + Send + Sync>,
+    session_recorder: Option<SessionConnectionRecorder>,
+    packet_inspector: Option<PacketInspectorConnection>,
+    network_statistics: Option<NetworkStatistics>,
 }
 
 impl LoginClient {
@@ -40,8 +46,11 @@ impl LoginClient {
         Self {
             server_address,
             client_message_rx,
-            server_message_tx,
+            server_message_tx: ServerMessageSender::new(server_message_tx),
             packet_codec: Box::new(ClientPacketCodec::default(&IROSE_112_TABLE)),
+            session_recorder: None,
+            packet_inspector: None,
+            network_statistics: None,
         }
     }
 
@@ -129,7 +138,7 @@ impl LoginClient {
 
     async fn handle_client_message(
         &self,
-        connection: &mut Connection<'_>,
+        connection: &mut InspectedConnection<'_>,
         message: ClientMessage,
     ) -> Result<(), anyhow::Error> {
         match message {
@@ -0,0 +1,741 @@
+use bevy::math::Vec3;
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+use tokio::net::{TcpListener, TcpStream};
+
+use rose_data::{WorldTicks, ZoneId};
+use rose_game_common::{
+    components::{
+        BasicStats, CharacterInfo, Equipment, ExperiencePoints, HealthPoints, Hotbar, Inventory,
+        Level, ManaPoints, SkillList, SkillPoints, Stamina, StatPoints, Team, UnionMembership,
+        MAX_STAMINA,
+    },
+    messages::{
+        server::{CharacterData, CharacterDataItems, CharacterListItem, ServerMessage},
+        ClientEntityId,
+    },
+};
+use rose_network_common::{Connection, Packet, PacketCodec};
+use rose_network_irose::{
+    game_client_packets::{PacketClientAttack, PacketClientMove},
+    game_server_packets::{
+        self, PacketServerAttackEntity, PacketServerCharacterInventory,
+        PacketServerCharacterQuestData, PacketServerJoinZone, PacketServerMoveEntity,
+        PacketServerSelectCharacter,
+    },
+    login_server_packets::{
+        self, ChannelListItem, ConnectionResult, LoginResult, PacketServerChannelList,
+        PacketServerLoginReply, PacketServerSelectServer, SelectServerResult,
+    },
+    world_server_packets::{
+        self, ConnectResult, PacketServerCharacterList, PacketServerMoveServer,
+    },
+    ServerPacketCodec, IROSE_112_TABLE,
+};
+
+use crate::protocol::SessionConnectionType;
+
+/// The irose client packet commands answered by [`MockServerScript::default_session`].
+#[derive(Copy, Clone, Debug)]
+#[repr(u16)]
+pub enum MockClientPackets {
+    LoginConnect = 0x703,
+    ChannelList = 0x704,
+    LoginRequest = 0x708,
+    SelectServer = 0x70a,
+    ConnectRequest = 0x70b,
+    CharacterList = 0x712,
+    SelectCharacter = 0x715,
+    JoinZone = 0x753,
+    Attack = 0x798,
+    Move = 0x79a,
+}
+
+pub type MockServerResponder =
+    Box<dyn Fn(&MockServerAddresses, &Packet) -> Vec<Packet> + Send + Sync>;
+
+/// The addresses the mock login, world and game servers are listening on,
+/// passed to every responder so scripted join server / move server replies
+/// can point the client at the next mock server.
+#[derive(Copy, Clone, Debug)]
+pub struct MockServerAddresses {
+    pub login: SocketAddr,
+    pub world: SocketAddr,
+    pub game: SocketAddr,
+}
+
+struct MockServerRule {
+    connection_type: SessionConnectionType,
+    client_command: u16,
+    responder: MockServerResponder,
+}
+
+/// A list of scripted responses to client packets, rules are matched by the
+/// connection they were received on and the client packet command.
+#[derive(Default)]
+pub struct MockServerScript {
+    rules: Vec<MockServerRule>,
+}
+
+impl MockServerScript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn on<F>(
+        mut self,
+        connection_type: SessionConnectionType,
+        client_command: u16,
+        responder: F,
+    ) -> Self
+    where
+        F: Fn(&MockServerAddresses, &Packet) -> Vec<Packet> + Send + Sync + 'static,
+    {
+        self.rules.push(MockServerRule {
+            connection_type,
+            client_command,
+            responder: Box::new(responder),
+        });
+        self
+    }
+
+    /// As [`MockServerScript::on`] but the responder returns typed server
+    /// messages, which are encoded with the irose server packets for the
+    /// connection the client packet was received on.
+    pub fn on_message<F>(
+        self,
+        connection_type: SessionConnectionType,
+        client_command: u16,
+        responder: F,
+    ) -> Self
+    where
+        F: Fn(&MockServerAddresses, &Packet) -> Vec<ServerMessage> + Send + Sync + 'static,
+    {
+        self.on(connection_type, client_command, move |addresses, packet| {
+            responder(addresses, packet)
+                .into_iter()
+                .filter_map(
+                    |message| match encode_server_message(connection_type, message) {
+                        Ok(packet) => Some(packet),
+                        Err(message) => {
+                            log::warn!(
+                                "Mock {:?} server can not encode {:?}",
+                                connection_type,
+                                message
+                            );
+                            None
+                        }
+                    },
+                )
+                .collect()
+        })
+    }
+
+    /// A script which accepts any account, lists a single server and channel,
+    /// a single character and then lets that character join zone 1, where
+    /// every move and attack is accepted as requested. The world and game
+    /// servers must be spawned with the same `packet_codec_seed`.
+    pub fn default_session(packet_codec_seed: u32) -> Self {
+        Self::new()
+            .on_message(
+                SessionConnectionType::Login,
+                MockClientPackets::LoginConnect as u16,
+                |_, _| {
+                    vec![ServerMessage::ConnectionRequestSuccess {
+                        packet_sequence_id: 0,
+                    }]
+                },
+            )
+            .on_message(
+                SessionConnectionType::Login,
+                MockClientPackets::LoginRequest as u16,
+                |_, _| {
+                    vec![ServerMessage::LoginSuccess {
+                        server_list: vec![(MOCK_SERVER_ID as u32, "Mock".to_string())],
+                    }]
+                },
+            )
+            .on_message(
+                SessionConnectionType::Login,
+                MockClientPackets::ChannelList as u16,
+                |_, _| {
+                    vec![ServerMessage::ChannelList {
+                        server_id: MOCK_SERVER_ID,
+                        channels: vec![(MOCK_CHANNEL_ID, "Mock Channel".to_string())],
+                    }]
+                },
+            )
+            .on_message(
+                SessionConnectionType::Login,
+                MockClientPackets::SelectServer as u16,
+                move |addresses, _| {
+                    vec![ServerMessage::JoinServerSuccess {
+                        login_token: MOCK_LOGIN_TOKEN,
+                        packet_codec_seed,
+                        ip: addresses.world.ip().to_string(),
+                        port: addresses.world.port(),
+                    }]
+                },
+            )
+            .on_message(
+                SessionConnectionType::World,
+                MockClientPackets::ConnectRequest as u16,
+                |_, _| {
+                    vec![ServerMessage::ConnectionRequestSuccess {
+                        packet_sequence_id: 0,
+                    }]
+                },
+            )
+            .on_message(
+                SessionConnectionType::World,
+                MockClientPackets::CharacterList as u16,
+                |_, _| {
+                    let character = mock_character_data();
+                    vec![ServerMessage::CharacterList {
+                        character_list: vec![CharacterListItem {
+                            info: character.character_info,
+                            level: character.level,
+                            delete_time: None,
+                            equipment: character.equipment,
+                        }],
+                    }]
+                },
+            )
+            .on_message(
+                SessionConnectionType::World,
+                MockClientPackets::SelectCharacter as u16,
+                move |addresses, _| {
+                    vec![ServerMessage::SelectCharacterSuccess {
+                        login_token: MOCK_LOGIN_TOKEN,
+                        packet_codec_seed,
+                        ip: addresses.game.ip().to_string(),
+                        port: addresses.game.port(),
+                    }]
+                },
+            )
+            .on_message(
+                SessionConnectionType::Game,
+                MockClientPackets::ConnectRequest as u16,
+                |_, _| {
+                    vec![
+                        ServerMessage::ConnectionRequestSuccess {
+                            packet_sequence_id: 0,
+                        },
+                        ServerMessage::CharacterData {
+                            data: Box::new(mock_character_data()),
+                        },
+                        ServerMessage::CharacterDataItems {
+                            data: Box::new(CharacterDataItems {
+                                inventory: Inventory::default(),
+                                equipment: Equipment::new(),
+                            }),
+                        },
+                        ServerMessage::CharacterDataQuest {
+                            quest_state: Box::default(),
+                        },
+                    ]
+                },
+            )
+            .on_message(
+                SessionConnectionType::Game,
+                MockClientPackets::JoinZone as u16,
+                |_, _| {
+                    let character = mock_character_data();
+                    vec![ServerMessage::JoinZone {
+                        entity_id: MOCK_ENTITY_ID,
+                        experience_points: character.experience_points,
+                        team: Team::default_character(),
+                        health_points: character.health_points,
+                        mana_points: character.mana_points,
+                        world_ticks: WorldTicks(0),
+                        craft_rate: 100,
+                        world_price_rate: 100,
+                        item_price_rate: 100,
+                        town_price_rate: 100,
+                    }]
+                },
+            )
+            .on_message(
+                SessionConnectionType::Game,
+                MockClientPackets::Move as u16,
+                |_, packet| {
+                    let Ok(request) = PacketClientMove::try_from(packet) else {
+                        return Vec::new();
+                    };
+
+                    // The mock server does not track the character position, the
+                    // client does not use the distance so it is left as 0
+                    vec![ServerMessage::MoveEntity {
+                        entity_id: MOCK_ENTITY_ID,
+                        target_entity_id: request.target_entity_id,
+                        distance: 0,
+                        x: request.x,
+                        y: request.y,
+                        z: request.z,
+                        move_mode: None,
+                    }]
+                },
+            )
+            .on_message(
+                SessionConnectionType::Game,
+                MockClientPackets::Attack as u16,
+                |_, packet| {
+                    let Ok(request) = PacketClientAttack::try_from(packet) else {
+                        return Vec::new();
+                    };
+
+                    let position = mock_character_data().position;
+                    vec![ServerMessage::AttackEntity {
+                        entity_id: MOCK_ENTITY_ID,
+                        target_entity_id: request.target_entity_id,
+                        distance: 0,
+                        x: position.x,
+                        y: position.y,
+                        z: position.z as u16,
+                    }]
+                },
+            )
+    }
+
+    fn respond(
+        &self,
+        connection_type: SessionConnectionType,
+        addresses: &MockServerAddresses,
+        packet: &Packet,
+    ) -> Option<Vec<Packet>> {
+        self.rules
+            .iter()
+            .find(|rule| {
+                rule.connection_type == connection_type && rule.client_command == packet.command
+            })
+            .map(|rule| (rule.responder)(addresses, packet))
+    }
+}
+
+pub const MOCK_SERVER_ID: usize = 1;
+pub const MOCK_CHANNEL_ID: u8 = 1;
+pub const MOCK_CHARACTER_NAME: &str = "MockCharacter";
+pub const MOCK_ENTITY_ID: ClientEntityId = ClientEntityId(1);
+const MOCK_LOGIN_TOKEN: u32 = 1;
+
+fn mock_character_data() -> CharacterData {
+    CharacterData {
+        character_info: CharacterInfo {
+            name: MOCK_CHARACTER_NAME.to_string(),
+            gender: 0,
+            race: 0,
+            birth_stone: 0,
+            job: 0,
+            face: 1,
+            hair: 0,
+            rank: 0,
+            fame: 0,
+            fame_b: 0,
+            fame_g: 0,
+            revive_zone_id: ZoneId::new(1).unwrap(),
+            revive_position: Vec3::new(520000.0, 520000.0, 0.0),
+            unique_id: 1,
+        },
+        position: Vec3::new(520000.0, 520000.0, 0.0),
+        zone_id: ZoneId::new(1).unwrap(),
+        basic_stats: BasicStats::default(),
+        level: Level::new(1),
+        equipment: Equipment::new(),
+        experience_points: ExperiencePoints::default(),
+        skill_list: SkillList::default(),
+        hotbar: Hotbar::default(),
+        health_points: HealthPoints::new(100),
+        mana_points: ManaPoints::new(100),
+        stat_points: StatPoints::default(),
+        skill_points: SkillPoints::default(),
+        union_membership: UnionMembership::default(),
+        stamina: Stamina::new(MAX_STAMINA),
+    }
+}
+
+/// Encodes the server messages used by the mock server scripts, messages
+/// which are not sent on `connection_type` are returned as the error.
+fn encode_server_message(
+    connection_type: SessionConnectionType,
+    message: ServerMessage,
+) -> Result<Packet, ServerMessage> {
+    match (connection_type, message) {
+        (
+            SessionConnectionType::Login,
+            ServerMessage::ConnectionRequestSuccess { packet_sequence_id },
+        ) => Ok(Packet::from(&login_server_packets::PacketConnectionReply {
+            status: ConnectionResult::Accepted,
+            packet_sequence_id,
+        })),
+        (SessionConnectionType::Login, ServerMessage::LoginSuccess { server_list }) => {
+            Ok(Packet::from(&PacketServerLoginReply {
+                result: LoginResult::Ok,
+                rights: 0x800,
+                group: 0,
+                servers: server_list,
+            }))
+        }
+        (
+            SessionConnectionType::Login,
+            ServerMessage::ChannelList {
+                server_id,
+                channels,
+            },
+        ) => Ok(Packet::from(&PacketServerChannelList {
+            server_id,
+            channels: channels
+                .iter()
+                .map(|(id, name)| ChannelListItem {
+                    id: *id,
+                    low_age: 0,
+                    high_age: 0,
+                    region_percent: 0,
+                    name,
+                })
+                .collect(),
+        })),
+        (
+            SessionConnectionType::Login,
+            ServerMessage::JoinServerSuccess {
+                login_token,
+                packet_codec_seed,
+                ip,
+                port,
+            },
+        ) => Ok(Packet::from(&PacketServerSelectServer {
+            result: SelectServerResult::Ok,
+            login_token,
+            packet_codec_seed,
+            ip: &ip,
+            port,
+        })),
+        (
+            SessionConnectionType::World,
+            ServerMessage::ConnectionRequestSuccess { packet_sequence_id },
+        ) => Ok(Packet::from(&world_server_packets::PacketConnectionReply {
+            result: ConnectResult::Ok,
+            packet_sequence_id,
+            pay_flags: 0xff,
+        })),
+        (SessionConnectionType::World, ServerMessage::CharacterList { character_list }) => {
+            Ok(Packet::from(&PacketServerCharacterList {
+                characters: character_list,
+            }))
+        }
+        (
+            SessionConnectionType::World,
+            ServerMessage::SelectCharacterSuccess {
+                login_token,
+                packet_codec_seed,
+                ip,
+                port,
+            },
+        ) => Ok(Packet::from(&PacketServerMoveServer {
+            login_token,
+            packet_codec_seed,
+            ip: &ip,
+            port,
+        })),
+        (
+            SessionConnectionType::Game,
+            ServerMessage::ConnectionRequestSuccess { packet_sequence_id },
+        ) => Ok(Packet::from(&game_server_packets::PacketConnectionReply {
+            result: ConnectResult::Ok,
+            packet_sequence_id,
+            pay_flags: 0xff,
+        })),
+        (SessionConnectionType::Game, ServerMessage::CharacterData { data }) => {
+            let data = *data;
+            Ok(Packet::from(&PacketServerSelectCharacter {
+                character_info: data.character_info,
+                position: data.position,
+                zone_id: data.zone_id,
+                basic_stats: data.basic_stats,
+                level: data.level,
+                equipment: data.equipment,
+                experience_points: data.experience_points,
+                skill_list: data.skill_list,
+                hotbar: data.hotbar,
+                health_points: data.health_points,
+                mana_points: data.mana_points,
+                stat_points: data.stat_points,
+                skill_points: data.skill_points,
+                union_membership: data.union_membership,
+                stamina: data.stamina,
+            }))
+        }
+        (SessionConnectionType::Game, ServerMessage::CharacterDataItems { data }) => {
+            let data = *data;
+            Ok(Packet::from(&PacketServerCharacterInventory {
+                inventory: data.inventory,
+                equipment: data.equipment,
+            }))
+        }
+        (SessionConnectionType::Game, ServerMessage::CharacterDataQuest { quest_state }) => {
+            Ok(Packet::from(&PacketServerCharacterQuestData {
+                quest_state: *quest_state,
+            }))
+        }
+        (
+            SessionConnectionType::Game,
+            ServerMessage::JoinZone {
+                entity_id,
+                experience_points,
+                team,
+                health_points,
+                mana_points,
+                world_ticks,
+                craft_rate,
+                world_price_rate,
+                item_price_rate,
+                town_price_rate,
+            },
+        ) => Ok(Packet::from(&PacketServerJoinZone {
+            entity_id,
+            experience_points,
+            team,
+            health_points,
+            mana_points,
+            world_ticks,
+            craft_rate,
+            world_price_rate,
+            item_price_rate,
+            town_price_rate,
+        })),
+        (
+            SessionConnectionType::Game,
+            ServerMessage::MoveEntity {
+                entity_id,
+                target_entity_id,
+                distance,
+                x,
+                y,
+                z,
+                move_mode,
+            },
+        ) => Ok(Packet::from(&PacketServerMoveEntity {
+            entity_id,
+            target_entity_id,
+            distance,
+            x,
+            y,
+            z,
+            move_mode,
+        })),
+        (
+            SessionConnectionType::Game,
+            ServerMessage::AttackEntity {
+                entity_id,
+                target_entity_id,
+                distance,
+                x,
+                y,
+                z,
+            },
+        ) => Ok(Packet::from(&PacketServerAttackEntity {
+            entity_id,
+            target_entity_id,
+            distance,
+            x,
+            y,
+            z,
+        })),
+        (_, message) => Err(message),
+    }
+}
+
+struct MockServerState {
+    script: MockServerScript,
+    addresses: MockServerAddresses,
+    packet_codec_seed: u32,
+    received_packets: Mutex<Vec<(SessionConnectionType, u16)>>,
+}
+
+/// An in-process stand in for the login, world and game servers which answers
+/// client packets from a [`MockServerScript`] over loopback sockets using the
+/// irose packet codec, intended for running the client without a real server.
+pub struct MockServer {
+    login_listener: std::net::TcpListener,
+    world_listener: std::net::TcpListener,
+    game_listener: std::net::TcpListener,
+    addresses: MockServerAddresses,
+}
+
+impl MockServer {
+    pub fn bind() -> Result<Self, std::io::Error> {
+        let login_listener = bind_loopback_listener()?;
+        let world_listener = bind_loopback_listener()?;
+        let game_listener = bind_loopback_listener()?;
+        let addresses = MockServerAddresses {
+            login: login_listener.local_addr()?,
+            world: world_listener.local_addr()?,
+            game: game_listener.local_addr()?,
+        };
+
+        Ok(Self {
+            login_listener,
+            world_listener,
+            game_listener,
+            addresses,
+        })
+    }
+
+    pub fn addresses(&self) -> MockServerAddresses {
+        self.addresses
+    }
+
+    /// Runs the mock server on a new thread, the world and game servers expect
+    /// the client to use `packet_codec_seed` which should be sent in the
+    /// scripted join server and move server replies.
+    pub fn spawn(self, script: MockServerScript, packet_codec_seed: u32) -> MockServerHandle {
+        let state = Arc::new(MockServerState {
+            script,
+            addresses: self.addresses,
+            packet_codec_seed,
+            received_packets: Mutex::new(Vec::new()),
+        });
+
+        let thread_state = state.clone();
+        std::thread::spawn(move || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap()
+                .block_on(async move {
+                    tokio::join!(
+                        run_listener(
+                            self.login_listener,
+                            SessionConnectionType::Login,
+                            thread_state.clone()
+                        ),
+                        run_listener(
+                            self.world_listener,
+                            SessionConnectionType::World,
+                            thread_state.clone()
+                        ),
+                        run_listener(
+                            self.game_listener,
+                            SessionConnectionType::Game,
+                            thread_state.clone()
+                        ),
+                    );
+                })
+        });
+
+        MockServerHandle { state }
+    }
+}
+
+fn bind_loopback_listener() -> Result<std::net::TcpListener, std::io::Error> {
+    // The listener is registered with the mock server runtime once spawned
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    listener.set_nonblocking(true)?;
+    Ok(listener)
+}
+
+pub struct MockServerHandle {
+    state: Arc<MockServerState>,
+}
+
+impl MockServerHandle {
+    pub fn addresses(&self) -> MockServerAddresses {
+        self.state.addresses
+    }
+
+    /// Every client packet command received so far, in order of arrival.
+    pub fn received_packets(&self) -> Vec<(SessionConnectionType, u16)> {
+        self.state.received_packets.lock().unwrap().clone()
+    }
+
+    pub fn has_received(
+        &self,
+        connection_type: SessionConnectionType,
+        client_command: u16,
+    ) -> bool {
+        self.state
+            .received_packets
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|received| *received == (connection_type, client_command))
+    }
+}
+
+async fn run_listener(
+    listener: std::net::TcpListener,
+    connection_type: SessionConnectionType,
+    state: Arc<MockServerState>,
+) {
+    let listener = match TcpListener::from_std(listener) {
+        Ok(listener) => listener,
+        Err(error) => {
+            log::warn!(
+                "Mock {:?} server failed to start listener: {}",
+                connection_type,
+                error
+            );
+            return;
+        }
+    };
+
+    loop {
+        match listener.accept().await {
+            Ok((socket, _)) => {
+                let state = state.clone();
+                tokio::spawn(async move {
+                    if let Err(error) = run_connection(socket, connection_type, state).await {
+                        log::debug!(
+                            "Mock {:?} server connection closed: {}",
+                            connection_type,
+                            error
+                        );
+                    }
+                });
+            }
+            Err(error) => {
+                log::warn!(
+                    "Mock {:?} server failed to accept connection: {}",
+                    connection_type,
+                    error
+                );
+                return;
+            }
+        }
+    }
+}
+
+async fn run_connection(
+    socket: TcpStream,
+    connection_type: SessionConnectionType,
+    state: Arc<MockServerState>,
+) -> Result<(), anyhow::Error> {
+    let packet_codec: Box<dyn PacketCodec + Send + Sync> = match connection_type {
+        SessionConnectionType::Login => Box::new(ServerPacketCodec::default(&IROSE_112_TABLE)),
+        SessionConnectionType::World | SessionConnectionType::Game => Box::new(
+            ServerPacketCodec::init(&IROSE_112_TABLE, state.packet_codec_seed),
+        ),
+    };
+    let mut connection = Connection::new(socket, packet_codec.as_ref());
+
+    loop {
+        let packet = connection.read_packet().await?;
+        state
+            .received_packets
+            .lock()
+            .unwrap()
+            .push((connection_type, packet.command));
+
+        if let Some(responses) = state
+            .script
+            .respond(connection_type, &state.addresses, &packet)
+        {
+            for response in responses {
+                connection.write_packet(response).await?;
+            }
+        } else {
+            log::debug!(target: "packets", "Mock {:?} server has no response for {:?}", connection_type, packet);
+        }
+    }
+}
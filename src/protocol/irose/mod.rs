@@ -1,7 +1,53 @@
 mod game_client;
+mod game_client_packets;
+mod game_server_packets;
 mod login_client;
+mod mock_server;
 mod world_client;
 
 pub use game_client::GameClient;
 pub use login_client::LoginClient;
+pub use mock_server::{
+    MockClientPackets, MockServer, MockServerAddresses, MockServerHandle, MockServerResponder,
+    MockServerScript, MOCK_CHANNEL_ID, MOCK_CHARACTER_NAME, MOCK_ENTITY_ID, MOCK_SERVER_ID,
+};
 pub use world_client::WorldClient;
+
+use crate::protocol::NetworkProtocol;
+
+pub fn network_protocol() -> NetworkProtocol {
+    NetworkProtocol {
+        login_client: Box::new(|server_address, client_message_rx, server_message_tx| {
+            Box::new(LoginClient::new(
+                server_address,
+                client_message_rx,
+                server_message_tx,
+            ))
+        }),
+        world_client: Box::new(
+            |server_address, packet_codec_seed, client_message_rx, server_message_tx| {
+                Box::new(WorldClient::new(
+                    server_address,
+                    packet_codec_seed,
+                    client_message_rx,
+                    server_message_tx,
+                ))
+            },
+        ),
+        game_client: Box::new(
+            |server_address,
+             packet_codec_seed,
+             client_message_rx,
+             chat_message_rx,
+             server_message_tx| {
+                Box::new(GameClient::new(
+                    server_address,
+                    packet_codec_seed,
+                    client_message_rx,
+                    chat_message_rx,
+                    server_message_tx,
+                ))
+            },
+        ),
+    }
+}
@@ -24,13 +24,19 @@ use rose_network_irose::{
     ClientPacketCodec, IROSE_112_TABLE,
 };
 
-use crate::protocol::{ProtocolClient, ProtocolClientError};
+use crate::protocol::{
+    InspectedConnection, NetworkStatistics, PacketInspectorConnection, ProtocolClient,
+    ProtocolClientError, ServerMessageSender, SessionConnectionRecorder,
+};
 
 pub struct WorldClient {
     server_address: SocketAddr,
     client_message_rx: tokio::sync::mpsc::UnboundedReceiver<ClientMessage>,
-    server_message_tx: crossbeam_channel::Sender<ServerMessage>,
+    server_message_tx: ServerMessageSender,
     packet_codec: Box<dyn PacketCodec + Send + Sync>,
+    session_recorder: Option<SessionConnectionRecorder>,
+    packet_inspector: Option<PacketInspectorConnection>,
+    network_statistics: Option<NetworkStatistics>,
 }
 
 impl WorldClient {
@@ -43,8 +49,11 @@ impl WorldClient {
         Self {
             server_address,
             client_message_rx,
-            server_message_tx,
+            server_message_tx: ServerMessageSender::new(server_message_tx),
             packet_codec: Box::new(ClientPacketCodec::init(&IROSE_112_TABLE, packet_codec_seed)),
+            session_recorder: None,
+            packet_inspector: None,
+            network_statistics: None,
         }
     }
 
@@ -120,7 +129,11 @@ impl WorldClient {
                 };
                 self.server_message_tx.send(message).ok();
             }
-            // ServerPackets::ReturnToCharacterSelect -> ServerMessage::ReturnToCharacterSelect
+            Some(ServerPackets::ReturnToCharacterSelect) => {
+                self.server_message_tx
+                    .send(ServerMessage::ReturnToCharacterSelect)
+                    .ok();
+            }
             _ => log::info!("Unhandled WorldClient packet {:?}", packet),
         }
 
@@ -129,7 +142,7 @@ impl WorldClient {
 
     async fn handle_client_message(
         &self,
-        connection: &mut Connection<'_>,
+        connection: &mut InspectedConnection<'_>,
         message: ClientMessage,
     ) -> Result<(), anyhow::Error> {
         match message {
@@ -12,21 +12,32 @@ pub trait ProtocolClient {
     async fn run_connection(&mut self) -> Result<(), anyhow::Error>;
 }
 
+/// Implements `ProtocolClient` and `SessionProtocolClient` for a client, the
+/// optional second argument names a `ChatMessage` receiver field which is
+/// passed to the client's `handle_chat_message`.
 #[macro_export]
 macro_rules! implement_protocol_client {
-    ( $x:ident ) => {
+    ( $x:ident $(, $chat_message_rx:ident)? ) => {
         #[async_trait]
         impl ProtocolClient for $x {
             async fn run_connection(&mut self) -> Result<(), anyhow::Error> {
                 let socket = TcpStream::connect(&self.server_address).await?;
-                let mut connection = Connection::new(socket, self.packet_codec.as_ref());
+                let mut connection = $crate::protocol::InspectedConnection::new(
+                    Connection::new(socket, self.packet_codec.as_ref()),
+                    self.packet_inspector.clone(),
+                    self.network_statistics.clone(),
+                );
 
                 loop {
                     tokio::select! {
                         packet = connection.read_packet() => {
                             match packet {
                                 Ok(packet) => {
-                                    match self.handle_packet(&packet).await {
+                                    if let Some(session_recorder) = self.session_recorder.as_ref() {
+                                        session_recorder.record_packet(&packet);
+                                    }
+
+                                    match self.handle_inspected_packet(&packet).await {
                                         Ok(_) => {},
                                         Err(error) => {
                                             log::warn!("Error {} handling packet [{:03X}] {:02x?}", error, packet.command, &packet.data[..]);
@@ -39,9 +50,32 @@ macro_rules! implement_protocol_client {
                                 }
                             }
                         },
+                        $(
+                            chat_message = self.$chat_message_rx.recv() => {
+                                if let Some(message) = chat_message {
+                                    self.handle_chat_message(&mut connection, message).await?;
+                                } else {
+                                    return Err(ProtocolClientError::ClientInitiatedDisconnect.into());
+                                }
+                            }
+                        )?
                         server_message = self.client_message_rx.recv() => {
                             if let Some(message) = server_message {
-                                self.handle_client_message(&mut connection, message).await?;
+                                if let Some(packet_inspector) = self.packet_inspector.as_ref() {
+                                    packet_inspector.add_message(&message);
+                                }
+
+                                if let Some(network_statistics) = self.network_statistics.as_ref() {
+                                    network_statistics.record_client_message(&message);
+                                }
+
+                                let result = self.handle_client_message(&mut connection, message).await;
+
+                                if let Some(packet_inspector) = self.packet_inspector.as_ref() {
+                                    packet_inspector.clear_messages();
+                                }
+
+                                result?;
                             } else {
                                 return Err(ProtocolClientError::ClientInitiatedDisconnect.into());
                             }
@@ -52,7 +86,82 @@ macro_rules! implement_protocol_client {
                 // Ok(())
             }
         }
+
+        impl $x {
+            async fn handle_inspected_packet(&self, packet: &Packet) -> Result<(), anyhow::Error> {
+                let result = self.handle_packet(packet).await;
+
+                if let Some(packet_inspector) = self.packet_inspector.as_ref() {
+                    packet_inspector.record_packet($crate::protocol::PacketDirection::Received, packet);
+                    packet_inspector.clear_messages();
+                }
+
+                result
+            }
+        }
+
+        #[async_trait]
+        impl $crate::protocol::SessionProtocolClient for $x {
+            fn set_session_recorder(
+                &mut self,
+                session_recorder: $crate::protocol::SessionConnectionRecorder,
+            ) {
+                self.session_recorder = Some(session_recorder);
+            }
+
+            fn set_packet_inspector(
+                &mut self,
+                packet_inspector: $crate::protocol::PacketInspectorConnection,
+            ) {
+                self.server_message_tx.set_packet_inspector(packet_inspector.clone());
+                self.packet_inspector = Some(packet_inspector);
+            }
+
+            fn set_network_statistics(
+                &mut self,
+                network_statistics: $crate::protocol::NetworkStatistics,
+            ) {
+                self.server_message_tx.set_network_statistics(network_statistics.clone());
+                self.network_statistics = Some(network_statistics);
+            }
+
+            fn client_message_rx(
+                &mut self,
+            ) -> &mut tokio::sync::mpsc::UnboundedReceiver<ClientMessage> {
+                &mut self.client_message_rx
+            }
+
+            async fn handle_replay_packet(&self, packet: &Packet) -> Result<(), anyhow::Error> {
+                // Replayed packets do not come through InspectedConnection::read_packet
+                if let Some(network_statistics) = self.network_statistics.as_ref() {
+                    network_statistics.record_packet_received(packet.data.len());
+                }
+
+                self.handle_inspected_packet(packet).await
+            }
+        }
     };
 }
 
+mod chat_message;
 pub mod irose;
+mod network_statistics;
+mod packet_inspector;
+mod registry;
+mod session;
+
+pub use chat_message::ChatMessage;
+pub use network_statistics::{NetworkStatistics, NetworkStatisticsTotals};
+pub use packet_inspector::{
+    InspectedConnection, PacketDirection, PacketInspector, PacketInspectorConnection,
+    PacketInspectorEntry, ServerMessageSender,
+};
+pub use registry::{
+    GameClientFactory, LoginClientFactory, NetworkProtocol, NetworkProtocolClient,
+    ProtocolRegistry, ServerClientFactory,
+};
+
+pub use session::{
+    ReplayClient, SessionConnection, SessionConnectionRecorder, SessionConnectionType,
+    SessionFileError, SessionPacket, SessionProtocolClient, SessionRecorder, SessionReplay,
+};
@@ -0,0 +1,111 @@
+use bevy::prelude::Resource;
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use rose_game_common::messages::{client::ClientMessage, server::ServerMessage, ClientEntityId};
+
+/// The size of the irose packet header which is not included in packet data.
+const PACKET_HEADER_SIZE: u64 = 6;
+
+/// Ignore move echoes which take longer than this, the server most likely
+/// dropped our move and the echo is for a later one.
+const MAX_ROUND_TRIP_TIME: Duration = Duration::from_secs(10);
+
+#[derive(Default)]
+struct RoundTripState {
+    player_entity_id: Option<ClientEntityId>,
+    pending_move: Option<Instant>,
+    samples: Vec<Duration>,
+}
+
+#[derive(Default)]
+struct NetworkStatisticsState {
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    packets_sent: AtomicU64,
+    packets_received: AtomicU64,
+    round_trip: Mutex<RoundTripState>,
+}
+
+#[derive(Copy, Clone, Default)]
+pub struct NetworkStatisticsTotals {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub packets_sent: u64,
+    pub packets_received: u64,
+}
+
+/// Counts the traffic of every protocol client connection, and estimates the
+/// round trip time from the server echoing our own `Move` as a `MoveEntity`.
+#[derive(Clone, Default, Resource)]
+pub struct NetworkStatistics {
+    state: Arc<NetworkStatisticsState>,
+}
+
+impl NetworkStatistics {
+    pub fn record_packet_sent(&self, data_len: usize) {
+        self.state
+            .bytes_sent
+            .fetch_add(data_len as u64 + PACKET_HEADER_SIZE, Ordering::Relaxed);
+        self.state.packets_sent.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_packet_received(&self, data_len: usize) {
+        self.state
+            .bytes_received
+            .fetch_add(data_len as u64 + PACKET_HEADER_SIZE, Ordering::Relaxed);
+        self.state.packets_received.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_client_message(&self, message: &ClientMessage) {
+        if let ClientMessage::Move { .. } = message {
+            let mut round_trip = self.state.round_trip.lock().unwrap();
+            if round_trip.pending_move.is_none() {
+                round_trip.pending_move = Some(Instant::now());
+            }
+        }
+    }
+
+    pub fn record_server_message(&self, message: &ServerMessage) {
+        match *message {
+            ServerMessage::JoinZone { entity_id, .. } => {
+                let mut round_trip = self.state.round_trip.lock().unwrap();
+                round_trip.player_entity_id = Some(entity_id);
+                round_trip.pending_move = None;
+            }
+            ServerMessage::MoveEntity { entity_id, .. } => {
+                let mut round_trip = self.state.round_trip.lock().unwrap();
+                if round_trip.player_entity_id != Some(entity_id) {
+                    return;
+                }
+
+                if let Some(pending_move) = round_trip.pending_move.take() {
+                    let round_trip_time = pending_move.elapsed();
+                    if round_trip_time < MAX_ROUND_TRIP_TIME {
+                        round_trip.samples.push(round_trip_time);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn totals(&self) -> NetworkStatisticsTotals {
+        NetworkStatisticsTotals {
+            bytes_sent: self.state.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: self.state.bytes_received.load(Ordering::Relaxed),
+            packets_sent: self.state.packets_sent.load(Ordering::Relaxed),
+            packets_received: self.state.packets_received.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Takes the round trip times measured since the last call.
+    pub fn take_round_trip_times(&self) -> Vec<Duration> {
+        std::mem::take(&mut self.state.round_trip.lock().unwrap().samples)
+    }
+}
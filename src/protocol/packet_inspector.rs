@@ -0,0 +1,232 @@
+use bevy::prelude::Resource;
+use bytes::Bytes;
+use std::{
+    collections::VecDeque,
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use rose_game_common::messages::server::ServerMessage;
+use rose_network_common::{Connection, Packet};
+
+use crate::protocol::{NetworkStatistics, SessionConnectionType};
+
+/// Limit the number of captured packets waiting to be collected, in case the
+/// inspector window stops collecting while capture is still enabled.
+const MAX_PENDING_ENTRIES: usize = 4096;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PacketDirection {
+    Sent,
+    Received,
+}
+
+#[derive(Clone)]
+pub struct PacketInspectorEntry {
+    pub id: u64,
+    pub time: Duration,
+    pub connection_type: SessionConnectionType,
+    pub direction: PacketDirection,
+    pub command: u16,
+    pub data: Bytes,
+    pub messages: Vec<String>,
+}
+
+struct PacketInspectorState {
+    capturing: AtomicBool,
+    next_id: AtomicU64,
+    start_time: Instant,
+    entries: Mutex<VecDeque<PacketInspectorEntry>>,
+}
+
+/// Captures raw packets and their decoded messages from every protocol client
+/// for the packet inspector debug window. Nothing is captured until
+/// [`PacketInspector::set_capturing`] is enabled.
+#[derive(Clone, Resource)]
+pub struct PacketInspector {
+    state: Arc<PacketInspectorState>,
+}
+
+impl Default for PacketInspector {
+    fn default() -> Self {
+        Self {
+            state: Arc::new(PacketInspectorState {
+                capturing: AtomicBool::new(false),
+                next_id: AtomicU64::new(0),
+                start_time: Instant::now(),
+                entries: Mutex::new(VecDeque::new()),
+            }),
+        }
+    }
+}
+
+impl PacketInspector {
+    pub fn is_capturing(&self) -> bool {
+        self.state.capturing.load(Ordering::Relaxed)
+    }
+
+    pub fn set_capturing(&self, capturing: bool) {
+        self.state.capturing.store(capturing, Ordering::Relaxed);
+    }
+
+    pub fn connection(&self, connection_type: SessionConnectionType) -> PacketInspectorConnection {
+        PacketInspectorConnection {
+            inspector: self.clone(),
+            connection_type,
+            messages: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Takes all packets captured since the last call.
+    pub fn take_entries(&self) -> Vec<PacketInspectorEntry> {
+        self.state.entries.lock().unwrap().drain(..).collect()
+    }
+
+    fn push_entry(
+        &self,
+        connection_type: SessionConnectionType,
+        direction: PacketDirection,
+        packet: &Packet,
+        messages: Vec<String>,
+    ) {
+        let mut entries = self.state.entries.lock().unwrap();
+        if entries.len() >= MAX_PENDING_ENTRIES {
+            entries.pop_front();
+        }
+
+        entries.push_back(PacketInspectorEntry {
+            id: self.state.next_id.fetch_add(1, Ordering::Relaxed),
+            time: self.state.start_time.elapsed(),
+            connection_type,
+            direction,
+            command: packet.command,
+            data: packet.data.clone(),
+            messages,
+        });
+    }
+}
+
+/// Captures the packets of a single login, world or game connection.
+///
+/// Decoded messages are collected with [`PacketInspectorConnection::add_message`]
+/// and attached to every packet recorded until they are cleared, so a received
+/// packet is recorded after it has been handled and a sent packet is recorded
+/// while its client message is being handled.
+#[derive(Clone)]
+pub struct PacketInspectorConnection {
+    inspector: PacketInspector,
+    connection_type: SessionConnectionType,
+    messages: Arc<Mutex<Vec<String>>>,
+}
+
+impl PacketInspectorConnection {
+    pub fn add_message(&self, message: &impl Debug) {
+        if self.inspector.is_capturing() {
+            self.messages
+                .lock()
+                .unwrap()
+                .push(format!("{:#?}", message));
+        }
+    }
+
+    pub fn clear_messages(&self) {
+        self.messages.lock().unwrap().clear();
+    }
+
+    pub fn record_packet(&self, direction: PacketDirection, packet: &Packet) {
+        if self.inspector.is_capturing() {
+            let messages = self.messages.lock().unwrap().clone();
+            self.inspector
+                .push_entry(self.connection_type, direction, packet, messages);
+        }
+    }
+}
+
+/// Sends decoded server messages to the game, and to the packet inspector and
+/// network statistics when they are attached.
+pub struct ServerMessageSender {
+    server_message_tx: crossbeam_channel::Sender<ServerMessage>,
+    packet_inspector: Option<PacketInspectorConnection>,
+    network_statistics: Option<NetworkStatistics>,
+}
+
+impl ServerMessageSender {
+    pub fn new(server_message_tx: crossbeam_channel::Sender<ServerMessage>) -> Self {
+        Self {
+            server_message_tx,
+            packet_inspector: None,
+            network_statistics: None,
+        }
+    }
+
+    pub fn set_packet_inspector(&mut self, packet_inspector: PacketInspectorConnection) {
+        self.packet_inspector = Some(packet_inspector);
+    }
+
+    pub fn set_network_statistics(&mut self, network_statistics: NetworkStatistics) {
+        self.network_statistics = Some(network_statistics);
+    }
+
+    pub fn send(
+        &self,
+        message: ServerMessage,
+    ) -> Result<(), crossbeam_channel::SendError<ServerMessage>> {
+        if let Some(packet_inspector) = self.packet_inspector.as_ref() {
+            packet_inspector.add_message(&message);
+        }
+
+        if let Some(network_statistics) = self.network_statistics.as_ref() {
+            network_statistics.record_server_message(&message);
+        }
+
+        self.server_message_tx.send(message)
+    }
+}
+
+/// Wraps a connection so every packet read or written is also recorded by the
+/// packet inspector and network statistics.
+pub struct InspectedConnection<'a> {
+    connection: Connection<'a>,
+    packet_inspector: Option<PacketInspectorConnection>,
+    network_statistics: Option<NetworkStatistics>,
+}
+
+impl<'a> InspectedConnection<'a> {
+    pub fn new(
+        connection: Connection<'a>,
+        packet_inspector: Option<PacketInspectorConnection>,
+        network_statistics: Option<NetworkStatistics>,
+    ) -> Self {
+        Self {
+            connection,
+            packet_inspector,
+            network_statistics,
+        }
+    }
+
+    pub async fn read_packet(&mut self) -> Result<Packet, anyhow::Error> {
+        let packet = self.connection.read_packet().await?;
+
+        if let Some(network_statistics) = self.network_statistics.as_ref() {
+            network_statistics.record_packet_received(packet.data.len());
+        }
+
+        Ok(packet)
+    }
+
+    pub async fn write_packet(&mut self, packet: Packet) -> Result<(), anyhow::Error> {
+        if let Some(packet_inspector) = self.packet_inspector.as_ref() {
+            packet_inspector.record_packet(PacketDirection::Sent, &packet);
+        }
+
+        if let Some(network_statistics) = self.network_statistics.as_ref() {
+            network_statistics.record_packet_sent(packet.data.len());
+        }
+
+        Ok(self.connection.write_packet(packet).await?)
+    }
+}
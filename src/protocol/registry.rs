@@ -0,0 +1,135 @@
+use async_trait::async_trait;
+use bevy::prelude::Resource;
+use std::{collections::HashMap, net::SocketAddr};
+
+use rose_game_common::messages::{client::ClientMessage, server::ServerMessage};
+use rose_network_common::Packet;
+
+use crate::protocol::{
+    ChatMessage, NetworkStatistics, PacketInspectorConnection, ProtocolClient,
+    SessionConnectionRecorder, SessionProtocolClient,
+};
+
+/// A protocol client which can be used for a login, world or game connection.
+pub trait NetworkProtocolClient: ProtocolClient + SessionProtocolClient + Send + Sync {}
+
+impl<T: ProtocolClient + SessionProtocolClient + Send + Sync> NetworkProtocolClient for T {}
+
+#[async_trait]
+impl ProtocolClient for Box<dyn NetworkProtocolClient> {
+    async fn run_connection(&mut self) -> Result<(), anyhow::Error> {
+        (**self).run_connection().await
+    }
+}
+
+#[async_trait]
+impl SessionProtocolClient for Box<dyn NetworkProtocolClient> {
+    fn set_session_recorder(&mut self, session_recorder: SessionConnectionRecorder) {
+        (**self).set_session_recorder(session_recorder)
+    }
+
+    fn set_packet_inspector(&mut self, packet_inspector: PacketInspectorConnection) {
+        (**self).set_packet_inspector(packet_inspector)
+    }
+
+    fn set_network_statistics(&mut self, network_statistics: NetworkStatistics) {
+        (**self).set_network_statistics(network_statistics)
+    }
+
+    fn client_message_rx(&mut self) -> &mut tokio::sync::mpsc::UnboundedReceiver<ClientMessage> {
+        (**self).client_message_rx()
+    }
+
+    async fn handle_replay_packet(&self, packet: &Packet) -> Result<(), anyhow::Error> {
+        (**self).handle_replay_packet(packet).await
+    }
+}
+
+pub type LoginClientFactory = Box<
+    dyn Fn(
+            SocketAddr,
+            tokio::sync::mpsc::UnboundedReceiver<ClientMessage>,
+            crossbeam_channel::Sender<ServerMessage>,
+        ) -> Box<dyn NetworkProtocolClient>
+        + Send
+        + Sync,
+>;
+
+/// Creates a world or game client, the u32 parameter is the packet codec seed
+/// sent by the previous server.
+pub type ServerClientFactory = Box<
+    dyn Fn(
+            SocketAddr,
+            u32,
+            tokio::sync::mpsc::UnboundedReceiver<ClientMessage>,
+            crossbeam_channel::Sender<ServerMessage>,
+        ) -> Box<dyn NetworkProtocolClient>
+        + Send
+        + Sync,
+>;
+
+/// Creates a game client, as for `ServerClientFactory` with an additional
+/// receiver for `ChatMessage`.
+pub type GameClientFactory = Box<
+    dyn Fn(
+            SocketAddr,
+            u32,
+            tokio::sync::mpsc::UnboundedReceiver<ClientMessage>,
+            tokio::sync::mpsc::UnboundedReceiver<ChatMessage>,
+            crossbeam_channel::Sender<ServerMessage>,
+        ) -> Box<dyn NetworkProtocolClient>
+        + Send
+        + Sync,
+>;
+
+/// The factories used to create the clients for each connection of a network
+/// protocol version.
+pub struct NetworkProtocol {
+    pub login_client: LoginClientFactory,
+    pub world_client: ServerClientFactory,
+    pub game_client: GameClientFactory,
+}
+
+/// Maps a network version, as used by `GameConfig.network_version`, to the
+/// protocol used to create the login, world and game clients.
+///
+/// Additional protocols can be registered from `SystemsConfig.add_custom_systems`
+/// before the configured network version is selected.
+#[derive(Default, Resource)]
+pub struct ProtocolRegistry {
+    protocols: HashMap<String, NetworkProtocol>,
+    selected: Option<String>,
+}
+
+impl ProtocolRegistry {
+    pub fn register(&mut self, network_version: impl Into<String>, protocol: NetworkProtocol) {
+        self.protocols.insert(network_version.into(), protocol);
+    }
+
+    pub fn contains(&self, network_version: &str) -> bool {
+        self.protocols.contains_key(network_version)
+    }
+
+    pub fn network_versions(&self) -> impl Iterator<Item = &str> {
+        self.protocols
+            .keys()
+            .map(|network_version| network_version.as_str())
+    }
+
+    /// Selects the protocol used for new connections, returns false if no
+    /// protocol is registered for the network version.
+    pub fn select(&mut self, network_version: &str) -> bool {
+        if self.contains(network_version) {
+            self.selected = Some(network_version.to_string());
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn selected(&self) -> Option<&NetworkProtocol> {
+        self.selected
+            .as_ref()
+            .and_then(|network_version| self.protocols.get(network_version))
+    }
+}
@@ -0,0 +1,429 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+use rose_game_common::messages::client::ClientMessage;
+use rose_network_common::Packet;
+
+use crate::protocol::{
+    NetworkStatistics, PacketInspectorConnection, ProtocolClient, ProtocolClientError,
+};
+
+const SESSION_FILE_MAGIC: &[u8; 8] = b"ROSESESS";
+const SESSION_FILE_VERSION: u32 = 1;
+
+#[derive(Debug, Error)]
+pub enum SessionFileError {
+    #[error("invalid session file header")]
+    InvalidHeader,
+    #[error("unsupported session file version {0}")]
+    UnsupportedVersion(u32),
+    #[error("invalid connection type {0}")]
+    InvalidConnectionType(u8),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SessionConnectionType {
+    Login,
+    World,
+    Game,
+}
+
+impl SessionConnectionType {
+    fn to_u8(self) -> u8 {
+        match self {
+            SessionConnectionType::Login => 0,
+            SessionConnectionType::World => 1,
+            SessionConnectionType::Game => 2,
+        }
+    }
+
+    fn from_u8(value: u8) -> Result<Self, SessionFileError> {
+        match value {
+            0 => Ok(SessionConnectionType::Login),
+            1 => Ok(SessionConnectionType::World),
+            2 => Ok(SessionConnectionType::Game),
+            _ => Err(SessionFileError::InvalidConnectionType(value)),
+        }
+    }
+}
+
+struct SessionRecorderState {
+    writer: BufWriter<File>,
+    next_connection_id: u32,
+}
+
+/// Writes every packet received from the server to a session file so that it
+/// can later be replayed with [`SessionReplay`].
+///
+/// The raw packets are recorded rather than the decoded `ServerMessage`s.
+/// `ServerMessage` has no serialisation we could store it with. A replay also
+/// passes the packets through the same decoding as a live connection, so bugs
+/// in the packet decoding can be reproduced from a recording too.
+///
+/// The recorder is shared between the login, world and game connections, each
+/// connection is assigned a unique id so the replay can keep them separate.
+#[derive(Clone)]
+pub struct SessionRecorder {
+    state: Arc<Mutex<SessionRecorderState>>,
+}
+
+impl SessionRecorder {
+    pub fn create(path: &Path) -> Result<Self, SessionFileError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(SESSION_FILE_MAGIC)?;
+        writer.write_all(&SESSION_FILE_VERSION.to_le_bytes())?;
+        writer.flush()?;
+
+        Ok(Self {
+            state: Arc::new(Mutex::new(SessionRecorderState {
+                writer,
+                next_connection_id: 0,
+            })),
+        })
+    }
+
+    pub fn connection(&self, connection_type: SessionConnectionType) -> SessionConnectionRecorder {
+        let mut state = self.state.lock().unwrap();
+        let connection_id = state.next_connection_id;
+        state.next_connection_id += 1;
+
+        SessionConnectionRecorder {
+            recorder: self.clone(),
+            connection_id,
+            connection_type,
+            start_time: Instant::now(),
+        }
+    }
+
+    fn write_packet(
+        &self,
+        connection_id: u32,
+        connection_type: SessionConnectionType,
+        elapsed: Duration,
+        packet: &Packet,
+    ) -> Result<(), std::io::Error> {
+        let mut state = self.state.lock().unwrap();
+        let writer = &mut state.writer;
+        writer.write_all(&connection_id.to_le_bytes())?;
+        writer.write_all(&[connection_type.to_u8()])?;
+        writer.write_all(&(elapsed.as_micros() as u64).to_le_bytes())?;
+        writer.write_all(&packet.command.to_le_bytes())?;
+        writer.write_all(&(packet.data.len() as u32).to_le_bytes())?;
+        writer.write_all(&packet.data[..])?;
+
+        // Flush after every packet so the recording survives a crash
+        writer.flush()
+    }
+}
+
+/// Records the packets of a single login, world or game connection.
+pub struct SessionConnectionRecorder {
+    recorder: SessionRecorder,
+    connection_id: u32,
+    connection_type: SessionConnectionType,
+    start_time: Instant,
+}
+
+impl SessionConnectionRecorder {
+    pub fn record_packet(&self, packet: &Packet) {
+        if let Err(error) = self.recorder.write_packet(
+            self.connection_id,
+            self.connection_type,
+            self.start_time.elapsed(),
+            packet,
+        ) {
+            log::warn!("Failed to record session packet with error: {}", error);
+        }
+    }
+}
+
+pub struct SessionPacket {
+    pub elapsed: Duration,
+    pub command: u16,
+    pub data: Bytes,
+}
+
+impl SessionPacket {
+    pub fn to_packet(&self) -> Packet {
+        Packet {
+            command: self.command,
+            data: self.data.clone(),
+        }
+    }
+}
+
+pub struct SessionConnection {
+    pub connection_type: SessionConnectionType,
+    pub packets: Vec<SessionPacket>,
+}
+
+/// A previously recorded session, each connection is handed out in recorded
+/// order to the replay client of the matching connection type.
+#[derive(Clone)]
+pub struct SessionReplay {
+    connections: Arc<Mutex<Vec<Option<SessionConnection>>>>,
+}
+
+impl SessionReplay {
+    pub fn load(path: &Path) -> Result<Self, SessionFileError> {
+        let mut reader = BufReader::new(File::open(path)?);
+
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != SESSION_FILE_MAGIC {
+            return Err(SessionFileError::InvalidHeader);
+        }
+
+        let version = read_u32(&mut reader)?;
+        if version != SESSION_FILE_VERSION {
+            return Err(SessionFileError::UnsupportedVersion(version));
+        }
+
+        let mut connections: Vec<(u32, SessionConnection)> = Vec::new();
+        loop {
+            let connection_id = match read_u32(&mut reader) {
+                Ok(connection_id) => connection_id,
+                Err(error) if error.kind() == std::io::ErrorKind::UnexpectedEof => break,
+                Err(error) => return Err(error.into()),
+            };
+
+            let mut connection_type = [0u8; 1];
+            reader.read_exact(&mut connection_type)?;
+            let connection_type = SessionConnectionType::from_u8(connection_type[0])?;
+
+            let mut elapsed = [0u8; 8];
+            reader.read_exact(&mut elapsed)?;
+            let elapsed = Duration::from_micros(u64::from_le_bytes(elapsed));
+
+            let mut command = [0u8; 2];
+            reader.read_exact(&mut command)?;
+            let command = u16::from_le_bytes(command);
+
+            let mut data = vec![0u8; read_u32(&mut reader)? as usize];
+            reader.read_exact(&mut data)?;
+
+            let packet = SessionPacket {
+                elapsed,
+                command,
+                data: Bytes::from(data),
+            };
+
+            if let Some((_, connection)) =
+                connections.iter_mut().find(|(id, _)| *id == connection_id)
+            {
+                connection.packets.push(packet);
+            } else {
+                connections.push((
+                    connection_id,
+                    SessionConnection {
+                        connection_type,
+                        packets: vec![packet],
+                    },
+                ));
+            }
+        }
+
+        connections.sort_by_key(|(connection_id, _)| *connection_id);
+
+        Ok(Self {
+            connections: Arc::new(Mutex::new(
+                connections
+                    .into_iter()
+                    .map(|(_, connection)| Some(connection))
+                    .collect(),
+            )),
+        })
+    }
+
+    /// Takes the next recorded connection of the given type, returns None
+    /// once the recording has no more connections of that type.
+    pub fn take_connection(
+        &self,
+        connection_type: SessionConnectionType,
+    ) -> Option<SessionConnection> {
+        self.connections
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|connection| {
+                connection.as_ref().map_or(false, |connection| {
+                    connection.connection_type == connection_type
+                })
+            })
+            .and_then(|connection| connection.take())
+    }
+}
+
+fn read_u32(reader: &mut impl Read) -> Result<u32, std::io::Error> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+/// Implemented by protocol clients so their packets can be recorded, and so a
+/// [`ReplayClient`] can decode recorded packets with the same code used for a
+/// live connection.
+#[async_trait]
+pub trait SessionProtocolClient {
+    fn set_session_recorder(&mut self, session_recorder: SessionConnectionRecorder);
+
+    fn set_packet_inspector(&mut self, packet_inspector: PacketInspectorConnection);
+
+    fn set_network_statistics(&mut self, network_statistics: NetworkStatistics);
+
+    fn client_message_rx(&mut self) -> &mut tokio::sync::mpsc::UnboundedReceiver<ClientMessage>;
+
+    async fn handle_replay_packet(&self, packet: &Packet) -> Result<(), anyhow::Error>;
+}
+
+/// Feeds a recorded connection back through a protocol client at the
+/// original pacing, any messages sent by the client are ignored.
+pub struct ReplayClient<T: SessionProtocolClient + Send + Sync> {
+    handler: T,
+    connection: Option<SessionConnection>,
+}
+
+impl<T: SessionProtocolClient + Send + Sync> ReplayClient<T> {
+    pub fn new(
+        handler: T,
+        session_replay: &SessionReplay,
+        connection_type: SessionConnectionType,
+    ) -> Self {
+        let connection = session_replay.take_connection(connection_type);
+        if connection.is_none() {
+            log::warn!(
+                "Session replay has no remaining {:?} connection",
+                connection_type
+            );
+        }
+
+        Self {
+            handler,
+            connection,
+        }
+    }
+}
+
+#[async_trait]
+impl<T: SessionProtocolClient + Send + Sync> ProtocolClient for ReplayClient<T> {
+    async fn run_connection(&mut self) -> Result<(), anyhow::Error> {
+        let packets = self
+            .connection
+            .take()
+            .map(|connection| connection.packets)
+            .unwrap_or_default();
+        let start_time = tokio::time::Instant::now();
+
+        for session_packet in packets {
+            let deadline = start_time + session_packet.elapsed;
+
+            loop {
+                tokio::select! {
+                    _ = tokio::time::sleep_until(deadline) => break,
+                    client_message = self.handler.client_message_rx().recv() => {
+                        if let Some(message) = client_message {
+                            log::debug!(target: "packets", "Replay ignored client message {:?}", message);
+                        } else {
+                            return Err(ProtocolClientError::ClientInitiatedDisconnect.into());
+                        }
+                    }
+                };
+            }
+
+            let packet = session_packet.to_packet();
+            if let Err(error) = self.handler.handle_replay_packet(&packet).await {
+                log::warn!(
+                    "Error {} handling replay packet [{:03X}] {:02x?}",
+                    error,
+                    packet.command,
+                    &packet.data[..]
+                );
+                return Err(error);
+            }
+        }
+
+        // Keep the connection alive until the client disconnects
+        while self.handler.client_message_rx().recv().await.is_some() {}
+        Err(ProtocolClientError::ClientInitiatedDisconnect.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(command: u16, data: &'static [u8]) -> Packet {
+        Packet {
+            command,
+            data: Bytes::from_static(data),
+        }
+    }
+
+    #[test]
+    fn session_file_round_trip() {
+        let path = std::env::temp_dir().join(format!(
+            "rose-offline-client-session-test-{}.bin",
+            std::process::id()
+        ));
+
+        let recorder = SessionRecorder::create(&path).unwrap();
+        let login = recorder.connection(SessionConnectionType::Login);
+        let game = recorder.connection(SessionConnectionType::Game);
+        login.record_packet(&packet(0x708, &[1, 2, 3]));
+        game.record_packet(&packet(0x715, &[]));
+        login.record_packet(&packet(0x704, &[4, 5]));
+        drop((login, game, recorder));
+
+        let replay = SessionReplay::load(&path);
+        std::fs::remove_file(&path).ok();
+        let replay = replay.unwrap();
+
+        assert!(replay
+            .take_connection(SessionConnectionType::World)
+            .is_none());
+
+        let login = replay
+            .take_connection(SessionConnectionType::Login)
+            .unwrap();
+        assert_eq!(login.connection_type, SessionConnectionType::Login);
+        assert_eq!(login.packets.len(), 2);
+        assert_eq!(login.packets[0].command, 0x708);
+        assert_eq!(&login.packets[0].data[..], &[1, 2, 3]);
+        assert_eq!(login.packets[1].command, 0x704);
+        assert_eq!(&login.packets[1].data[..], &[4, 5]);
+        assert!(login.packets[0].elapsed <= login.packets[1].elapsed);
+
+        let game = replay.take_connection(SessionConnectionType::Game).unwrap();
+        assert_eq!(game.packets.len(), 1);
+        assert_eq!(game.packets[0].command, 0x715);
+        assert!(game.packets[0].data.is_empty());
+
+        // Each connection is only handed out once
+        assert!(replay
+            .take_connection(SessionConnectionType::Login)
+            .is_none());
+    }
+
+    #[test]
+    fn session_file_invalid_header() {
+        let path = std::env::temp_dir().join(format!(
+            "rose-offline-client-session-invalid-test-{}.bin",
+            std::process::id()
+        ));
+        std::fs::write(&path, b"NOTASESSION").unwrap();
+
+        let result = SessionReplay::load(&path);
+        std::fs::remove_file(&path).ok();
+        assert!(matches!(result, Err(SessionFileError::InvalidHeader)));
+    }
+}
@@ -25,6 +25,8 @@ use bevy::{
     },
 };
 
+use crate::resources::RenderConfiguration;
+
 pub const ZONE_LIGHTING_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 0x444949d32b35d5d9);
 
@@ -198,7 +200,11 @@ impl FromWorld for ZoneLightingUniformMeta {
     }
 }
 
-fn extract_uniform_data(mut commands: Commands, zone_lighting: Extract<Res<ZoneLighting>>) {
+fn extract_uniform_data(
+    mut commands: Commands,
+    zone_lighting: Extract<Res<ZoneLighting>>,
+    render_configuration: Extract<Res<RenderConfiguration>>,
+) {
     commands.insert_resource(ZoneLightingUniformData {
         map_ambient_color: zone_lighting.map_ambient_color.extend(1.0),
         character_ambient_color: zone_lighting.character_ambient_color.extend(1.0),
@@ -206,7 +212,7 @@ fn extract_uniform_data(mut commands: Commands, zone_lighting: Extract<Res<ZoneL
         light_direction: zone_lighting.light_direction.extend(1.0),
         fog_color: zone_lighting.fog_color.extend(1.0),
         fog_density: if zone_lighting.color_fog_enabled {
-            zone_lighting.fog_density
+            zone_lighting.fog_density / render_configuration.fog_distance_multiplier.max(0.01)
         } else {
             0.0
         },
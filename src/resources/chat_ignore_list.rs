@@ -0,0 +1,28 @@
+use bevy::prelude::Resource;
+use std::collections::BTreeSet;
+
+/// Names of characters whose chat messages are hidden from the chatbox.
+#[derive(Default, Resource)]
+pub struct ChatIgnoreList {
+    names: BTreeSet<String>,
+}
+
+impl ChatIgnoreList {
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.contains(&name.to_lowercase())
+    }
+
+    /// Returns false if the name was already ignored.
+    pub fn add(&mut self, name: &str) -> bool {
+        self.names.insert(name.to_lowercase())
+    }
+
+    /// Returns false if the name was not ignored.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.names.remove(&name.to_lowercase())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(|name| name.as_str())
+    }
+}
@@ -0,0 +1,78 @@
+use std::time::{Duration, Instant};
+
+use bevy::prelude::Resource;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ConnectionType {
+    Login,
+    World,
+    Game,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ReconnectStage {
+    Waiting,
+    WaitServerList,
+    WaitCharacterList,
+    SelectedCharacter,
+}
+
+pub struct ReconnectAttempt {
+    pub attempt: u32,
+    pub next_attempt_time: Instant,
+    pub stage: ReconnectStage,
+}
+
+#[derive(Resource)]
+pub struct ReconnectSettings {
+    pub enabled: bool,
+    pub max_attempts: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl ReconnectSettings {
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.initial_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay)
+    }
+}
+
+/// Tracks the login details used to reach the game server, and any lost
+/// connection, so that we can display the error and reconnect to the same
+/// character.
+#[derive(Default, Resource)]
+pub struct ConnectionState {
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub server_id: Option<usize>,
+    pub channel_id: Option<usize>,
+    pub server_name: Option<String>,
+    pub channel_name: Option<String>,
+    pub character_name: Option<String>,
+    pub lost_connection: Option<(ConnectionType, String)>,
+    pub reconnect: Option<ReconnectAttempt>,
+}
+
+impl ConnectionState {
+    pub fn connection_lost(&mut self, connection_type: ConnectionType, error: String) {
+        self.lost_connection = Some((connection_type, error));
+    }
+
+    pub fn is_reconnecting(&self) -> bool {
+        self.reconnect.is_some()
+    }
+
+    pub fn can_reconnect(&self) -> bool {
+        self.username.is_some()
+            && self.password.is_some()
+            && self.server_id.is_some()
+            && self.channel_id.is_some()
+            && self.character_name.is_some()
+    }
+
+    pub fn cancel_reconnect(&mut self) {
+        self.reconnect = None;
+    }
+}
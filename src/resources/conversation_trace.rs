@@ -0,0 +1,99 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::Resource;
+use rose_file_readers::{ConFile, ConMessageType, VfsPathBuf};
+
+use crate::{
+    resources::GameData,
+    scripting::lua4::{Lua4Function, Lua4VM, Lua4VMTrace, Lua4Value},
+};
+
+pub struct ConversationTraceMessage {
+    /// None for the initial messages of the conversation
+    pub menu_index: Option<usize>,
+    pub message_type: &'static str,
+    pub string_id: usize,
+    pub text: Option<String>,
+    pub condition_function: String,
+    pub action_function: String,
+    pub message_value: i32,
+}
+
+/// The most recently opened conversation, recorded whilst the conversation
+/// trace viewer window is open.
+#[derive(Default, Resource)]
+pub struct ConversationTrace {
+    /// Set whilst the trace viewer window is open
+    pub enabled: bool,
+
+    pub con_file_path: String,
+    pub messages: Vec<ConversationTraceMessage>,
+    pub functions: Vec<(String, Arc<Lua4Function>)>,
+    pub trace: Arc<Mutex<Lua4VMTrace>>,
+}
+
+impl ConversationTrace {
+    /// Resets the trace for a new conversation, returns the trace to use
+    /// for its VM.
+    pub fn begin(
+        &mut self,
+        con_file_path: &VfsPathBuf,
+        con_file: &ConFile,
+        game_data: &GameData,
+    ) -> Arc<Mutex<Lua4VMTrace>> {
+        self.con_file_path = con_file_path.path().to_string_lossy().to_string();
+        self.functions.clear();
+        self.trace.lock().unwrap().clear();
+
+        let initial_messages = con_file
+            .initial_messages
+            .iter()
+            .map(|message| (None, message));
+        let menu_messages = con_file
+            .menus
+            .iter()
+            .enumerate()
+            .flat_map(|(menu_index, menu)| {
+                menu.messages
+                    .iter()
+                    .map(move |message| (Some(menu_index), message))
+            });
+
+        self.messages = initial_messages
+            .chain(menu_messages)
+            .map(|(menu_index, message)| ConversationTraceMessage {
+                menu_index,
+                message_type: match message.message_type {
+                    ConMessageType::Close => "Close",
+                    ConMessageType::PlayerSelect => "PlayerSelect",
+                    ConMessageType::JumpSelect => "JumpSelect",
+                    ConMessageType::NextMessage => "NextMessage",
+                    ConMessageType::ShowMessage => "ShowMessage",
+                },
+                string_id: message.string_id as usize,
+                text: game_data
+                    .ltb_event
+                    .get_string(message.string_id as usize, 2)
+                    .map(|text| text.to_string()),
+                condition_function: message.condition_function.clone(),
+                action_function: message.action_function.clone(),
+                message_value: message.message_value,
+            })
+            .collect();
+
+        self.trace.clone()
+    }
+
+    /// Collects the lua functions defined by the conversation script.
+    pub fn set_functions(&mut self, lua_vm: &Lua4VM) {
+        self.functions = lua_vm
+            .globals
+            .iter()
+            .filter_map(|(name, value)| match value {
+                Lua4Value::Closure(function, _) => Some((name.clone(), function.clone())),
+                _ => None,
+            })
+            .collect();
+        self.functions.sort_by(|(_, a), (_, b)| a.line.cmp(&b.line));
+    }
+}
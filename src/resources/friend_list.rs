@@ -0,0 +1,44 @@
+use bevy::prelude::Resource;
+
+/// Names of characters in the Community dialog friends list.
+#[derive(Default, Resource)]
+pub struct FriendList {
+    names: Vec<String>,
+}
+
+impl FriendList {
+    pub fn contains(&self, name: &str) -> bool {
+        self.names
+            .iter()
+            .any(|friend| friend.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns false if the name was already a friend.
+    pub fn add(&mut self, name: &str) -> bool {
+        if self.contains(name) {
+            return false;
+        }
+
+        self.names.push(name.to_string());
+        self.names.sort_by_key(|name| name.to_lowercase());
+        true
+    }
+
+    /// Returns false if the name was not a friend.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let Some(index) = self
+            .names
+            .iter()
+            .position(|friend| friend.eq_ignore_ascii_case(name))
+        else {
+            return false;
+        };
+
+        self.names.remove(index);
+        true
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(|name| name.as_str())
+    }
+}
@@ -5,15 +5,19 @@ use rose_game_common::{
     messages::{client::ClientMessage, server::ServerMessage},
 };
 
+use crate::protocol::ChatMessage;
+
 #[derive(Resource)]
 pub struct GameConnection {
     pub client_message_tx: tokio::sync::mpsc::UnboundedSender<ClientMessage>,
+    pub chat_message_tx: tokio::sync::mpsc::UnboundedSender<ChatMessage>,
     pub server_message_rx: crossbeam_channel::Receiver<ServerMessage>,
 }
 
 impl GameConnection {
     pub fn new(
         client_message_tx: tokio::sync::mpsc::UnboundedSender<ClientMessage>,
+        chat_message_tx: tokio::sync::mpsc::UnboundedSender<ChatMessage>,
         server_message_rx: crossbeam_channel::Receiver<ServerMessage>,
         login_token: u32,
         password: Password,
@@ -27,6 +31,7 @@ impl GameConnection {
 
         Self {
             client_message_tx,
+            chat_message_tx,
             server_message_rx,
         }
     }
@@ -0,0 +1,16 @@
+use bevy::prelude::Resource;
+
+#[derive(Resource)]
+pub struct GameplaySettings {
+    pub auto_pickup: bool,
+    pub camera_sensitivity: f32,
+}
+
+impl Default for GameplaySettings {
+    fn default() -> Self {
+        Self {
+            auto_pickup: false,
+            camera_sensitivity: 1.0,
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use bevy::prelude::Resource;
+
+use rose_file_readers::{StbFile, VirtualFilesystem};
+
+pub const HELP_INDEX_PATH: &str = "3DDATA/STB/HELP.STB";
+
+pub struct HelpTopic {
+    pub title: String,
+    pub text: String,
+}
+
+/// Help topics shown in the Help dialog, the first column of each row is the
+/// topic title and the remaining columns are its text.
+#[derive(Default, Resource)]
+pub struct HelpIndex {
+    pub topics: Vec<HelpTopic>,
+}
+
+impl HelpIndex {
+    pub fn load(vfs: &VirtualFilesystem) -> Self {
+        let mut index = Self::default();
+
+        let stb = match vfs.read_file::<StbFile, _>(HELP_INDEX_PATH) {
+            Ok(stb) => stb,
+            Err(error) => {
+                log::warn!(
+                    "Failed to load help index from {} with error: {}",
+                    HELP_INDEX_PATH,
+                    error
+                );
+                return index;
+            }
+        };
+
+        for row in 0..stb.rows() {
+            let title = stb.get(row, 0).trim();
+            if title.is_empty() {
+                continue;
+            }
+
+            let text = (1..stb.columns())
+                .map(|column| stb.get(row, column).trim())
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n\n");
+
+            index.topics.push(HelpTopic {
+                title: title.to_string(),
+                text,
+            });
+        }
+
+        log::info!(
+            "Loaded {} help topics from {}",
+            index.topics.len(),
+            HELP_INDEX_PATH
+        );
+        index
+    }
+}
@@ -0,0 +1,621 @@
+use std::collections::HashMap;
+
+use bevy::prelude::{Input, KeyCode, Resource};
+use enum_map::{enum_map, Enum, EnumMap};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Enum)]
+pub enum KeyAction {
+    Hotbar1,
+    Hotbar2,
+    Hotbar3,
+    Hotbar4,
+    Hotbar5,
+    Hotbar6,
+    Hotbar7,
+    Hotbar8,
+    ToggleCharacterInfo,
+    ToggleInventory,
+    ToggleClan,
+    ToggleSkillList,
+    ToggleQuestList,
+    ToggleSettings,
+    ToggleDebugUi,
+    DebugInspectorPick,
+    DebugPhysicsSpawnBalls,
+    FreeCameraForward,
+    FreeCameraBackward,
+    FreeCameraLeft,
+    FreeCameraRight,
+    FreeCameraDown,
+    FreeCameraUp,
+    FreeCameraSpeedBoost,
+}
+
+impl KeyAction {
+    pub const HOTBAR: [KeyAction; 8] = [
+        KeyAction::Hotbar1,
+        KeyAction::Hotbar2,
+        KeyAction::Hotbar3,
+        KeyAction::Hotbar4,
+        KeyAction::Hotbar5,
+        KeyAction::Hotbar6,
+        KeyAction::Hotbar7,
+        KeyAction::Hotbar8,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = KeyAction> {
+        (0..KeyAction::LENGTH).map(KeyAction::from_usize)
+    }
+
+    /// The name used for this action in the `[keybindings]` section of config.toml
+    pub fn config_name(self) -> &'static str {
+        match self {
+            KeyAction::Hotbar1 => "hotbar_1",
+            KeyAction::Hotbar2 => "hotbar_2",
+            KeyAction::Hotbar3 => "hotbar_3",
+            KeyAction::Hotbar4 => "hotbar_4",
+            KeyAction::Hotbar5 => "hotbar_5",
+            KeyAction::Hotbar6 => "hotbar_6",
+            KeyAction::Hotbar7 => "hotbar_7",
+            KeyAction::Hotbar8 => "hotbar_8",
+            KeyAction::ToggleCharacterInfo => "toggle_character_info",
+            KeyAction::ToggleInventory => "toggle_inventory",
+            KeyAction::ToggleClan => "toggle_clan",
+            KeyAction::ToggleSkillList => "toggle_skill_list",
+            KeyAction::ToggleQuestList => "toggle_quest_list",
+            KeyAction::ToggleSettings => "toggle_settings",
+            KeyAction::ToggleDebugUi => "toggle_debug_ui",
+            KeyAction::DebugInspectorPick => "debug_inspector_pick",
+            KeyAction::DebugPhysicsSpawnBalls => "debug_physics_spawn_balls",
+            KeyAction::FreeCameraForward => "free_camera_forward",
+            KeyAction::FreeCameraBackward => "free_camera_backward",
+            KeyAction::FreeCameraLeft => "free_camera_left",
+            KeyAction::FreeCameraRight => "free_camera_right",
+            KeyAction::FreeCameraDown => "free_camera_down",
+            KeyAction::FreeCameraUp => "free_camera_up",
+            KeyAction::FreeCameraSpeedBoost => "free_camera_speed_boost",
+        }
+    }
+
+    pub fn from_config_name(name: &str) -> Option<KeyAction> {
+        KeyAction::iter().find(|action| action.config_name() == name)
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            KeyAction::Hotbar1 => "Hotbar Slot 1",
+            KeyAction::Hotbar2 => "Hotbar Slot 2",
+            KeyAction::Hotbar3 => "Hotbar Slot 3",
+            KeyAction::Hotbar4 => "Hotbar Slot 4",
+            KeyAction::Hotbar5 => "Hotbar Slot 5",
+            KeyAction::Hotbar6 => "Hotbar Slot 6",
+            KeyAction::Hotbar7 => "Hotbar Slot 7",
+            KeyAction::Hotbar8 => "Hotbar Slot 8",
+            KeyAction::ToggleCharacterInfo => "Character Info",
+            KeyAction::ToggleInventory => "Inventory",
+            KeyAction::ToggleClan => "Clan",
+            KeyAction::ToggleSkillList => "Skill List",
+            KeyAction::ToggleQuestList => "Quest List",
+            KeyAction::ToggleSettings => "Settings",
+            KeyAction::ToggleDebugUi => "Debug UI",
+            KeyAction::DebugInspectorPick => "Debug Inspector Pick",
+            KeyAction::DebugPhysicsSpawnBalls => "Debug Physics Spawn Balls",
+            KeyAction::FreeCameraForward => "Free Camera Forward",
+            KeyAction::FreeCameraBackward => "Free Camera Backward",
+            KeyAction::FreeCameraLeft => "Free Camera Left",
+            KeyAction::FreeCameraRight => "Free Camera Right",
+            KeyAction::FreeCameraDown => "Free Camera Down",
+            KeyAction::FreeCameraUp => "Free Camera Up",
+            KeyAction::FreeCameraSpeedBoost => "Free Camera Speed Boost",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub key: KeyCode,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+}
+
+const KEY_NAMES: &[(KeyCode, &str)] = &[
+    (KeyCode::Key1, "1"),
+    (KeyCode::Key2, "2"),
+    (KeyCode::Key3, "3"),
+    (KeyCode::Key4, "4"),
+    (KeyCode::Key5, "5"),
+    (KeyCode::Key6, "6"),
+    (KeyCode::Key7, "7"),
+    (KeyCode::Key8, "8"),
+    (KeyCode::Key9, "9"),
+    (KeyCode::Key0, "0"),
+    (KeyCode::A, "A"),
+    (KeyCode::B, "B"),
+    (KeyCode::C, "C"),
+    (KeyCode::D, "D"),
+    (KeyCode::E, "E"),
+    (KeyCode::F, "F"),
+    (KeyCode::G, "G"),
+    (KeyCode::H, "H"),
+    (KeyCode::I, "I"),
+    (KeyCode::J, "J"),
+    (KeyCode::K, "K"),
+    (KeyCode::L, "L"),
+    (KeyCode::M, "M"),
+    (KeyCode::N, "N"),
+    (KeyCode::O, "O"),
+    (KeyCode::P, "P"),
+    (KeyCode::Q, "Q"),
+    (KeyCode::R, "R"),
+    (KeyCode::S, "S"),
+    (KeyCode::T, "T"),
+    (KeyCode::U, "U"),
+    (KeyCode::V, "V"),
+    (KeyCode::W, "W"),
+    (KeyCode::X, "X"),
+    (KeyCode::Y, "Y"),
+    (KeyCode::Z, "Z"),
+    (KeyCode::F1, "F1"),
+    (KeyCode::F2, "F2"),
+    (KeyCode::F3, "F3"),
+    (KeyCode::F4, "F4"),
+    (KeyCode::F5, "F5"),
+    (KeyCode::F6, "F6"),
+    (KeyCode::F7, "F7"),
+    (KeyCode::F8, "F8"),
+    (KeyCode::F9, "F9"),
+    (KeyCode::F10, "F10"),
+    (KeyCode::F11, "F11"),
+    (KeyCode::F12, "F12"),
+    (KeyCode::Escape, "Escape"),
+    (KeyCode::Tab, "Tab"),
+    (KeyCode::Space, "Space"),
+    (KeyCode::Return, "Enter"),
+    (KeyCode::Back, "Backspace"),
+    (KeyCode::Insert, "Insert"),
+    (KeyCode::Delete, "Delete"),
+    (KeyCode::Home, "Home"),
+    (KeyCode::End, "End"),
+    (KeyCode::PageUp, "PageUp"),
+    (KeyCode::PageDown, "PageDown"),
+    (KeyCode::Up, "Up"),
+    (KeyCode::Down, "Down"),
+    (KeyCode::Left, "Left"),
+    (KeyCode::Right, "Right"),
+    (KeyCode::Grave, "Grave"),
+    (KeyCode::Minus, "Minus"),
+    (KeyCode::Equals, "Equals"),
+    (KeyCode::BracketLeft, "BracketLeft"),
+    (KeyCode::BracketRight, "BracketRight"),
+    (KeyCode::Backslash, "Backslash"),
+    (KeyCode::Semicolon, "Semicolon"),
+    (KeyCode::Apostrophe, "Apostrophe"),
+    (KeyCode::Comma, "Comma"),
+    (KeyCode::Period, "Period"),
+    (KeyCode::Slash, "Slash"),
+    (KeyCode::Numpad0, "Numpad0"),
+    (KeyCode::Numpad1, "Numpad1"),
+    (KeyCode::Numpad2, "Numpad2"),
+    (KeyCode::Numpad3, "Numpad3"),
+    (KeyCode::Numpad4, "Numpad4"),
+    (KeyCode::Numpad5, "Numpad5"),
+    (KeyCode::Numpad6, "Numpad6"),
+    (KeyCode::Numpad7, "Numpad7"),
+    (KeyCode::Numpad8, "Numpad8"),
+    (KeyCode::Numpad9, "Numpad9"),
+    (KeyCode::ShiftLeft, "LShift"),
+    (KeyCode::ShiftRight, "RShift"),
+    (KeyCode::ControlLeft, "LCtrl"),
+    (KeyCode::ControlRight, "RCtrl"),
+    (KeyCode::AltLeft, "LAlt"),
+    (KeyCode::AltRight, "RAlt"),
+];
+
+pub fn is_modifier_key(key: KeyCode) -> bool {
+    matches!(
+        key,
+        KeyCode::ShiftLeft
+            | KeyCode::ShiftRight
+            | KeyCode::ControlLeft
+            | KeyCode::ControlRight
+            | KeyCode::AltLeft
+            | KeyCode::AltRight
+    )
+}
+
+impl KeyBinding {
+    pub const fn new(key: KeyCode) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            alt: false,
+            shift: false,
+        }
+    }
+
+    pub const fn ctrl(key: KeyCode) -> Self {
+        Self {
+            key,
+            ctrl: true,
+            alt: false,
+            shift: false,
+        }
+    }
+
+    pub const fn alt(key: KeyCode) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            alt: true,
+            shift: false,
+        }
+    }
+
+    /// This key with the modifiers which are currently held. Held keys in
+    /// `ignored_keys` do not count as modifiers, nor does the bound key itself
+    /// when it is a modifier.
+    fn with_held_modifiers(&self, input: &Input<KeyCode>, ignored_keys: &[KeyCode]) -> Self {
+        let held = |keys: [KeyCode; 2]| {
+            keys.into_iter()
+                .any(|key| key != self.key && !ignored_keys.contains(&key) && input.pressed(key))
+        };
+
+        Self {
+            key: self.key,
+            ctrl: held([KeyCode::ControlLeft, KeyCode::ControlRight]),
+            alt: held([KeyCode::AltLeft, KeyCode::AltRight]),
+            shift: held([KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+        }
+    }
+
+    /// Returns true if every modifier of this binding is also used by `other`.
+    fn modifiers_within(&self, other: &KeyBinding) -> bool {
+        (!self.ctrl || other.ctrl) && (!self.alt || other.alt) && (!self.shift || other.shift)
+    }
+
+    /// Creates a binding from the currently held modifiers and a key which was just pressed.
+    pub fn from_input(key: KeyCode, input: &Input<KeyCode>) -> Self {
+        Self {
+            key,
+            ctrl: input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]),
+            alt: input.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]),
+            shift: input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+        }
+    }
+
+    /// Parses a binding such as "Ctrl+Shift+F1"
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut ctrl = false;
+        let mut alt = false;
+        let mut shift = false;
+        let mut key = None;
+
+        for part in text.split('+').map(|part| part.trim()) {
+            if key.is_some() {
+                return None;
+            }
+
+            if part.eq_ignore_ascii_case("ctrl") {
+                ctrl = true;
+            } else if part.eq_ignore_ascii_case("alt") {
+                alt = true;
+            } else if part.eq_ignore_ascii_case("shift") {
+                shift = true;
+            } else {
+                key = KEY_NAMES
+                    .iter()
+                    .find(|(_, name)| name.eq_ignore_ascii_case(part))
+                    .map(|(key, _)| *key);
+                if key.is_none() {
+                    return None;
+                }
+            }
+        }
+
+        Some(Self {
+            key: key?,
+            ctrl,
+            alt,
+            shift,
+        })
+    }
+}
+
+impl std::fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+
+        match KEY_NAMES.iter().find(|(key, _)| *key == self.key) {
+            Some((_, name)) => write!(f, "{}", name),
+            None => write!(f, "{:?}", self.key),
+        }
+    }
+}
+
+/// Bindings are read from bevy's `Input<KeyCode>`, which egui does not
+/// consume, so egui widgets also receive any bound keys. Systems handling
+/// bindings should check `wants_keyboard_input` first so typing into a text
+/// field does not also trigger actions.
+#[derive(Resource)]
+pub struct KeyBindings {
+    pub bindings: EnumMap<KeyAction, Vec<KeyBinding>>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            bindings: enum_map! {
+                KeyAction::Hotbar1 => vec![KeyBinding::new(KeyCode::F1)],
+                KeyAction::Hotbar2 => vec![KeyBinding::new(KeyCode::F2)],
+                KeyAction::Hotbar3 => vec![KeyBinding::new(KeyCode::F3)],
+                KeyAction::Hotbar4 => vec![KeyBinding::new(KeyCode::F4)],
+                KeyAction::Hotbar5 => vec![KeyBinding::new(KeyCode::F5)],
+                KeyAction::Hotbar6 => vec![KeyBinding::new(KeyCode::F6)],
+                KeyAction::Hotbar7 => vec![KeyBinding::new(KeyCode::F7)],
+                KeyAction::Hotbar8 => vec![KeyBinding::new(KeyCode::F8)],
+                KeyAction::ToggleCharacterInfo => vec![KeyBinding::alt(KeyCode::A)],
+                KeyAction::ToggleInventory => {
+                    vec![KeyBinding::alt(KeyCode::I), KeyBinding::alt(KeyCode::V)]
+                }
+                KeyAction::ToggleClan => vec![KeyBinding::alt(KeyCode::N)],
+                KeyAction::ToggleSkillList => vec![KeyBinding::alt(KeyCode::S)],
+                KeyAction::ToggleQuestList => vec![KeyBinding::alt(KeyCode::Q)],
+                KeyAction::ToggleSettings => vec![KeyBinding::alt(KeyCode::O)],
+                KeyAction::ToggleDebugUi => vec![KeyBinding::ctrl(KeyCode::D)],
+                KeyAction::DebugInspectorPick => vec![KeyBinding::new(KeyCode::P)],
+                KeyAction::DebugPhysicsSpawnBalls => vec![KeyBinding::new(KeyCode::B)],
+                KeyAction::FreeCameraForward => vec![KeyBinding::new(KeyCode::W)],
+                KeyAction::FreeCameraBackward => vec![KeyBinding::new(KeyCode::S)],
+                KeyAction::FreeCameraLeft => vec![KeyBinding::new(KeyCode::A)],
+                KeyAction::FreeCameraRight => vec![KeyBinding::new(KeyCode::D)],
+                KeyAction::FreeCameraDown => vec![KeyBinding::new(KeyCode::Q)],
+                KeyAction::FreeCameraUp => vec![KeyBinding::new(KeyCode::E)],
+                KeyAction::FreeCameraSpeedBoost => vec![KeyBinding::new(KeyCode::ShiftLeft)],
+            },
+        }
+    }
+}
+
+impl KeyBindings {
+    /// The default bindings, with any actions in the `[keybindings]` config
+    /// section replaced by the configured bindings.
+    pub fn from_config(config: &HashMap<String, Vec<String>>) -> Self {
+        let mut key_bindings = Self::default();
+
+        for (action_name, bindings) in config.iter() {
+            let Some(action) = KeyAction::from_config_name(action_name) else {
+                log::warn!("Unknown key binding action {}", action_name);
+                continue;
+            };
+
+            key_bindings.bindings[action] = bindings
+                .iter()
+                .filter_map(|text| {
+                    let binding = KeyBinding::parse(text);
+                    if binding.is_none() {
+                        log::warn!("Invalid key binding {} for {}", text, action_name);
+                    }
+                    binding
+                })
+                .collect();
+        }
+
+        for (binding, action_a, action_b) in key_bindings.conflicts() {
+            log::warn!(
+                "Key binding {} is used by both {} and {}",
+                binding,
+                action_a.config_name(),
+                action_b.config_name()
+            );
+        }
+
+        key_bindings
+    }
+
+    /// The `[keybindings]` config section for the current bindings.
+    pub fn to_config(&self) -> HashMap<String, Vec<String>> {
+        self.bindings
+            .iter()
+            .map(|(action, bindings)| {
+                (
+                    action.config_name().to_string(),
+                    bindings.iter().map(|binding| binding.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// The modifiers of the binding must be held. Any other held modifiers are
+    /// ignored unless a binding for the same key uses them, so F1 still works
+    /// whilst holding Shift, but S does not trigger whilst holding Alt because
+    /// Alt+S is bound.
+    fn modifiers_match(
+        &self,
+        binding: &KeyBinding,
+        input: &Input<KeyCode>,
+        ignored_keys: &[KeyCode],
+    ) -> bool {
+        let held = binding.with_held_modifiers(input, ignored_keys);
+
+        binding.modifiers_within(&held)
+            && !self.bindings.values().flatten().any(|other| {
+                other.key == binding.key
+                    && other != binding
+                    && binding.modifiers_within(other)
+                    && other.modifiers_within(&held)
+            })
+    }
+
+    pub fn pressed(&self, action: KeyAction, input: &Input<KeyCode>) -> bool {
+        self.bindings[action]
+            .iter()
+            .any(|binding| input.pressed(binding.key) && self.modifiers_match(binding, input, &[]))
+    }
+
+    pub fn just_pressed(&self, action: KeyAction, input: &Input<KeyCode>) -> bool {
+        self.bindings[action].iter().any(|binding| {
+            input.just_pressed(binding.key) && self.modifiers_match(binding, input, &[])
+        })
+    }
+
+    /// Like `pressed`, but the keys bound to `held_action` may also be held
+    /// down, e.g. moving the free camera whilst holding the speed boost key.
+    pub fn pressed_with(
+        &self,
+        action: KeyAction,
+        held_action: KeyAction,
+        input: &Input<KeyCode>,
+    ) -> bool {
+        let held_keys: Vec<KeyCode> = self.bindings[held_action]
+            .iter()
+            .map(|binding| binding.key)
+            .collect();
+
+        self.bindings[action].iter().any(|binding| {
+            input.pressed(binding.key) && self.modifiers_match(binding, input, &held_keys)
+        })
+    }
+
+    /// Returns every binding which is assigned to more than one action.
+    pub fn conflicts(&self) -> Vec<(KeyBinding, KeyAction, KeyAction)> {
+        let mut conflicts = Vec::new();
+
+        for (action_a, bindings_a) in self.bindings.iter() {
+            for (action_b, bindings_b) in self.bindings.iter() {
+                if action_b.into_usize() <= action_a.into_usize() {
+                    continue;
+                }
+
+                for binding in bindings_a.iter() {
+                    if bindings_b.contains(binding) {
+                        conflicts.push((*binding, action_a, action_b));
+                    }
+                }
+            }
+        }
+
+        conflicts
+    }
+
+    pub fn is_conflicting(&self, action: KeyAction, binding: &KeyBinding) -> bool {
+        self.bindings
+            .iter()
+            .any(|(other_action, bindings)| other_action != action && bindings.contains(binding))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(KeyBinding::parse("F1"), Some(KeyBinding::new(KeyCode::F1)));
+        assert_eq!(
+            KeyBinding::parse("alt+s"),
+            Some(KeyBinding::alt(KeyCode::S))
+        );
+        assert_eq!(
+            KeyBinding::parse(" Ctrl + Shift + Enter "),
+            Some(KeyBinding {
+                key: KeyCode::Return,
+                ctrl: true,
+                alt: false,
+                shift: true,
+            })
+        );
+        assert_eq!(
+            KeyBinding::parse("LShift"),
+            Some(KeyBinding::new(KeyCode::ShiftLeft))
+        );
+
+        assert_eq!(KeyBinding::parse(""), None);
+        assert_eq!(KeyBinding::parse("Ctrl"), None);
+        assert_eq!(KeyBinding::parse("Ctrl+"), None);
+        assert_eq!(KeyBinding::parse("NotAKey"), None);
+        assert_eq!(KeyBinding::parse("A+B"), None);
+        assert_eq!(KeyBinding::parse("A+Ctrl"), None);
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for &(key, _) in KEY_NAMES.iter() {
+            for modifiers in 0..8 {
+                let binding = KeyBinding {
+                    key,
+                    ctrl: modifiers & 1 != 0,
+                    alt: modifiers & 2 != 0,
+                    shift: modifiers & 4 != 0,
+                };
+                assert_eq!(KeyBinding::parse(&binding.to_string()), Some(binding));
+            }
+        }
+
+        assert_eq!(
+            KeyBinding {
+                key: KeyCode::F1,
+                ctrl: true,
+                alt: true,
+                shift: true,
+            }
+            .to_string(),
+            "Ctrl+Alt+Shift+F1"
+        );
+    }
+
+    #[test]
+    fn conflicts() {
+        let mut key_bindings = KeyBindings::default();
+        assert!(key_bindings.conflicts().is_empty());
+
+        key_bindings.bindings[KeyAction::ToggleClan] = vec![KeyBinding::alt(KeyCode::S)];
+        assert_eq!(
+            key_bindings.conflicts(),
+            vec![(
+                KeyBinding::alt(KeyCode::S),
+                KeyAction::ToggleClan,
+                KeyAction::ToggleSkillList
+            )]
+        );
+        assert!(key_bindings.is_conflicting(KeyAction::ToggleClan, &KeyBinding::alt(KeyCode::S)));
+        assert!(!key_bindings.is_conflicting(KeyAction::ToggleClan, &KeyBinding::alt(KeyCode::N)));
+    }
+
+    #[test]
+    fn unbound_modifiers_are_ignored() {
+        let key_bindings = KeyBindings::default();
+
+        let mut input = Input::<KeyCode>::default();
+        input.press(KeyCode::ShiftLeft);
+        input.press(KeyCode::F1);
+        assert!(key_bindings.just_pressed(KeyAction::Hotbar1, &input));
+
+        let mut input = Input::<KeyCode>::default();
+        input.press(KeyCode::ControlLeft);
+        input.press(KeyCode::AltRight);
+        input.press(KeyCode::F8);
+        assert!(key_bindings.just_pressed(KeyAction::Hotbar8, &input));
+
+        // Alt+S is bound to the skill list, so S alone must not also trigger
+        let mut input = Input::<KeyCode>::default();
+        input.press(KeyCode::AltLeft);
+        input.press(KeyCode::S);
+        assert!(key_bindings.just_pressed(KeyAction::ToggleSkillList, &input));
+        assert!(!key_bindings.pressed(KeyAction::FreeCameraBackward, &input));
+
+        // Modified bindings still require their modifiers
+        let mut input = Input::<KeyCode>::default();
+        input.press(KeyCode::S);
+        assert!(!key_bindings.just_pressed(KeyAction::ToggleSkillList, &input));
+        assert!(key_bindings.pressed(KeyAction::FreeCameraBackward, &input));
+    }
+}
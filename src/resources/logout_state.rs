@@ -0,0 +1,39 @@
+use std::time::{Duration, Instant};
+
+use bevy::prelude::Resource;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LogoutType {
+    CharacterSelect,
+    Exit,
+}
+
+#[derive(Default, Resource)]
+pub struct LogoutState {
+    pub logout_type: Option<LogoutType>,
+    pub request_sent: bool,
+    pub wait_until: Option<Instant>,
+}
+
+impl LogoutState {
+    pub fn request(&mut self, logout_type: LogoutType) {
+        self.logout_type = Some(logout_type);
+        self.request_sent = false;
+        self.wait_until = None;
+    }
+
+    pub fn cancel(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.logout_type.is_some()
+    }
+
+    /// The time left before the server will accept our logout request, as
+    /// given by the wait duration of a failed logout.
+    pub fn remaining_wait(&self) -> Option<Duration> {
+        self.wait_until
+            .map(|wait_until| wait_until.saturating_duration_since(Instant::now()))
+    }
+}
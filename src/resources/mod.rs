@@ -2,25 +2,38 @@ mod account;
 mod app_state;
 mod character_list;
 mod character_select_state;
+mod chat_ignore_list;
 mod client_entity_list;
+mod connection_state;
+mod conversation_trace;
 mod current_zone;
 mod damage_digits_spawner;
 mod debug_inspector;
 mod debug_render;
+mod friend_list;
 mod game_connection;
 mod game_data;
+mod gameplay_settings;
+mod help_index;
+mod key_bindings;
 mod login_connection;
 mod login_state;
+mod logout_state;
 mod name_tag_cache;
 mod name_tag_settings;
+mod network_session;
 mod network_thread;
+mod quest_objective_table;
 mod render_configuration;
+mod repair_state;
 mod selected_target;
 mod server_configuration;
 mod server_list;
+mod settings_file;
 mod sound_cache;
 mod sound_settings;
 mod specular_texture;
+mod system_func_table;
 mod ui_resources;
 mod virtual_filesystem;
 mod world_connection;
@@ -32,24 +45,42 @@ pub use account::Account;
 pub use app_state::AppState;
 pub use character_list::CharacterList;
 pub use character_select_state::CharacterSelectState;
+pub use chat_ignore_list::ChatIgnoreList;
 pub use client_entity_list::ClientEntityList;
+pub use connection_state::{
+    ConnectionState, ConnectionType, ReconnectAttempt, ReconnectSettings, ReconnectStage,
+};
+pub use conversation_trace::{ConversationTrace, ConversationTraceMessage};
 pub use current_zone::CurrentZone;
 pub use damage_digits_spawner::DamageDigitsSpawner;
 pub use debug_inspector::DebugInspector;
 pub use debug_render::DebugRenderConfig;
+pub use friend_list::FriendList;
 pub use game_connection::GameConnection;
 pub use game_data::GameData;
+pub use gameplay_settings::GameplaySettings;
+pub use help_index::{HelpIndex, HelpTopic, HELP_INDEX_PATH};
+pub use key_bindings::{is_modifier_key, KeyAction, KeyBinding, KeyBindings};
 pub use login_connection::LoginConnection;
 pub use login_state::LoginState;
+pub use logout_state::{LogoutState, LogoutType};
 pub use name_tag_settings::NameTagSettings;
+pub use network_session::NetworkSession;
 pub use network_thread::{run_network_thread, NetworkThread, NetworkThreadMessage};
+pub use quest_objective_table::{
+    QuestObjectiveItem, QuestObjectiveKill, QuestObjectivePosition, QuestObjectiveTable,
+    QuestObjectives, QUEST_OBJECTIVE_TABLE_PATH,
+};
 pub use render_configuration::RenderConfiguration;
+pub use repair_state::{RepairMode, RepairState};
 pub use selected_target::SelectedTarget;
 pub use server_configuration::ServerConfiguration;
 pub use server_list::{ServerList, ServerListGameServer, ServerListWorldServer};
+pub use settings_file::SettingsFile;
 pub use sound_cache::SoundCache;
 pub use sound_settings::SoundSettings;
 pub use specular_texture::SpecularTexture;
+pub use system_func_table::{SystemFuncAction, SystemFuncTable, SYSTEM_FUNC_TABLE_PATH};
 pub use ui_resources::{
     load_ui_resources, ui_requested_cursor_apply_system, update_ui_resources, UiCursorType,
     UiRequestedCursor, UiResources, UiSprite, UiSpriteSheet, UiSpriteSheetType, UiTexture,
@@ -0,0 +1,11 @@
+use bevy::prelude::Resource;
+
+use crate::protocol::{SessionRecorder, SessionReplay};
+
+#[derive(Default, Resource)]
+pub enum NetworkSession {
+    #[default]
+    Live,
+    Record(SessionRecorder),
+    Replay(SessionReplay),
+}
@@ -0,0 +1,467 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use bevy::prelude::Resource;
+use rose_data::{ItemReference, QuestTrigger};
+use rose_file_readers::{
+    QsdCondition, QsdConditionOperator, QsdItem, QsdReward, QsdVariableType, VfsFile,
+    VirtualFilesystem,
+};
+use serde::Deserialize;
+
+use crate::resources::GameData;
+
+/// Optional file which overrides the objectives derived from the quest
+/// triggers, used for quests whose objectives can not be found in the QSD.
+///
+/// ```toml
+/// [[quest]]
+/// id = 12
+/// items = [{ item = 401001, quantity = 5 }]
+/// npcs = [1012]
+/// positions = [{ zone = 2, x = 520000.0, y = 530000.0 }]
+/// ```
+///
+/// Items are in the same base1000 format as `QF_getQuestItemQuantity`, and
+/// positions are world positions. `items` and `npcs` replace the derived
+/// objectives when present.
+pub const QUEST_OBJECTIVE_TABLE_PATH: &str = "3DDATA/QUESTDATA/QUEST_OBJECTIVES.TOML";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuestObjectiveItem {
+    pub item: ItemReference,
+    pub quantity: u32,
+}
+
+/// A monster whose death trigger counts towards a quest variable, ordered by
+/// variable so monsters counting towards the same variable are adjacent.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct QuestObjectiveKill {
+    pub variable: usize,
+    pub npc: u16,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct QuestObjectivePosition {
+    pub zone: u16,
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct QuestObjectives {
+    pub id: usize,
+    pub items: Vec<QuestObjectiveItem>,
+    pub kills: Vec<QuestObjectiveKill>,
+    pub npcs: Vec<u16>,
+    pub positions: Vec<QuestObjectivePosition>,
+
+    /// The value each quest variable must reach to complete the quest.
+    pub required_variables: BTreeMap<usize, u32>,
+}
+
+impl QuestObjectives {
+    pub fn has_npc(&self, npc_id: u16) -> bool {
+        self.npcs.contains(&npc_id) || self.kills.iter().any(|kill| kill.npc == npc_id)
+    }
+}
+
+#[derive(Deserialize)]
+struct QuestObjectiveOverrideItem {
+    item: usize,
+    quantity: u32,
+}
+
+#[derive(Deserialize)]
+struct QuestObjectiveOverride {
+    id: usize,
+    items: Option<Vec<QuestObjectiveOverrideItem>>,
+    npcs: Option<Vec<u16>>,
+    #[serde(default)]
+    positions: Vec<QuestObjectivePosition>,
+}
+
+#[derive(Deserialize)]
+struct QuestObjectiveFile {
+    #[serde(default)]
+    quest: Vec<QuestObjectiveOverride>,
+}
+
+/// The smallest value which passes `value <operator> required`, for conditions
+/// which check a count has been reached.
+fn required_count(operator: QsdConditionOperator, required: u32) -> Option<u32> {
+    match operator {
+        QsdConditionOperator::Equals | QsdConditionOperator::GreaterThanEqual => Some(required),
+        QsdConditionOperator::GreaterThan => Some(required + 1),
+        _ => None,
+    }
+    .filter(|count| *count > 0)
+}
+
+/// Maps quest ids to their objectives.
+#[derive(Default, Resource)]
+pub struct QuestObjectiveTable {
+    quests: HashMap<usize, QuestObjectives>,
+}
+
+impl QuestObjectiveTable {
+    pub fn get(&self, quest_id: usize) -> Option<&QuestObjectives> {
+        self.quests.get(&quest_id)
+    }
+
+    fn get_mut(&mut self, quest_id: usize) -> &mut QuestObjectives {
+        self.quests
+            .entry(quest_id)
+            .or_insert_with(|| QuestObjectives {
+                id: quest_id,
+                ..Default::default()
+            })
+    }
+
+    /// Adds the quest items and quest variable counts checked by the
+    /// conditions of `trigger` to the quest selected before them.
+    pub fn add_trigger(
+        &mut self,
+        trigger: &QuestTrigger,
+        decode_item: impl Fn(&QsdItem) -> Option<ItemReference>,
+    ) {
+        let mut selected_quest = None;
+
+        for condition in trigger.conditions.iter() {
+            match *condition {
+                QsdCondition::SelectQuest { id } => selected_quest = Some(id),
+                QsdCondition::QuestItem {
+                    item: Some(ref item),
+                    equipment_index: None,
+                    required_count: count,
+                    operator,
+                } => {
+                    let (Some(quest_id), Some(item), Some(quantity)) = (
+                        selected_quest,
+                        decode_item(item),
+                        required_count(operator, count),
+                    ) else {
+                        continue;
+                    };
+                    if !item.item_type.is_quest_item() {
+                        continue;
+                    }
+
+                    let objectives = self.get_mut(quest_id);
+                    if let Some(objective_item) = objectives
+                        .items
+                        .iter_mut()
+                        .find(|objective_item| objective_item.item == item)
+                    {
+                        objective_item.quantity = objective_item.quantity.max(quantity);
+                    } else {
+                        objectives.items.push(QuestObjectiveItem { item, quantity });
+                    }
+                }
+                QsdCondition::QuestVariable {
+                    variable_type: QsdVariableType::Variable,
+                    variable_id,
+                    operator,
+                    value,
+                } => {
+                    let (Some(quest_id), Some(quantity)) = (
+                        selected_quest,
+                        required_count(operator, value.max(0) as u32),
+                    ) else {
+                        continue;
+                    };
+
+                    let required = self
+                        .get_mut(quest_id)
+                        .required_variables
+                        .entry(variable_id)
+                        .or_default();
+                    *required = (*required).max(quantity);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Adds `npc_id` as a kill objective of every quest whose variables are
+    /// changed by its death trigger, `triggers` being the trigger and those
+    /// chained after it.
+    pub fn add_death_trigger<'a>(
+        &mut self,
+        npc_id: u16,
+        triggers: impl Iterator<Item = &'a QuestTrigger>,
+    ) {
+        for trigger in triggers {
+            let mut selected_quest = trigger.conditions.iter().find_map(|condition| {
+                if let QsdCondition::SelectQuest { id } = *condition {
+                    Some(id)
+                } else {
+                    None
+                }
+            });
+
+            for reward in trigger.rewards.iter() {
+                match *reward {
+                    QsdReward::SelectQuest { id } => selected_quest = Some(id),
+                    QsdReward::QuestVariable {
+                        variable_type: QsdVariableType::Variable,
+                        variable_id,
+                        ..
+                    } => {
+                        let Some(quest_id) = selected_quest else {
+                            continue;
+                        };
+
+                        let kill = QuestObjectiveKill {
+                            npc: npc_id,
+                            variable: variable_id,
+                        };
+                        let objectives = self.get_mut(quest_id);
+                        if !objectives.kills.contains(&kill) {
+                            objectives.kills.push(kill);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Derives the objectives of every quest from the quest triggers and the
+    /// NPC death triggers.
+    pub fn from_game_data(game_data: &GameData) -> Self {
+        let mut table = Self::default();
+
+        for trigger in game_data.quests.triggers.values() {
+            table.add_trigger(trigger, |item| {
+                game_data
+                    .data_decoder
+                    .decode_item_reference(item.item_number, item.item_type)
+            });
+        }
+
+        for npc_data in game_data.npcs.iter() {
+            if npc_data.death_quest_trigger_name.is_empty() {
+                continue;
+            }
+
+            let mut visited = HashSet::new();
+            let triggers = std::iter::successors(
+                game_data
+                    .quests
+                    .get_trigger_by_name(&npc_data.death_quest_trigger_name),
+                |trigger| {
+                    trigger
+                        .next_trigger_name
+                        .as_ref()
+                        .and_then(|name| game_data.quests.get_trigger_by_name(name))
+                },
+            )
+            .take_while(|trigger| visited.insert(trigger.name.clone()));
+            table.add_death_trigger(npc_data.id.get(), triggers);
+        }
+
+        for objectives in table.quests.values_mut() {
+            objectives.kills.sort();
+        }
+
+        table
+    }
+
+    /// Derives the objectives from `game_data`, then applies any overrides
+    /// from `QUEST_OBJECTIVE_TABLE_PATH` if it exists in the VFS.
+    pub fn load(vfs: &VirtualFilesystem, game_data: &GameData) -> Self {
+        let mut table = Self::from_game_data(game_data);
+
+        let Ok(file) = vfs.open_file(QUEST_OBJECTIVE_TABLE_PATH) else {
+            return table;
+        };
+        let bytes: Vec<u8> = match file {
+            VfsFile::Buffer(buffer) => buffer,
+            VfsFile::View(view) => view.into(),
+        };
+
+        match std::str::from_utf8(&bytes)
+            .map_err(anyhow::Error::from)
+            .and_then(|toml_str| {
+                toml::from_str::<QuestObjectiveFile>(toml_str).map_err(anyhow::Error::from)
+            }) {
+            Ok(file) => {
+                log::info!(
+                    "Loaded objective overrides for {} quests from {}",
+                    file.quest.len(),
+                    QUEST_OBJECTIVE_TABLE_PATH
+                );
+                for quest in file.quest {
+                    let objectives = table.get_mut(quest.id);
+                    if let Some(items) = quest.items {
+                        objectives.items = items
+                            .iter()
+                            .filter_map(|objective_item| {
+                                Some(QuestObjectiveItem {
+                                    item: game_data
+                                        .data_decoder
+                                        .decode_item_base1000(objective_item.item)?,
+                                    quantity: objective_item.quantity,
+                                })
+                            })
+                            .collect();
+                    }
+                    if let Some(npcs) = quest.npcs {
+                        objectives.npcs = npcs;
+                        objectives.kills.clear();
+                    }
+                    objectives.positions = quest.positions;
+                }
+            }
+            Err(error) => {
+                log::error!(
+                    "Failed to load quest objectives from {} with error: {}",
+                    QUEST_OBJECTIVE_TABLE_PATH,
+                    error
+                );
+            }
+        }
+
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rose_data::ItemType;
+    use rose_file_readers::QsdRewardOperator;
+
+    fn trigger(conditions: Vec<QsdCondition>, rewards: Vec<QsdReward>) -> QuestTrigger {
+        QuestTrigger {
+            name: "test".to_string(),
+            conditions,
+            rewards,
+            next_trigger_name: None,
+        }
+    }
+
+    fn quest_item(item_number: usize, operator: QsdConditionOperator, count: u32) -> QsdCondition {
+        QsdCondition::QuestItem {
+            item: Some(QsdItem {
+                item_type: 13,
+                item_number: item_number as _,
+            }),
+            equipment_index: None,
+            required_count: count,
+            operator,
+        }
+    }
+
+    fn decode_quest_item(item: &QsdItem) -> Option<ItemReference> {
+        (item.item_type == 13).then(|| ItemReference::new(ItemType::Quest, item.item_number as _))
+    }
+
+    #[test]
+    fn quest_items_from_conditions() {
+        let mut table = QuestObjectiveTable::default();
+
+        // Items checked before a quest is selected are not objectives
+        table.add_trigger(
+            &trigger(
+                vec![
+                    quest_item(1, QsdConditionOperator::GreaterThanEqual, 2),
+                    QsdCondition::SelectQuest { id: 5 },
+                    quest_item(2, QsdConditionOperator::GreaterThanEqual, 3),
+                    quest_item(3, QsdConditionOperator::GreaterThan, 4),
+                    quest_item(4, QsdConditionOperator::LessThan, 1),
+                ],
+                vec![],
+            ),
+            decode_quest_item,
+        );
+        // The highest required count across triggers is kept
+        table.add_trigger(
+            &trigger(
+                vec![
+                    QsdCondition::SelectQuest { id: 5 },
+                    quest_item(2, QsdConditionOperator::Equals, 10),
+                ],
+                vec![],
+            ),
+            decode_quest_item,
+        );
+
+        assert!(table.get(0).is_none());
+        assert_eq!(
+            table.get(5).unwrap().items,
+            [
+                QuestObjectiveItem {
+                    item: ItemReference::new(ItemType::Quest, 2),
+                    quantity: 10,
+                },
+                QuestObjectiveItem {
+                    item: ItemReference::new(ItemType::Quest, 3),
+                    quantity: 5,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn kills_from_death_triggers() {
+        let mut table = QuestObjectiveTable::default();
+        table.add_trigger(
+            &trigger(
+                vec![
+                    QsdCondition::SelectQuest { id: 7 },
+                    QsdCondition::QuestVariable {
+                        variable_type: QsdVariableType::Variable,
+                        variable_id: 1,
+                        operator: QsdConditionOperator::GreaterThanEqual,
+                        value: 6,
+                    },
+                ],
+                vec![],
+            ),
+            decode_quest_item,
+        );
+
+        let add_kill = |variable_id| QsdReward::QuestVariable {
+            variable_type: QsdVariableType::Variable,
+            variable_id,
+            operator: QsdRewardOperator::Add,
+            value: 1,
+        };
+        let death_triggers = [
+            trigger(vec![QsdCondition::SelectQuest { id: 7 }], vec![add_kill(1)]),
+            trigger(vec![], vec![QsdReward::SelectQuest { id: 8 }, add_kill(0)]),
+            // Variables changed without a selected quest are ignored
+            trigger(vec![], vec![add_kill(2)]),
+        ];
+        table.add_death_trigger(301, death_triggers.iter());
+        table.add_death_trigger(302, death_triggers[..1].iter());
+
+        let objectives = table.get(7).unwrap();
+        assert_eq!(
+            objectives.kills,
+            [
+                QuestObjectiveKill {
+                    npc: 301,
+                    variable: 1
+                },
+                QuestObjectiveKill {
+                    npc: 302,
+                    variable: 1
+                },
+            ]
+        );
+        assert_eq!(objectives.required_variables.get(&1), Some(&6));
+        assert!(objectives.has_npc(302));
+        assert!(!objectives.has_npc(303));
+
+        assert_eq!(
+            table.get(8).unwrap().kills,
+            [QuestObjectiveKill {
+                npc: 301,
+                variable: 0
+            }]
+        );
+    }
+}
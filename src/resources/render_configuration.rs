@@ -1,7 +1,17 @@
-use bevy::prelude::Resource;
+use bevy::{math::Vec2, prelude::Resource};
 
 #[derive(Resource)]
 pub struct RenderConfiguration {
     pub passthrough_terrain_textures: bool,
     pub trail_effect_duration_multiplier: f32,
+
+    pub fullscreen: bool,
+    pub window_size: Vec2,
+    pub disable_vsync: bool,
+
+    /// Half the width of the area around the player which casts shadows
+    pub shadow_distance: f32,
+
+    /// Scales the distance at which fog starts, the zone fog density is divided by this
+    pub fog_distance_multiplier: f32,
 }
@@ -0,0 +1,30 @@
+use bevy::prelude::Resource;
+
+use rose_game_common::{components::ItemSlot, messages::ClientEntityId};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RepairMode {
+    UsingNpc { npc_entity_id: ClientEntityId },
+    UsingItem { use_item_slot: ItemSlot },
+}
+
+/// Whilst a repair mode is active, clicking an equipment item in the inventory
+/// will request for it to be repaired.
+#[derive(Default, Resource)]
+pub struct RepairState {
+    pub mode: Option<RepairMode>,
+}
+
+impl RepairState {
+    pub fn start(&mut self, mode: RepairMode) {
+        self.mode = Some(mode);
+    }
+
+    pub fn cancel(&mut self) {
+        self.mode = None;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.mode.is_some()
+    }
+}
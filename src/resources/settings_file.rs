@@ -0,0 +1,109 @@
+use std::path::PathBuf;
+
+use bevy::prelude::Resource;
+
+/// The config.toml which settings changed in game are saved back to.
+#[derive(Resource)]
+pub struct SettingsFile {
+    pub path: PathBuf,
+}
+
+impl SettingsFile {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Applies `update` to the file contents, editing the document in place so
+    /// that comments, formatting and unknown keys are preserved.
+    pub fn update(
+        &self,
+        update: impl FnOnce(&mut toml_edit::Document),
+    ) -> Result<(), anyhow::Error> {
+        let toml_str = match std::fs::read_to_string(&self.path) {
+            Ok(toml_str) => toml_str,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(error.into()),
+        };
+
+        let mut document = toml_str.parse::<toml_edit::Document>()?;
+        update(&mut document);
+
+        if let Some(parent) = self.path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+        std::fs::write(&self.path, document.to_string())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_preserves_comments_and_unknown_keys() {
+        let path = std::env::temp_dir().join(format!(
+            "rose-offline-client-settings-test-{}.toml",
+            std::process::id()
+        ));
+        std::fs::write(
+            &path,
+            r#"# My settings
+[graphics]
+# Keep the shadows short
+shadow_distance = 100.0
+unknown_graphics_key = "kept"
+
+[my_own_table]
+value = 1 # trailing comment
+"#,
+        )
+        .unwrap();
+
+        let settings_file = SettingsFile::new(path.clone());
+        let result = settings_file.update(|document| {
+            document["graphics"]["shadow_distance"] = toml_edit::value(200.0);
+            document["sound"]["enabled"] = toml_edit::value(false);
+        });
+        let toml_str = std::fs::read_to_string(&path);
+        std::fs::remove_file(&path).ok();
+        result.unwrap();
+        let toml_str = toml_str.unwrap();
+
+        assert!(toml_str.contains("# My settings"));
+        assert!(toml_str.contains("# Keep the shadows short"));
+        assert!(toml_str.contains("unknown_graphics_key = \"kept\""));
+        assert!(toml_str.contains("value = 1 # trailing comment"));
+
+        let document = toml_str.parse::<toml_edit::Document>().unwrap();
+        assert_eq!(
+            document["graphics"]["shadow_distance"].as_float(),
+            Some(200.0)
+        );
+        assert_eq!(document["sound"]["enabled"].as_bool(), Some(false));
+        assert_eq!(document["my_own_table"]["value"].as_integer(), Some(1));
+    }
+
+    #[test]
+    fn update_creates_missing_file() {
+        let path = std::env::temp_dir()
+            .join(format!(
+                "rose-offline-client-settings-missing-test-{}",
+                std::process::id()
+            ))
+            .join("config.toml");
+
+        let settings_file = SettingsFile::new(path.clone());
+        let result = settings_file.update(|document| {
+            document["gameplay"]["auto_pickup"] = toml_edit::value(true);
+        });
+        let toml_str = std::fs::read_to_string(&path);
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+        result.unwrap();
+
+        let document = toml_str.unwrap().parse::<toml_edit::Document>().unwrap();
+        assert_eq!(document["gameplay"]["auto_pickup"].as_bool(), Some(true));
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use bevy::prelude::Resource;
+use rose_file_readers::{VfsFile, VirtualFilesystem};
+use serde::Deserialize;
+
+/// Optional file which can add or replace entries in the system function table.
+///
+/// Each key is a system function name followed by a list of actions, e.g.
+/// ```toml
+/// [[owl]]
+/// type = "open_conversation"
+/// path = "3DDATA/EVENT/OBJECT007.CON"
+/// ```
+pub const SYSTEM_FUNC_TABLE_PATH: &str = "3DDATA/EVENT/SYSTEM_FUNC.TOML";
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SystemFuncAction {
+    OpenConversation { path: String },
+    PlayEffect { path: String },
+    QuestTrigger { name: String },
+    Warp { warp_gate_id: u16 },
+}
+
+/// Maps system function names, as called by event objects and quest rewards,
+/// to the actions to perform.
+#[derive(Default, Resource)]
+pub struct SystemFuncTable {
+    functions: HashMap<String, Vec<SystemFuncAction>>,
+}
+
+impl SystemFuncTable {
+    pub fn get(&self, name: &str) -> Option<&[SystemFuncAction]> {
+        self.functions.get(name).map(|actions| actions.as_slice())
+    }
+
+    pub fn insert(&mut self, name: String, actions: Vec<SystemFuncAction>) {
+        self.functions.insert(name, actions);
+    }
+
+    /// The irose system functions, extended by `SYSTEM_FUNC_TABLE_PATH` if it
+    /// exists in the VFS.
+    pub fn load(vfs: &VirtualFilesystem) -> Self {
+        let mut table = Self::irose();
+
+        let Ok(file) = vfs.open_file(SYSTEM_FUNC_TABLE_PATH) else {
+            return table;
+        };
+        let bytes: Vec<u8> = match file {
+            VfsFile::Buffer(buffer) => buffer,
+            VfsFile::View(view) => view.into(),
+        };
+
+        match std::str::from_utf8(&bytes)
+            .map_err(anyhow::Error::from)
+            .and_then(|toml_str| {
+                toml::from_str::<HashMap<String, Vec<SystemFuncAction>>>(toml_str)
+                    .map_err(anyhow::Error::from)
+            }) {
+            Ok(functions) => {
+                log::info!(
+                    "Loaded {} system functions from {}",
+                    functions.len(),
+                    SYSTEM_FUNC_TABLE_PATH
+                );
+                table.functions.extend(functions);
+            }
+            Err(error) => {
+                log::error!(
+                    "Failed to load system functions from {} with error: {}",
+                    SYSTEM_FUNC_TABLE_PATH,
+                    error
+                );
+            }
+        }
+
+        table
+    }
+
+    fn irose() -> Self {
+        let mut table = Self::default();
+
+        for (name, path) in [
+            ("Lunar_Warp_Gate01", "3DDATA/EVENT/OBJECT001.CON"),
+            ("mushroom", "3DDATA/EVENT/OBJECT002.CON"),
+            ("sandglass", "3DDATA/EVENT/OBJECT003.CON"),
+            ("horriblebook", "3DDATA/EVENT/OBJECT004.CON"),
+            ("piramid01", "3DDATA/EVENT/OBJECT005.CON"),
+            ("piramid02", "3DDATA/EVENT/OBJECT006.CON"),
+            ("piramid03", "3DDATA/EVENT/OBJECT005.CON"),
+            ("owl", "3DDATA/EVENT/OBJECT007.CON"),
+            ("mana", "3DDATA/EVENT/OBJECT008.CON"),
+            ("genzistone", "3DDATA/EVENT/OBJECT009.CON"),
+        ] {
+            table.insert(
+                name.to_string(),
+                vec![SystemFuncAction::OpenConversation {
+                    path: path.to_string(),
+                }],
+            );
+        }
+
+        table
+    }
+}
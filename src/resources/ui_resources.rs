@@ -112,9 +112,11 @@ pub struct UiResources {
     pub dialog_character_info: Handle<Dialog>,
     pub dialog_chatbox: Handle<Dialog>,
     pub dialog_clan: Handle<Dialog>,
+    pub dialog_community: Handle<Dialog>,
     pub dialog_create_avatar: Handle<Dialog>,
     pub dialog_create_clan: Handle<Dialog>,
     pub dialog_game_menu: Handle<Dialog>,
+    pub dialog_help: Handle<Dialog>,
     pub dialog_inventory: Handle<Dialog>,
     pub dialog_message_box: Handle<Dialog>,
     pub dialog_number_input: Handle<Dialog>,
@@ -128,8 +130,11 @@ pub struct UiResources {
     pub dialog_quest_list: Handle<Dialog>,
     pub dialog_respawn: Handle<Dialog>,
     pub dialog_select_server: Handle<Dialog>,
+    pub dialog_separate: Handle<Dialog>,
     pub dialog_skill_list: Handle<Dialog>,
     pub dialog_skill_tree: Handle<Dialog>,
+    pub dialog_system: Handle<Dialog>,
+    pub dialog_upgrade: Handle<Dialog>,
     pub skill_tree_dealer: Handle<Dialog>,
     pub skill_tree_hawker: Handle<Dialog>,
     pub skill_tree_muse: Handle<Dialog>,
@@ -142,6 +147,9 @@ pub struct UiResources {
 pub struct UiRequestedCursor {
     pub moving_camera: bool,
     pub world_cursor: UiCursorType,
+
+    // Used over both the world and ui, e.g. whilst in repair mode
+    pub override_cursor: Option<UiCursorType>,
 }
 
 impl UiResources {
@@ -521,11 +529,13 @@ pub fn load_ui_resources(
         dialog_character_info: dialog_files["DLGAVATA.XML"].clone(),
         dialog_chatbox: dialog_files["DLGCHAT.XML"].clone(),
         dialog_clan: dialog_files["DLGCLAN.XML"].clone(),
+        dialog_community: dialog_files["DLGCOMM.XML"].clone(),
         dialog_create_avatar: dialog_files[
             "DLGCREATEAVATAR.XML"].clone(),
             dialog_create_clan: dialog_files[
                 "DLGORGANIZECLAN.XML"].clone(),
         dialog_game_menu: dialog_files["DLGMENU.XML"].clone(),
+        dialog_help: dialog_files["DLGHELP.XML"].clone(),
         dialog_inventory: dialog_files["DLGITEM.XML"].clone(),
         dialog_login: dialog_files["DLGLOGIN.XML"].clone(),
         dialog_message_box: dialog_files["MSGBOX.XML"].clone(),
@@ -540,8 +550,11 @@ pub fn load_ui_resources(
         dialog_quest_list: dialog_files["DLGQUEST.XML"].clone(),
         dialog_respawn: dialog_files["DLGRESTART.XML"].clone(),
         dialog_select_server: dialog_files["DLGSELSVR.XML"].clone(),
+        dialog_separate: dialog_files["DLGSEPARATE.XML"].clone(),
         dialog_skill_list: dialog_files["DLGSKILL.XML"].clone(),
         dialog_skill_tree: dialog_files["DLGSKILLTREE.XML"].clone(),
+        dialog_system: dialog_files["DLGSYSTEM.XML"].clone(),
+        dialog_upgrade: dialog_files["DLGUPGRADE.XML"].clone(),
         skill_tree_dealer: dialog_files["SKILLTREE_DEALER.XML"].clone(),
         skill_tree_hawker: dialog_files["SKILLTREE_HOWKER.XML"].clone(),
         skill_tree_muse: dialog_files["SKILLTREE_MUSE.XML"].clone(),
@@ -576,7 +589,16 @@ pub fn ui_requested_cursor_apply_system(
         return;
     };
 
-    if egui_ctx.ctx_mut().wants_pointer_input() {
+    if let Some(override_cursor) = ui_requested_cursor.override_cursor {
+        let requested_icon = ui_resources.cursors[override_cursor]
+            .cursor
+            .as_ref()
+            .unwrap_or(&CursorIcon::Default);
+
+        if window.cursor.icon != *requested_icon {
+            window.cursor.icon = requested_icon.clone();
+        }
+    } else if egui_ctx.ctx_mut().wants_pointer_input() {
         // Allow text selection cursor, otherwise use the default in game cursor icon
         let requested_icon = match egui_requested_cursor.cursor {
             CursorIcon::Text => &CursorIcon::Text,
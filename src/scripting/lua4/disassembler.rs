@@ -0,0 +1,420 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    fmt::Write,
+};
+
+use crate::scripting::lua4::{Lua4Function, Lua4Instruction};
+
+/// Something in a chunk which `Lua4VM` would fail on when executed.
+#[derive(Debug, Clone)]
+pub struct Lua4DisassemblyIssue {
+    pub function_line: u32,
+    pub pc: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Lua4Disassembly {
+    pub text: String,
+    pub globals_read: BTreeSet<String>,
+    pub globals_written: BTreeSet<String>,
+    pub issues: Vec<Lua4DisassemblyIssue>,
+}
+
+#[derive(Default)]
+pub struct Lua4Disassembler {
+    /// Globals provided by the host before the chunk is run, e.g. rust closures and constants.
+    pub defined_globals: HashSet<String>,
+
+    /// Globals which are provided by the host but do nothing.
+    pub stubbed_globals: HashSet<String>,
+}
+
+/// Lua 4 line info stores the pc at which each line starts, with negative
+/// values used to skip multiple lines, see luaG_getline. Lines are counted
+/// from the start of the chunk for every function, not from `function.line`.
+fn get_line(function: &Lua4Function, pc: usize) -> Option<u32> {
+    let line_infos = &function.line_infos;
+    let line_info = |index: usize| line_infos.get(index).map(|value| *value as i32);
+
+    let mut line = 1;
+    let mut index = 0;
+    if line_info(index)? < 0 {
+        line -= line_info(index)?;
+        index += 1;
+    }
+
+    loop {
+        let mut next_line = line + 1;
+        let mut next_index = index + 1;
+        match line_info(next_index) {
+            Some(skip) if skip < 0 => {
+                next_line -= skip;
+                next_index += 1;
+            }
+            Some(_) => {}
+            None => break,
+        }
+
+        match line_info(next_index) {
+            Some(next_pc) if next_pc as usize <= pc => {
+                line = next_line;
+                index = next_index;
+            }
+            _ => break,
+        }
+    }
+
+    Some(line as u32)
+}
+
+fn collect_globals_written(function: &Lua4Function, globals: &mut BTreeSet<String>) {
+    for instruction in function.instructions.iter() {
+        if let Lua4Instruction::OP_SETGLOBAL(kstr) = *instruction {
+            if let Some(name) = function.constant_strings.get(kstr as usize) {
+                globals.insert(name.clone());
+            }
+        }
+    }
+
+    for constant_function in function.constant_functions.iter() {
+        collect_globals_written(constant_function, globals);
+    }
+}
+
+impl Lua4Disassembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn disassemble(&self, function: &Lua4Function) -> Lua4Disassembly {
+        let mut disassembly = Lua4Disassembly::default();
+        collect_globals_written(function, &mut disassembly.globals_written);
+        self.disassemble_function(function, None, 0, &mut disassembly);
+        disassembly
+    }
+
+    fn disassemble_function(
+        &self,
+        function: &Lua4Function,
+        num_upvalues: Option<u32>,
+        depth: usize,
+        disassembly: &mut Lua4Disassembly,
+    ) {
+        let indent = "  ".repeat(depth);
+        let out = &mut disassembly.text;
+
+        writeln!(
+            out,
+            "{}function <{}:{}> ({} instructions, {}{} parameters, {} upvalues, {} stack)",
+            indent,
+            function.source,
+            function.line,
+            function.instructions.len(),
+            function.num_parameters,
+            if function.is_var_arg { "+" } else { "" },
+            num_upvalues.unwrap_or(0),
+            function.max_stack_size
+        )
+        .ok();
+
+        if !function.constant_strings.is_empty() {
+            writeln!(
+                out,
+                "{}strings ({}):",
+                indent,
+                function.constant_strings.len()
+            )
+            .ok();
+            for (index, string) in function.constant_strings.iter().enumerate() {
+                writeln!(out, "{}  [{}] {:?}", indent, index, string).ok();
+            }
+        }
+
+        if !function.constant_numbers.is_empty() {
+            writeln!(
+                out,
+                "{}numbers ({}):",
+                indent,
+                function.constant_numbers.len()
+            )
+            .ok();
+            for (index, number) in function.constant_numbers.iter().enumerate() {
+                writeln!(out, "{}  [{}] {}", indent, index, number).ok();
+            }
+        }
+
+        if !function.local_vars.is_empty() {
+            writeln!(out, "{}locals ({}):", indent, function.local_vars.len()).ok();
+            for (index, local_var) in function.local_vars.iter().enumerate() {
+                writeln!(
+                    out,
+                    "{}  [{}] {} pc {}-{}",
+                    indent, index, local_var.name, local_var.start_pc, local_var.end_pc
+                )
+                .ok();
+            }
+        }
+
+        // Number of upvalues each nested function is created with
+        let mut constant_function_upvalues = vec![None; function.constant_functions.len()];
+
+        for (pc, instruction) in function.instructions.iter().enumerate() {
+            let mut issues = Vec::new();
+            let constant_string = |kstr: u32, issues: &mut Vec<String>| {
+                function
+                    .constant_strings
+                    .get(kstr as usize)
+                    .map(|string| format!("{:?}", string))
+                    .unwrap_or_else(|| {
+                        issues.push(format!("string constant {} out of range", kstr));
+                        "?".to_string()
+                    })
+            };
+            let jump_target = |target: i32, issues: &mut Vec<String>| {
+                let target_pc = pc as i32 + 1 + target;
+                if target_pc < 0 || target_pc as usize >= function.instructions.len() {
+                    issues.push(format!("jump target {} out of range", target_pc));
+                }
+                format!("to [{:03}]", target_pc)
+            };
+
+            let comment = match *instruction {
+                Lua4Instruction::OP_PUSHSTRING(kstr)
+                | Lua4Instruction::OP_GETDOTTED(kstr)
+                | Lua4Instruction::OP_PUSHSELF(kstr) => constant_string(kstr, &mut issues),
+                Lua4Instruction::OP_GETGLOBAL(kstr) => {
+                    let comment = constant_string(kstr, &mut issues);
+                    if let Some(name) = function.constant_strings.get(kstr as usize) {
+                        if self.stubbed_globals.contains(name) {
+                            issues.push(format!("global {} is only stubbed", name));
+                        } else if !self.defined_globals.contains(name)
+                            && !disassembly.globals_written.contains(name)
+                        {
+                            issues.push(format!("global {} is not defined", name));
+                        }
+                        disassembly.globals_read.insert(name.clone());
+                    }
+                    comment
+                }
+                Lua4Instruction::OP_SETGLOBAL(kstr) => constant_string(kstr, &mut issues),
+                Lua4Instruction::OP_PUSHNUM(knum) | Lua4Instruction::OP_PUSHNEGNUM(knum) => {
+                    match function.constant_numbers.get(knum as usize) {
+                        Some(number) => format!("{}", number),
+                        None => {
+                            issues.push(format!("number constant {} out of range", knum));
+                            "?".to_string()
+                        }
+                    }
+                }
+                Lua4Instruction::OP_PUSHUPVALUE(index) => {
+                    if num_upvalues.map_or(false, |num_upvalues| index >= num_upvalues) {
+                        issues.push(format!("upvalue {} out of range", index));
+                    }
+                    String::new()
+                }
+                Lua4Instruction::OP_GETLOCAL(index) | Lua4Instruction::OP_SETLOCAL(index) => {
+                    function
+                        .local_vars
+                        .iter()
+                        .filter(|local_var| {
+                            local_var.start_pc as usize <= pc && pc < local_var.end_pc as usize
+                        })
+                        .nth(index as usize)
+                        .map(|local_var| local_var.name.clone())
+                        .unwrap_or_default()
+                }
+                Lua4Instruction::OP_JMPNE(target)
+                | Lua4Instruction::OP_JMPEQ(target)
+                | Lua4Instruction::OP_JMPLT(target)
+                | Lua4Instruction::OP_JMPLE(target)
+                | Lua4Instruction::OP_JMPGT(target)
+                | Lua4Instruction::OP_JMPGE(target)
+                | Lua4Instruction::OP_JMPT(target)
+                | Lua4Instruction::OP_JMPF(target)
+                | Lua4Instruction::OP_JMPONT(target)
+                | Lua4Instruction::OP_JMPONF(target)
+                | Lua4Instruction::OP_JMP(target)
+                | Lua4Instruction::OP_FORPREP(target)
+                | Lua4Instruction::OP_FORLOOP(target)
+                | Lua4Instruction::OP_LFORPREP(target)
+                | Lua4Instruction::OP_LFORLOOP(target) => jump_target(target, &mut issues),
+                Lua4Instruction::OP_CLOSURE(kproto, num_upvalues) => {
+                    match constant_function_upvalues.get_mut(kproto as usize) {
+                        Some(upvalues) => {
+                            upvalues.get_or_insert(num_upvalues);
+                            format!("function {}", kproto)
+                        }
+                        None => {
+                            issues.push(format!("function constant {} out of range", kproto));
+                            "?".to_string()
+                        }
+                    }
+                }
+                _ => String::new(),
+            };
+
+            let line = get_line(function, pc)
+                .map(|line| format!("{}", line))
+                .unwrap_or_else(|| "-".to_string());
+            let instruction = format!("{:?}", instruction);
+            writeln!(
+                disassembly.text,
+                "{}  [{:03}] line {:<5} {:<24} {}{}",
+                indent,
+                pc,
+                line,
+                instruction,
+                if comment.is_empty() { "" } else { "; " },
+                comment
+            )
+            .ok();
+
+            for message in issues {
+                writeln!(disassembly.text, "{}        ^ {}", indent, message).ok();
+                disassembly.issues.push(Lua4DisassemblyIssue {
+                    function_line: function.line,
+                    pc,
+                    message,
+                });
+            }
+        }
+
+        for (constant_function, num_upvalues) in function
+            .constant_functions
+            .iter()
+            .zip(constant_function_upvalues)
+        {
+            writeln!(disassembly.text).ok();
+            self.disassemble_function(constant_function, num_upvalues, depth + 1, disassembly);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scripting::lua4::vm::tests::{
+        compile, op, op_s, op_u, TestFunction, OP_CLOSURE, OP_END, OP_GETGLOBAL, OP_JMP,
+        OP_PUSHINT, OP_RETURN, OP_SETGLOBAL,
+    };
+
+    fn load(function: TestFunction) -> std::sync::Arc<Lua4Function> {
+        Lua4Function::from_bytes(&compile(&function)).unwrap()
+    }
+
+    #[test]
+    fn line_info() {
+        // 1: a = 1
+        // 2:
+        // 3: b = 2
+        // 4: c = 3
+        // ...
+        // 8: d = 4
+        // 9:
+        // 10: function f()
+        // 11:   return 5
+        // 12: end
+        let function = load(TestFunction {
+            line_infos: vec![0, -1, 2, 4, -3, 6, -1, 8],
+            strings: vec!["a", "b", "c", "d", "f"],
+            functions: vec![TestFunction {
+                line: 10,
+                line_infos: vec![-10, 0, 2],
+                instructions: vec![op_s(OP_PUSHINT, 5), op_u(OP_RETURN, 0), op(OP_END)],
+                ..Default::default()
+            }],
+            instructions: vec![
+                op_s(OP_PUSHINT, 1),
+                op_u(OP_SETGLOBAL, 0),
+                op_s(OP_PUSHINT, 2),
+                op_u(OP_SETGLOBAL, 1),
+                op_s(OP_PUSHINT, 3),
+                op_u(OP_SETGLOBAL, 2),
+                op_s(OP_PUSHINT, 4),
+                op_u(OP_SETGLOBAL, 3),
+                op_u(OP_CLOSURE, 0),
+                op_u(OP_SETGLOBAL, 4),
+                op(OP_END),
+            ],
+            ..Default::default()
+        });
+
+        let lines: Vec<_> = (0..function.instructions.len())
+            .map(|pc| get_line(&function, pc))
+            .collect();
+        assert_eq!(
+            lines,
+            [1, 1, 3, 3, 4, 4, 8, 8, 10, 10, 10].map(Some).to_vec()
+        );
+
+        // Nested functions count lines from the start of the chunk too
+        let nested = &function.constant_functions[0];
+        assert_eq!(get_line(nested, 0), Some(11));
+        assert_eq!(get_line(nested, 1), Some(11));
+        assert_eq!(get_line(nested, 2), Some(12));
+
+        let disassembly = Lua4Disassembler::new().disassemble(&function);
+        assert!(disassembly.text.contains("[008] line 10 "));
+        assert!(disassembly.text.contains("[002] line 12 "));
+    }
+
+    #[test]
+    fn no_line_info() {
+        let function = load(TestFunction {
+            instructions: vec![op(OP_END)],
+            ..Default::default()
+        });
+        assert_eq!(get_line(&function, 0), None);
+        assert!(Lua4Disassembler::new()
+            .disassemble(&function)
+            .text
+            .contains("[000] line -     "));
+    }
+
+    #[test]
+    fn issues() {
+        let mut disassembler = Lua4Disassembler::new();
+        disassembler.defined_globals.insert("defined".to_string());
+        disassembler.stubbed_globals.insert("stubbed".to_string());
+
+        let function = load(TestFunction {
+            strings: vec!["defined", "stubbed", "missing", "written"],
+            instructions: vec![
+                op_u(OP_GETGLOBAL, 0),
+                op_u(OP_GETGLOBAL, 1),
+                op_u(OP_GETGLOBAL, 2),
+                op_u(OP_GETGLOBAL, 3),
+                op_u(OP_SETGLOBAL, 3),
+                op_u(OP_GETGLOBAL, 4),
+                op_s(OP_JMP, 5),
+                op(OP_END),
+            ],
+            ..Default::default()
+        });
+
+        let disassembly = disassembler.disassemble(&function);
+        let issues: Vec<_> = disassembly
+            .issues
+            .iter()
+            .map(|issue| (issue.pc, issue.message.as_str()))
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                (1, "global stubbed is only stubbed"),
+                (2, "global missing is not defined"),
+                (5, "string constant 4 out of range"),
+                (6, "jump target 12 out of range"),
+            ]
+        );
+        assert_eq!(
+            disassembly.globals_read.iter().collect::<Vec<_>>(),
+            ["defined", "missing", "stubbed", "written"]
+        );
+        assert_eq!(
+            disassembly.globals_written.iter().collect::<Vec<_>>(),
+            ["written"]
+        );
+    }
+}
@@ -1,9 +1,15 @@
+mod disassembler;
 mod function;
 mod instruction;
+mod table;
+mod trace;
 mod value;
 mod vm;
 
+pub use disassembler::{Lua4Disassembler, Lua4Disassembly, Lua4DisassemblyIssue};
 pub use function::Lua4Function;
 pub use instruction::Lua4Instruction;
+pub use table::Lua4Table;
+pub use trace::{Lua4TraceEntry, Lua4VMTrace};
 pub use value::Lua4Value;
 pub use vm::{Lua4VM, Lua4VMError, Lua4VMRustClosures};
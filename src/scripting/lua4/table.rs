@@ -0,0 +1,78 @@
+use crate::scripting::lua4::{Lua4VMError, Lua4Value};
+
+/// A Lua table, entries are kept in insertion order so that iterating with
+/// OP_LFORPREP / OP_LFORLOOP is deterministic.
+///
+/// Assigning nil to a key keeps the entry with a nil value, which allows a
+/// script to clear fields of a table whilst iterating over it.
+#[derive(Debug, Default)]
+pub struct Lua4Table {
+    entries: Vec<(Lua4Value, Lua4Value)>,
+}
+
+impl Lua4Table {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    fn find(&self, key: &Lua4Value) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|(entry_key, _)| entry_key == key)
+    }
+
+    pub fn get(&self, key: &Lua4Value) -> Lua4Value {
+        self.find(key)
+            .map(|index| self.entries[index].1.clone())
+            .unwrap_or(Lua4Value::Nil)
+    }
+
+    pub fn set(&mut self, key: Lua4Value, value: Lua4Value) -> Result<(), Lua4VMError> {
+        match key {
+            Lua4Value::Nil => return Err(Lua4VMError::InvalidTableKey),
+            Lua4Value::Number(number) if number.is_nan() => {
+                return Err(Lua4VMError::InvalidTableKey)
+            }
+            _ => {}
+        }
+
+        if let Some(index) = self.find(&key) {
+            self.entries[index].1 = value;
+        } else if !matches!(value, Lua4Value::Nil) {
+            self.entries.push((key, value));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the entry after `key`, or the first entry if `key` is nil.
+    pub fn next(&self, key: &Lua4Value) -> Option<(Lua4Value, Lua4Value)> {
+        let start = match key {
+            Lua4Value::Nil => 0,
+            key => self.find(key)? + 1,
+        };
+
+        self.entries[start..]
+            .iter()
+            .find(|(_, value)| !matches!(value, Lua4Value::Nil))
+            .cloned()
+    }
+
+    /// The number of non-nil values in the table.
+    pub fn len(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|(_, value)| !matches!(value, Lua4Value::Nil))
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
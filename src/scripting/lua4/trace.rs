@@ -0,0 +1,78 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::scripting::lua4::{Lua4Instruction, Lua4Value};
+
+/// Maximum number of entries kept in a trace, older entries are discarded first.
+const MAX_TRACE_ENTRIES: usize = 20000;
+
+#[derive(Clone, Debug)]
+pub enum Lua4TraceEntry {
+    /// A global closure was called by the host, e.g. a conversation condition function.
+    CallGlobal {
+        name: String,
+        parameters: Vec<Lua4Value>,
+    },
+    /// The result of a previous `CallGlobal`.
+    ReturnGlobal {
+        name: String,
+        results: Result<Vec<Lua4Value>, String>,
+    },
+    /// An instruction about to be executed, the stack and upvalues are only
+    /// recorded whilst `Lua4VMTrace::capture_stack` is set.
+    Instruction {
+        function_source: String,
+        function_line: u32,
+        pc: usize,
+        instruction: Lua4Instruction,
+        stack: Option<Vec<Lua4Value>>,
+        upvalues: Option<Vec<Lua4Value>>,
+    },
+    /// A call from the script into a rust closure.
+    RustClosure {
+        name: String,
+        parameters: Vec<Lua4Value>,
+        results: Result<Vec<Lua4Value>, String>,
+    },
+}
+
+/// Records the execution of a [`Lua4VM`](crate::scripting::lua4::Lua4VM) so
+/// that it can be inspected afterwards, enabled by setting `Lua4VM::trace`.
+#[derive(Default)]
+pub struct Lua4VMTrace {
+    entries: VecDeque<Lua4TraceEntry>,
+    num_discarded: usize,
+
+    /// When set, a copy of the stack and upvalues is recorded with every
+    /// instruction. This is expensive so should only be set whilst someone is
+    /// looking at the trace.
+    pub capture_stack: bool,
+
+    /// The globals of the VM when the last call from the host returned.
+    pub globals: HashMap<String, Lua4Value>,
+}
+
+impl Lua4VMTrace {
+    pub fn push(&mut self, entry: Lua4TraceEntry) {
+        if self.entries.len() >= MAX_TRACE_ENTRIES {
+            self.entries.pop_front();
+            self.num_discarded += 1;
+        }
+
+        self.entries.push_back(entry);
+    }
+
+    pub fn entries(&self) -> &VecDeque<Lua4TraceEntry> {
+        &self.entries
+    }
+
+    /// The number of entries which were discarded because the trace was full.
+    pub fn num_discarded(&self) -> usize {
+        self.num_discarded
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.num_discarded = 0;
+        self.globals.clear();
+    }
+}
@@ -1,8 +1,12 @@
-use std::{any::Any, cmp::Ordering, sync::Arc};
+use std::{
+    any::Any,
+    cmp::Ordering,
+    sync::{Arc, Mutex},
+};
 
 use num_traits::ToPrimitive;
 
-use crate::scripting::lua4::Lua4Function;
+use crate::scripting::lua4::{Lua4Function, Lua4Table};
 
 #[derive(Clone, Debug)]
 pub enum Lua4Value {
@@ -10,12 +14,16 @@ pub enum Lua4Value {
     UserData(Arc<dyn Any + Send + Sync>),
     Number(f64),
     String(String),
-    Table,
+    Table(Arc<Mutex<Lua4Table>>),
     Closure(Arc<Lua4Function>, Vec<Lua4Value>),
     RustClosure(String),
 }
 
 impl Lua4Value {
+    pub fn new_table(table: Lua4Table) -> Self {
+        Lua4Value::Table(Arc::new(Mutex::new(table)))
+    }
+
     pub fn to_user_type<T: Any>(&self) -> Result<&T, LuaValueConversionError> {
         if let Lua4Value::UserData(user_data) = self {
             user_data
@@ -69,7 +77,13 @@ impl PartialEq for Lua4Value {
                     false
                 }
             }
-            Lua4Value::Table => todo!("Implement LuaValue::Table"),
+            Lua4Value::Table(value) => {
+                if let Lua4Value::Table(other) = other {
+                    Arc::ptr_eq(value, other)
+                } else {
+                    false
+                }
+            }
             Lua4Value::UserData(_) => false,
             Lua4Value::Closure(_, _) => false,
             Lua4Value::RustClosure(_) => false,
@@ -226,9 +240,47 @@ impl TryFrom<&Lua4Value> for String {
 
     fn try_from(value: &Lua4Value) -> Result<Self, Self::Error> {
         match value {
-            Lua4Value::Number(number) => Ok(format!("{}", *number)),
+            Lua4Value::Number(number) => Ok(format_lua_number(*number)),
             Lua4Value::String(string) => Ok(string.clone()),
             _ => Err(LuaValueConversionError::InvalidType),
         }
     }
 }
+
+/// Formats a number the same as Lua's `tostring`, which uses `"%.14g"`.
+pub fn format_lua_number(number: f64) -> String {
+    const PRECISION: i32 = 14;
+
+    if number.is_nan() {
+        return "nan".to_string();
+    } else if number.is_infinite() {
+        return if number > 0.0 { "inf" } else { "-inf" }.to_string();
+    } else if number == 0.0 {
+        return if number.is_sign_negative() { "-0" } else { "0" }.to_string();
+    }
+
+    // The exponent after rounding to the precision decides between %e and %f
+    let exponential = format!("{:.*e}", (PRECISION - 1) as usize, number);
+    let (mantissa, exponent) = exponential.split_once('e').unwrap();
+    let exponent: i32 = exponent.parse().unwrap();
+
+    if (-4..PRECISION).contains(&exponent) {
+        let fixed = format!("{:.*}", (PRECISION - 1 - exponent) as usize, number);
+        trim_fraction_zeros(&fixed).to_string()
+    } else {
+        format!(
+            "{}e{}{:02}",
+            trim_fraction_zeros(mantissa),
+            if exponent < 0 { '-' } else { '+' },
+            exponent.abs()
+        )
+    }
+}
+
+fn trim_fraction_zeros(number: &str) -> &str {
+    if number.contains('.') {
+        number.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        number
+    }
+}
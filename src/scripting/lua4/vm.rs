@@ -1,7 +1,20 @@
-use std::{collections::HashMap, fmt::Write};
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    sync::{Arc, Mutex},
+};
 use thiserror::Error;
 
-use crate::scripting::lua4::{Lua4Function, Lua4Instruction, Lua4Value};
+use crate::scripting::lua4::{
+    value::format_lua_number, Lua4Function, Lua4Instruction, Lua4Table, Lua4TraceEntry,
+    Lua4VMTrace, Lua4Value,
+};
+
+/// OP_CALL number of results which means keep all results
+const MULT_RET: u32 = 255;
+
+/// Number of list items set by each OP_SETLIST
+const LFIELDS_PER_FLUSH: u32 = 64;
 
 #[derive(Debug, Clone, Error)]
 pub enum Lua4VMError {
@@ -14,8 +27,23 @@ pub enum Lua4VMError {
     #[error("Expected value to be a Closure")]
     NotClosure,
 
-    #[error("Unimplemented instruction {0:?}")]
-    Unimplemented(Lua4Instruction),
+    #[error("Expected value to be a Table")]
+    NotTable,
+
+    #[error("Invalid table key")]
+    InvalidTableKey,
+
+    #[error("Invalid operand for arithmetic")]
+    InvalidArithmetic,
+
+    #[error("Invalid operand for concat")]
+    InvalidConcat,
+
+    #[error("`for' {0} must be a number")]
+    InvalidForLoop(&'static str),
+
+    #[error("Upvalue {0} not found")]
+    UpvalueNotFound(u32),
 }
 
 pub trait Lua4VMRustClosures {
@@ -29,6 +57,9 @@ pub trait Lua4VMRustClosures {
 #[derive(Default)]
 pub struct Lua4VM {
     pub globals: HashMap<String, Lua4Value>,
+
+    /// When set, every instruction and closure call is recorded to the trace.
+    pub trace: Option<Arc<Mutex<Lua4VMTrace>>>,
 }
 
 impl Lua4VM {
@@ -49,6 +80,90 @@ impl Lua4VM {
         rust_closures: &mut T,
         function: &Lua4Function,
         parameters: &[Lua4Value],
+    ) -> Result<Vec<Lua4Value>, anyhow::Error> {
+        let results = self.call_lua_closure(rust_closures, function, &[], parameters);
+        self.record_trace_globals();
+        results
+    }
+
+    fn record_trace(&self, entry: impl FnOnce(&Lua4VMTrace) -> Lua4TraceEntry) {
+        if let Some(trace) = self.trace.as_ref() {
+            let mut trace = trace.lock().unwrap();
+            let entry = entry(&trace);
+            trace.push(entry);
+        }
+    }
+
+    fn record_trace_globals(&self) {
+        if let Some(trace) = self.trace.as_ref() {
+            trace.lock().unwrap().globals = self.globals.clone();
+        }
+    }
+
+    fn call_value<T: Lua4VMRustClosures>(
+        &mut self,
+        rust_closures: &mut T,
+        closure: Lua4Value,
+        parameters: Vec<Lua4Value>,
+    ) -> Result<Vec<Lua4Value>, anyhow::Error> {
+        match closure {
+            Lua4Value::Closure(function, upvalues) => {
+                self.call_lua_closure(rust_closures, &function, &upvalues, &parameters)
+            }
+            Lua4Value::RustClosure(function_name) => {
+                let mut debug_message = String::new();
+
+                if log::log_enabled!(target: "lua", log::Level::Debug) {
+                    write!(&mut debug_message, "Call rust closure: {}(", function_name).ok();
+
+                    for paremeter in parameters.iter().take(1) {
+                        write!(&mut debug_message, "{:?}", paremeter).ok();
+                    }
+
+                    for paremeter in parameters.iter().skip(1) {
+                        write!(&mut debug_message, ", {:?}", paremeter).ok();
+                    }
+                    debug_message.push(')');
+                }
+
+                let traced_parameters = self.trace.as_ref().map(|_| parameters.clone());
+                let results = rust_closures.call_rust_closure(&function_name, parameters);
+                if let Some(parameters) = traced_parameters {
+                    self.record_trace(|_| Lua4TraceEntry::RustClosure {
+                        name: function_name.clone(),
+                        parameters,
+                        results: results.as_ref().cloned().map_err(|error| error.to_string()),
+                    });
+                }
+                let results = results?;
+
+                if log::log_enabled!(target: "lua", log::Level::Debug) {
+                    write!(&mut debug_message, " = [").ok();
+
+                    for value in results.iter().take(1) {
+                        write!(&mut debug_message, "{:?}", value).ok();
+                    }
+
+                    for value in results.iter().skip(1) {
+                        write!(&mut debug_message, ", {:?}", value).ok();
+                    }
+                    debug_message.push(']');
+                }
+
+                log::debug!(target: "lua", "{}", debug_message);
+
+                Ok(results)
+            }
+            _ => Err(Lua4VMError::NotClosure.into()),
+        }
+    }
+
+    fn call_lua_closure<T: Lua4VMRustClosures>(
+        &mut self,
+        rust_closures: &mut T,
+        function: &Lua4Function,
+        upvalues: &[Lua4Value],
+        parameters: &[Lua4Value],
     ) -> Result<Vec<Lua4Value>, anyhow::Error> {
         let mut stack = Vec::with_capacity(function.max_stack_size as usize);
         let local_stack_index = stack.len();
@@ -56,9 +171,33 @@ impl Lua4VM {
             stack.push(parameters.get(i).cloned().unwrap_or(Lua4Value::Nil));
         }
 
+        if function.is_var_arg {
+            // Extra parameters are passed in the local table `arg`, with the count in arg.n
+            let extra_parameters = parameters
+                .get(function.num_parameters as usize..)
+                .unwrap_or_default();
+            let mut arg = Lua4Table::with_capacity(extra_parameters.len() + 1);
+            for (index, value) in extra_parameters.iter().enumerate() {
+                arg.set(Lua4Value::from(index + 1), value.clone())?;
+            }
+            arg.set(
+                Lua4Value::String("n".to_string()),
+                Lua4Value::from(extra_parameters.len()),
+            )?;
+            stack.push(Lua4Value::new_table(arg));
+        }
+
         let mut pc = 0;
         loop {
             let instruction = function.instructions[pc];
+            self.record_trace(|trace| Lua4TraceEntry::Instruction {
+                function_source: function.source.clone(),
+                function_line: function.line,
+                pc,
+                instruction,
+                stack: trace.capture_stack.then(|| stack.clone()),
+                upvalues: trace.capture_stack.then(|| upvalues.to_vec()),
+            });
             pc += 1;
             log::trace!(target: "lua", "[{:03}] {:?}", pc, instruction);
             match instruction {
@@ -72,56 +211,24 @@ impl Lua4VM {
                     let parameters =
                         stack.split_off(local_stack_index + parameter_stack_index as usize + 1);
                     let closure = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
+                    let mut results = self.call_value(rust_closures, closure, parameters)?;
 
-                    let mut results = if let Lua4Value::Closure(function, _upvalues) = closure {
-                        let function = function.clone();
-                        self.call_lua_function(rust_closures, &function, &parameters)?
-                    } else if let Lua4Value::RustClosure(function_name) = closure {
-                        let mut debug_message = String::new();
-
-                        if log::log_enabled!(target: "lua", log::Level::Debug) {
-                            write!(&mut debug_message, "Call rust closure: {}(", function_name)
-                                .ok();
-
-                            for paremeter in parameters.iter().take(1) {
-                                write!(&mut debug_message, "{:?}", paremeter).ok();
-                            }
-
-                            for paremeter in parameters.iter().skip(1) {
-                                write!(&mut debug_message, ", {:?}", paremeter).ok();
-                            }
-                            debug_message.push(')');
-                        }
-
-                        let results =
-                            rust_closures.call_rust_closure(&function_name, parameters)?;
-
-                        if log::log_enabled!(target: "lua", log::Level::Debug) {
-                            write!(&mut debug_message, " = [").ok();
-
-                            for value in results.iter().take(1) {
-                                write!(&mut debug_message, "{:?}", value).ok();
-                            }
-
-                            for value in results.iter().skip(1) {
-                                write!(&mut debug_message, ", {:?}", value).ok();
-                            }
-                            debug_message.push(']');
-                        }
-
-                        log::debug!(target: "lua", "{}", debug_message);
-
-                        results
-                    } else {
-                        return Err(Lua4VMError::NotClosure.into());
-                    };
-
-                    results.reverse();
-                    for _ in 0..num_results {
-                        stack.push(results.pop().unwrap_or(Lua4Value::Nil));
+                    if num_results != MULT_RET {
+                        results.resize(num_results as usize, Lua4Value::Nil);
                     }
+                    stack.extend(results);
+                }
+                Lua4Instruction::OP_TAILCALL(parameter_stack_index, return_stack_index) => {
+                    let parameters =
+                        stack.split_off(local_stack_index + parameter_stack_index as usize + 1);
+                    let closure = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
+                    let results = self.call_value(rust_closures, closure, parameters)?;
+                    stack.extend(results);
+
+                    // Leave only results on stack
+                    stack.drain(0..local_stack_index + return_stack_index as usize);
+                    break;
                 }
-                // TODO: Lua4Instruction::OP_TAILCALL(u32, u32)
                 Lua4Instruction::OP_PUSHNIL(count) => {
                     for _ in 0..count {
                         stack.push(Lua4Value::Nil);
@@ -146,7 +253,13 @@ impl Lua4VM {
                 Lua4Instruction::OP_PUSHNEGNUM(knum) => {
                     stack.push(Lua4Value::Number(-function.constant_numbers[knum as usize]));
                 }
-                // TODO: Lua4Instruction::OP_PUSHUPVALUE(u32)
+                Lua4Instruction::OP_PUSHUPVALUE(index) => {
+                    let value = upvalues
+                        .get(index as usize)
+                        .ok_or(Lua4VMError::UpvalueNotFound(index))?
+                        .clone();
+                    stack.push(value);
+                }
                 Lua4Instruction::OP_GETLOCAL(index) => {
                     let value = stack
                         .get(local_stack_index + index as usize)
@@ -162,11 +275,36 @@ impl Lua4VM {
                         .clone();
                     stack.push(value);
                 }
-                // TODO: Lua4Instruction::OP_GETTABLE
-                // TODO: Lua4Instruction::OP_GETDOTTED(u32)
-                // TODO: Lua4Instruction::OP_GETINDEXED(u32)
-                // TODO: Lua4Instruction::OP_PUSHSELF(u32)
-                // TODO: Lua4Instruction::OP_CREATETABLE(u32)
+                Lua4Instruction::OP_GETTABLE => {
+                    let key = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
+                    let table = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
+                    stack.push(get_table_value(&table, &key)?);
+                }
+                Lua4Instruction::OP_GETDOTTED(kstr) => {
+                    let key = Lua4Value::String(function.constant_strings[kstr as usize].clone());
+                    let table = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
+                    stack.push(get_table_value(&table, &key)?);
+                }
+                Lua4Instruction::OP_GETINDEXED(index) => {
+                    let key = stack
+                        .get(local_stack_index + index as usize)
+                        .ok_or(Lua4VMError::MissingStackValue)?
+                        .clone();
+                    let table = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
+                    stack.push(get_table_value(&table, &key)?);
+                }
+                Lua4Instruction::OP_PUSHSELF(kstr) => {
+                    // Replaces table on top of stack with table[kstr], table
+                    let key = Lua4Value::String(function.constant_strings[kstr as usize].clone());
+                    let table = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
+                    stack.push(get_table_value(&table, &key)?);
+                    stack.push(table);
+                }
+                Lua4Instruction::OP_CREATETABLE(size) => {
+                    stack.push(Lua4Value::new_table(Lua4Table::with_capacity(
+                        size as usize,
+                    )));
+                }
                 Lua4Instruction::OP_SETLOCAL(index) => {
                     stack[local_stack_index + index as usize] =
                         stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
@@ -177,18 +315,115 @@ impl Lua4VM {
                         stack.pop().ok_or(Lua4VMError::MissingStackValue)?,
                     );
                 }
-                // TODO: Lua4Instruction::OP_SETTABLE(u32, u32)
-                // TODO: Lua4Instruction::OP_SETLIST(u32, u32)
-                // TODO: Lua4Instruction::OP_SETMAP(u32)
-                // TODO: Lua4Instruction::OP_ADD
-                // TODO: Lua4Instruction::OP_ADDI(i32)
-                // TODO: Lua4Instruction::OP_SUB
-                // TODO: Lua4Instruction::OP_MULT
-                // TODO: Lua4Instruction::OP_DIV
-                // TODO: Lua4Instruction::OP_POW
-                // TODO: Lua4Instruction::OP_CONCAT(u32)
-                // TODO: Lua4Instruction::OP_MINUS
-                // TODO: Lua4Instruction::OP_NOT
+                Lua4Instruction::OP_SETTABLE(table_offset, num_pop) => {
+                    // Table is at top - a, key at top - a + 1, value at top - 1
+                    let table_index = stack
+                        .len()
+                        .checked_sub(table_offset as usize)
+                        .ok_or(Lua4VMError::MissingStackValue)?;
+                    let value = stack.last().ok_or(Lua4VMError::MissingStackValue)?;
+                    let key = stack
+                        .get(table_index + 1)
+                        .ok_or(Lua4VMError::MissingStackValue)?;
+                    set_table_value(&stack[table_index], key.clone(), value.clone())?;
+
+                    let new_len = stack
+                        .len()
+                        .checked_sub(num_pop as usize)
+                        .ok_or(Lua4VMError::MissingStackValue)?;
+                    stack.truncate(new_len);
+                }
+                Lua4Instruction::OP_SETLIST(flush_index, count) => {
+                    let values_index = stack
+                        .len()
+                        .checked_sub(count as usize)
+                        .ok_or(Lua4VMError::MissingStackValue)?;
+                    let values = stack.split_off(values_index);
+                    let table = stack.last().ok_or(Lua4VMError::MissingStackValue)?;
+                    let first_index = flush_index * LFIELDS_PER_FLUSH + 1;
+
+                    for (index, value) in values.into_iter().enumerate() {
+                        set_table_value(
+                            table,
+                            Lua4Value::from((first_index as usize) + index),
+                            value,
+                        )?;
+                    }
+                }
+                Lua4Instruction::OP_SETMAP(count) => {
+                    let values_index = stack
+                        .len()
+                        .checked_sub(2 * count as usize)
+                        .ok_or(Lua4VMError::MissingStackValue)?;
+                    let values = stack.split_off(values_index);
+                    let table = stack.last().ok_or(Lua4VMError::MissingStackValue)?;
+
+                    let mut values = values.into_iter();
+                    while let (Some(key), Some(value)) = (values.next(), values.next()) {
+                        set_table_value(table, key, value)?;
+                    }
+                }
+                Lua4Instruction::OP_ADD => {
+                    let rhs = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
+                    let lhs = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
+                    stack.push(arithmetic(&lhs, &rhs, |lhs, rhs| lhs + rhs)?);
+                }
+                Lua4Instruction::OP_ADDI(value) => {
+                    let lhs = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
+                    stack.push(arithmetic(&lhs, &Lua4Value::from(value), |lhs, rhs| {
+                        lhs + rhs
+                    })?);
+                }
+                Lua4Instruction::OP_SUB => {
+                    let rhs = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
+                    let lhs = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
+                    stack.push(arithmetic(&lhs, &rhs, |lhs, rhs| lhs - rhs)?);
+                }
+                Lua4Instruction::OP_MULT => {
+                    let rhs = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
+                    let lhs = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
+                    stack.push(arithmetic(&lhs, &rhs, |lhs, rhs| lhs * rhs)?);
+                }
+                Lua4Instruction::OP_DIV => {
+                    let rhs = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
+                    let lhs = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
+                    stack.push(arithmetic(&lhs, &rhs, |lhs, rhs| lhs / rhs)?);
+                }
+                Lua4Instruction::OP_POW => {
+                    let rhs = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
+                    let lhs = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
+                    stack.push(arithmetic(&lhs, &rhs, f64::powf)?);
+                }
+                Lua4Instruction::OP_CONCAT(count) => {
+                    let values_index = stack
+                        .len()
+                        .checked_sub(count as usize)
+                        .ok_or(Lua4VMError::MissingStackValue)?;
+                    let mut result = String::new();
+                    for value in stack.drain(values_index..) {
+                        match value {
+                            Lua4Value::String(string) => result.push_str(&string),
+                            Lua4Value::Number(number) => {
+                                result.push_str(&format_lua_number(number))
+                            }
+                            _ => return Err(Lua4VMError::InvalidConcat.into()),
+                        }
+                    }
+                    stack.push(Lua4Value::String(result));
+                }
+                Lua4Instruction::OP_MINUS => {
+                    let value = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
+                    let value = value.to_f64().map_err(|_| Lua4VMError::InvalidArithmetic)?;
+                    stack.push(Lua4Value::Number(-value));
+                }
+                Lua4Instruction::OP_NOT => {
+                    let value = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
+                    stack.push(if matches!(value, Lua4Value::Nil) {
+                        Lua4Value::Number(1.0)
+                    } else {
+                        Lua4Value::Nil
+                    });
+                }
                 Lua4Instruction::OP_JMPNE(target) => {
                     let rhs = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
                     let lhs = stack.pop().ok_or(Lua4VMError::MissingStackValue)?;
@@ -278,10 +513,84 @@ impl Lua4VM {
                     stack.push(Lua4Value::Nil);
                     pc = (pc as i32 + 1) as usize;
                 }
-                // TODO: Lua4Instruction::OP_FORPREP(i32)
-                // TODO: Lua4Instruction::OP_FORLOOP(i32)
-                // TODO: Lua4Instruction::OP_LFORPREP(i32)
-                // TODO: Lua4Instruction::OP_LFORLOOP(i32)
+                Lua4Instruction::OP_FORPREP(target) => {
+                    // Stack has initial value, limit, step
+                    let len = stack.len();
+                    if len < 3 {
+                        return Err(Lua4VMError::MissingStackValue.into());
+                    }
+                    let step = stack[len - 1]
+                        .to_f64()
+                        .map_err(|_| Lua4VMError::InvalidForLoop("step"))?;
+                    let limit = stack[len - 2]
+                        .to_f64()
+                        .map_err(|_| Lua4VMError::InvalidForLoop("limit"))?;
+                    let initial = stack[len - 3]
+                        .to_f64()
+                        .map_err(|_| Lua4VMError::InvalidForLoop("initial value"))?;
+                    stack[len - 1] = Lua4Value::Number(step);
+                    stack[len - 2] = Lua4Value::Number(limit);
+                    stack[len - 3] = Lua4Value::Number(initial);
+
+                    if (step > 0.0 && initial > limit) || (step <= 0.0 && initial < limit) {
+                        // Empty loop, remove control variables and jump to loop end
+                        stack.truncate(len - 3);
+                        pc = (pc as i32 + target) as usize;
+                    }
+                }
+                Lua4Instruction::OP_FORLOOP(target) => {
+                    let len = stack.len();
+                    if len < 3 {
+                        return Err(Lua4VMError::MissingStackValue.into());
+                    }
+                    let step = stack[len - 1]
+                        .to_f64()
+                        .map_err(|_| Lua4VMError::InvalidForLoop("step"))?;
+                    let limit = stack[len - 2]
+                        .to_f64()
+                        .map_err(|_| Lua4VMError::InvalidForLoop("limit"))?;
+                    let index = stack[len - 3]
+                        .to_f64()
+                        .map_err(|_| Lua4VMError::InvalidForLoop("index"))?
+                        + step;
+                    stack[len - 3] = Lua4Value::Number(index);
+
+                    if (step > 0.0 && index > limit) || (step <= 0.0 && index < limit) {
+                        // End of loop, remove control variables
+                        stack.truncate(len - 3);
+                    } else {
+                        pc = (pc as i32 + target) as usize;
+                    }
+                }
+                Lua4Instruction::OP_LFORPREP(target) => {
+                    // Stack has table, push the first key, value
+                    let table = stack.last().ok_or(Lua4VMError::MissingStackValue)?;
+                    if let Some((key, value)) = next_table_entry(table, &Lua4Value::Nil)? {
+                        stack.push(key);
+                        stack.push(value);
+                    } else {
+                        // Empty loop, remove table and jump to loop end
+                        stack.pop();
+                        pc = (pc as i32 + target) as usize;
+                    }
+                }
+                Lua4Instruction::OP_LFORLOOP(target) => {
+                    // Stack has table, key, value
+                    let len = stack.len();
+                    if len < 3 {
+                        return Err(Lua4VMError::MissingStackValue.into());
+                    }
+
+                    if let Some((key, value)) = next_table_entry(&stack[len - 3], &stack[len - 2])?
+                    {
+                        stack[len - 2] = key;
+                        stack[len - 1] = value;
+                        pc = (pc as i32 + target) as usize;
+                    } else {
+                        // End of loop, remove table, key, value
+                        stack.truncate(len - 3);
+                    }
+                }
                 Lua4Instruction::OP_CLOSURE(kproto, b) => {
                     let upvalues = stack.split_off(stack.len() - b as usize);
                     stack.push(Lua4Value::Closure(
@@ -289,9 +598,6 @@ impl Lua4VM {
                         upvalues,
                     ));
                 }
-                _ => {
-                    anyhow::bail!(Lua4VMError::Unimplemented(instruction))
-                }
             }
         }
 
@@ -308,11 +614,656 @@ impl Lua4VM {
             .get_global(name)
             .ok_or_else(|| Lua4VMError::GlobalNotFound(name.into()))?;
 
-        if let Lua4Value::Closure(function, _upvalues) = global_value {
+        if let Lua4Value::Closure(function, upvalues) = global_value {
             let function = function.clone();
-            self.call_lua_function(rust_closures, &function, parameters)
+            let upvalues = upvalues.clone();
+
+            self.record_trace(|_| Lua4TraceEntry::CallGlobal {
+                name: name.to_string(),
+                parameters: parameters.to_vec(),
+            });
+            let results = self.call_lua_closure(rust_closures, &function, &upvalues, parameters);
+            self.record_trace(|_| Lua4TraceEntry::ReturnGlobal {
+                name: name.to_string(),
+                results: results.as_ref().cloned().map_err(|error| error.to_string()),
+            });
+            self.record_trace_globals();
+            results
         } else {
             Err(Lua4VMError::NotClosure.into())
         }
     }
 }
+
+fn get_table_value(table: &Lua4Value, key: &Lua4Value) -> Result<Lua4Value, Lua4VMError> {
+    if let Lua4Value::Table(table) = table {
+        Ok(table.lock().unwrap().get(key))
+    } else {
+        Err(Lua4VMError::NotTable)
+    }
+}
+
+fn set_table_value(table: &Lua4Value, key: Lua4Value, value: Lua4Value) -> Result<(), Lua4VMError> {
+    if let Lua4Value::Table(table) = table {
+        table.lock().unwrap().set(key, value)
+    } else {
+        Err(Lua4VMError::NotTable)
+    }
+}
+
+fn next_table_entry(
+    table: &Lua4Value,
+    key: &Lua4Value,
+) -> Result<Option<(Lua4Value, Lua4Value)>, Lua4VMError> {
+    if let Lua4Value::Table(table) = table {
+        Ok(table.lock().unwrap().next(key))
+    } else {
+        Err(Lua4VMError::NotTable)
+    }
+}
+
+fn arithmetic(
+    lhs: &Lua4Value,
+    rhs: &Lua4Value,
+    op: fn(f64, f64) -> f64,
+) -> Result<Lua4Value, Lua4VMError> {
+    // Strings are converted to numbers as in lua's tonumber
+    let lhs = lhs.to_f64().map_err(|_| Lua4VMError::InvalidArithmetic)?;
+    let rhs = rhs.to_f64().map_err(|_| Lua4VMError::InvalidArithmetic)?;
+    Ok(Lua4Value::Number(op(lhs, rhs)))
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    // Opcodes and argument encoding from Lua 4.0 lopcodes.h
+    pub(crate) const OP_END: u32 = 0;
+    pub(crate) const OP_RETURN: u32 = 1;
+    pub(crate) const OP_CALL: u32 = 2;
+    const OP_TAILCALL: u32 = 3;
+    pub(crate) const OP_PUSHINT: u32 = 6;
+    const OP_PUSHSTRING: u32 = 7;
+    const OP_PUSHNUM: u32 = 8;
+    const OP_PUSHUPVALUE: u32 = 10;
+    const OP_GETLOCAL: u32 = 11;
+    pub(crate) const OP_GETGLOBAL: u32 = 12;
+    const OP_GETTABLE: u32 = 13;
+    const OP_GETDOTTED: u32 = 14;
+    const OP_GETINDEXED: u32 = 15;
+    const OP_PUSHSELF: u32 = 16;
+    const OP_CREATETABLE: u32 = 17;
+    const OP_SETLOCAL: u32 = 18;
+    pub(crate) const OP_SETGLOBAL: u32 = 19;
+    const OP_SETTABLE: u32 = 20;
+    const OP_SETLIST: u32 = 21;
+    const OP_SETMAP: u32 = 22;
+    const OP_ADD: u32 = 23;
+    const OP_ADDI: u32 = 24;
+    const OP_SUB: u32 = 25;
+    const OP_MULT: u32 = 26;
+    const OP_DIV: u32 = 27;
+    const OP_POW: u32 = 28;
+    const OP_CONCAT: u32 = 29;
+    const OP_MINUS: u32 = 30;
+    pub(crate) const OP_JMP: u32 = 42;
+    const OP_FORPREP: u32 = 44;
+    const OP_FORLOOP: u32 = 45;
+    const OP_LFORPREP: u32 = 46;
+    const OP_LFORLOOP: u32 = 47;
+    pub(crate) const OP_CLOSURE: u32 = 48;
+
+    const MAXARG_S: i32 = ((1 << 26) - 1) >> 1;
+
+    pub(crate) fn op(opcode: u32) -> u32 {
+        opcode
+    }
+
+    pub(crate) fn op_u(opcode: u32, u: u32) -> u32 {
+        opcode | (u << 6)
+    }
+
+    pub(crate) fn op_s(opcode: u32, s: i32) -> u32 {
+        op_u(opcode, (s + MAXARG_S) as u32)
+    }
+
+    fn op_ab(opcode: u32, a: u32, b: u32) -> u32 {
+        opcode | (b << 6) | (a << 15)
+    }
+
+    #[derive(Default)]
+    pub(crate) struct TestFunction {
+        pub line: u32,
+        pub num_parameters: u32,
+        pub line_infos: Vec<i32>,
+        pub strings: Vec<&'static str>,
+        pub numbers: Vec<f64>,
+        pub functions: Vec<TestFunction>,
+        pub instructions: Vec<u32>,
+    }
+
+    fn write_int(bytes: &mut Vec<u8>, value: u32) {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_string(bytes: &mut Vec<u8>, value: &str) {
+        // Lua strings are written with their null terminator
+        write_int(bytes, value.len() as u32 + 1);
+        bytes.extend_from_slice(value.as_bytes());
+        bytes.push(0);
+    }
+
+    fn write_function(bytes: &mut Vec<u8>, function: &TestFunction) {
+        write_string(bytes, "=test");
+        write_int(bytes, function.line);
+        write_int(bytes, function.num_parameters);
+        bytes.push(0); // is_var_arg
+        write_int(bytes, 32); // max_stack_size
+        write_int(bytes, 0); // local_vars
+
+        write_int(bytes, function.line_infos.len() as u32);
+        for &line_info in function.line_infos.iter() {
+            bytes.extend_from_slice(&line_info.to_le_bytes());
+        }
+
+        write_int(bytes, function.strings.len() as u32);
+        for string in function.strings.iter() {
+            write_string(bytes, string);
+        }
+
+        write_int(bytes, function.numbers.len() as u32);
+        for number in function.numbers.iter() {
+            bytes.extend_from_slice(&number.to_le_bytes());
+        }
+
+        write_int(bytes, function.functions.len() as u32);
+        for function in function.functions.iter() {
+            write_function(bytes, function);
+        }
+
+        write_int(bytes, function.instructions.len() as u32);
+        for &instruction in function.instructions.iter() {
+            write_int(bytes, instruction);
+        }
+    }
+
+    /// Builds a little endian Lua 4.0 chunk as written by luac.
+    pub(crate) fn compile(function: &TestFunction) -> Vec<u8> {
+        let mut bytes = vec![27];
+        bytes.extend_from_slice(b"Lua");
+        bytes.extend_from_slice(&[0x40, 1, 4, 4, 4, 32, 6, 9, 8]);
+        bytes.extend_from_slice(&(std::f64::consts::PI * 1E8).to_le_bytes());
+        write_function(&mut bytes, function);
+        bytes
+    }
+
+    struct TestRustClosures;
+
+    impl Lua4VMRustClosures for TestRustClosures {
+        fn call_rust_closure(
+            &mut self,
+            name: &str,
+            parameters: Vec<Lua4Value>,
+        ) -> Result<Vec<Lua4Value>, Lua4VMError> {
+            let value = parameters
+                .first()
+                .and_then(|value| value.to_f64().ok())
+                .ok_or(Lua4VMError::InvalidArithmetic)?;
+
+            match name {
+                "double" => Ok(vec![Lua4Value::Number(value * 2.0)]),
+                "pair" => Ok(vec![
+                    Lua4Value::Number(value),
+                    Lua4Value::Number(value + 1.0),
+                ]),
+                _ => Err(Lua4VMError::GlobalNotFound(name.into())),
+            }
+        }
+    }
+
+    fn run(
+        vm: &mut Lua4VM,
+        function: TestFunction,
+        parameters: &[Lua4Value],
+    ) -> Result<Vec<Lua4Value>, anyhow::Error> {
+        let function = Lua4Function::from_bytes(&compile(&function))?;
+        vm.call_lua_function(&mut TestRustClosures, &function, parameters)
+    }
+
+    fn get_number(vm: &mut Lua4VM, name: &str) -> f64 {
+        vm.get_global(name).unwrap().to_f64().unwrap()
+    }
+
+    fn get_table_entry(table: &Lua4Value, key: Lua4Value) -> Lua4Value {
+        get_table_value(table, &key).unwrap()
+    }
+
+    #[test]
+    fn format_numbers() {
+        assert_eq!(format_lua_number(0.0), "0");
+        assert_eq!(format_lua_number(100.0), "100");
+        assert_eq!(format_lua_number(-2.5), "-2.5");
+        assert_eq!(format_lua_number(0.1 + 0.2), "0.3");
+        assert_eq!(format_lua_number(1.0 / 3.0), "0.33333333333333");
+        assert_eq!(format_lua_number(0.0001), "0.0001");
+        assert_eq!(format_lua_number(0.00001), "1e-05");
+        assert_eq!(format_lua_number(12345678901234.0), "12345678901234");
+        assert_eq!(format_lua_number(1e15), "1e+15");
+        assert_eq!(format_lua_number(1e20), "1e+20");
+        assert_eq!(format_lua_number(-1.5e100), "-1.5e+100");
+    }
+
+    #[test]
+    fn tables() {
+        // t = { 1, 2, ..., 70; x = "y" }
+        // t.z = t[2] + t[70]
+        // local k = 3; v = t[k]
+        let mut instructions = vec![op_u(OP_CREATETABLE, 71)];
+        for i in 1..=64 {
+            instructions.push(op_s(OP_PUSHINT, i));
+        }
+        // The first 64 items are flushed before the remaining are pushed
+        instructions.push(op_ab(OP_SETLIST, 0, 64));
+        for i in 65..=70 {
+            instructions.push(op_s(OP_PUSHINT, i));
+        }
+        instructions.extend([
+            op_ab(OP_SETLIST, 1, 6),
+            op_u(OP_PUSHSTRING, 0),
+            op_u(OP_PUSHSTRING, 1),
+            op_u(OP_SETMAP, 1),
+            op_u(OP_SETGLOBAL, 2),
+            op_u(OP_GETGLOBAL, 2),
+            op_u(OP_PUSHSTRING, 3),
+            op_u(OP_GETGLOBAL, 2),
+            op_s(OP_PUSHINT, 2),
+            op(OP_GETTABLE),
+            op_u(OP_GETGLOBAL, 2),
+            op_s(OP_PUSHINT, 70),
+            op(OP_GETTABLE),
+            op(OP_ADD),
+            op_ab(OP_SETTABLE, 3, 3),
+            op_s(OP_PUSHINT, 3),
+            op_u(OP_GETGLOBAL, 2),
+            op_u(OP_GETINDEXED, 0),
+            op_u(OP_SETGLOBAL, 4),
+            op_u(OP_RETURN, 1),
+            op(OP_END),
+        ]);
+
+        let mut vm = Lua4VM::new();
+        let results = run(
+            &mut vm,
+            TestFunction {
+                strings: vec!["x", "y", "t", "z", "v"],
+                instructions,
+                ..Default::default()
+            },
+            &[],
+        )
+        .unwrap();
+        assert!(results.is_empty());
+
+        let t = vm.get_global("t").unwrap().clone();
+        if let Lua4Value::Table(table) = &t {
+            assert_eq!(table.lock().unwrap().len(), 72);
+        } else {
+            panic!("Expected t to be a table");
+        }
+        assert_eq!(get_table_entry(&t, Lua4Value::from(1)), Lua4Value::from(1));
+        assert_eq!(
+            get_table_entry(&t, Lua4Value::from(64)),
+            Lua4Value::from(64)
+        );
+        assert_eq!(
+            get_table_entry(&t, Lua4Value::from(65)),
+            Lua4Value::from(65)
+        );
+        assert_eq!(
+            get_table_entry(&t, Lua4Value::from(70)),
+            Lua4Value::from(70)
+        );
+        assert_eq!(get_table_entry(&t, Lua4Value::from(71)), Lua4Value::Nil);
+        assert_eq!(
+            get_table_entry(&t, Lua4Value::String("x".to_string())),
+            Lua4Value::String("y".to_string())
+        );
+        assert_eq!(
+            get_table_entry(&t, Lua4Value::String("z".to_string())),
+            Lua4Value::from(72)
+        );
+        assert_eq!(get_number(&mut vm, "v"), 3.0);
+    }
+
+    #[test]
+    fn arithmetic() {
+        // a = -((7 - 2) * 3 / 2 + 1) ^ 2
+        // b = "4" + 1
+        let mut vm = Lua4VM::new();
+        run(
+            &mut vm,
+            TestFunction {
+                strings: vec!["a", "4", "b"],
+                instructions: vec![
+                    op_s(OP_PUSHINT, 7),
+                    op_s(OP_PUSHINT, 2),
+                    op(OP_SUB),
+                    op_s(OP_PUSHINT, 3),
+                    op(OP_MULT),
+                    op_s(OP_PUSHINT, 2),
+                    op(OP_DIV),
+                    op_s(OP_ADDI, 1),
+                    op_s(OP_PUSHINT, 2),
+                    op(OP_POW),
+                    op(OP_MINUS),
+                    op_u(OP_SETGLOBAL, 0),
+                    op_u(OP_PUSHSTRING, 1),
+                    op_s(OP_PUSHINT, 1),
+                    op(OP_ADD),
+                    op_u(OP_SETGLOBAL, 2),
+                    op(OP_END),
+                ],
+                ..Default::default()
+            },
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(get_number(&mut vm, "a"), -72.25);
+        assert_eq!(get_number(&mut vm, "b"), 5.0);
+    }
+
+    #[test]
+    fn concat() {
+        // s = "a" .. 1 .. 1e20 .. (0.1 + 0.2) .. -2.5
+        let mut vm = Lua4VM::new();
+        run(
+            &mut vm,
+            TestFunction {
+                strings: vec!["a", "s"],
+                numbers: vec![1e20, 0.1, 0.2, -2.5],
+                instructions: vec![
+                    op_u(OP_PUSHSTRING, 0),
+                    op_s(OP_PUSHINT, 1),
+                    op_u(OP_PUSHNUM, 0),
+                    op_u(OP_PUSHNUM, 1),
+                    op_u(OP_PUSHNUM, 2),
+                    op(OP_ADD),
+                    op_u(OP_PUSHNUM, 3),
+                    op_u(OP_CONCAT, 5),
+                    op_u(OP_SETGLOBAL, 1),
+                    op(OP_END),
+                ],
+                ..Default::default()
+            },
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(
+            vm.get_global("s"),
+            Some(&Lua4Value::String("a11e+200.3-2.5".to_string()))
+        );
+    }
+
+    #[test]
+    fn concat_invalid() {
+        // s = "a" .. {}
+        let mut vm = Lua4VM::new();
+        let result = run(
+            &mut vm,
+            TestFunction {
+                strings: vec!["a"],
+                instructions: vec![
+                    op_u(OP_PUSHSTRING, 0),
+                    op_u(OP_CREATETABLE, 0),
+                    op_u(OP_CONCAT, 2),
+                    op(OP_END),
+                ],
+                ..Default::default()
+            },
+            &[],
+        );
+
+        assert!(matches!(
+            result.unwrap_err().downcast_ref::<Lua4VMError>(),
+            Some(Lua4VMError::InvalidConcat)
+        ));
+    }
+
+    #[test]
+    fn tailcall() {
+        // function (x) return pair(double(x)) end
+        let mut vm = Lua4VM::new();
+        vm.set_global(
+            "double".to_string(),
+            Lua4Value::RustClosure("double".to_string()),
+        );
+        vm.set_global(
+            "pair".to_string(),
+            Lua4Value::RustClosure("pair".to_string()),
+        );
+
+        let results = run(
+            &mut vm,
+            TestFunction {
+                num_parameters: 1,
+                strings: vec!["pair", "double"],
+                instructions: vec![
+                    op_u(OP_GETGLOBAL, 0),
+                    op_u(OP_GETGLOBAL, 1),
+                    op_u(OP_GETLOCAL, 0),
+                    op_ab(OP_CALL, 2, MULT_RET),
+                    op_ab(OP_TAILCALL, 1, 1),
+                    op(OP_END),
+                ],
+                ..Default::default()
+            },
+            &[Lua4Value::from(21)],
+        )
+        .unwrap();
+
+        assert_eq!(results, vec![Lua4Value::from(42), Lua4Value::from(43)]);
+    }
+
+    #[test]
+    fn pushself() {
+        // obj = { value = 10, method = function(self, n) return self.value + n end }
+        // result = obj:method(5)
+        let method = TestFunction {
+            num_parameters: 2,
+            strings: vec!["value"],
+            instructions: vec![
+                op_u(OP_GETLOCAL, 0),
+                op_u(OP_GETDOTTED, 0),
+                op_u(OP_GETLOCAL, 1),
+                op(OP_ADD),
+                op_u(OP_RETURN, 2),
+                op(OP_END),
+            ],
+            ..Default::default()
+        };
+
+        let mut vm = Lua4VM::new();
+        run(
+            &mut vm,
+            TestFunction {
+                strings: vec!["value", "method", "obj", "result"],
+                functions: vec![method],
+                instructions: vec![
+                    op_u(OP_CREATETABLE, 2),
+                    op_u(OP_PUSHSTRING, 0),
+                    op_s(OP_PUSHINT, 10),
+                    op_u(OP_PUSHSTRING, 1),
+                    op_ab(OP_CLOSURE, 0, 0),
+                    op_u(OP_SETMAP, 2),
+                    op_u(OP_SETGLOBAL, 2),
+                    op_u(OP_GETGLOBAL, 2),
+                    op_u(OP_PUSHSELF, 1),
+                    op_s(OP_PUSHINT, 5),
+                    op_ab(OP_CALL, 0, 1),
+                    op_u(OP_SETGLOBAL, 3),
+                    op(OP_END),
+                ],
+                ..Default::default()
+            },
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(get_number(&mut vm, "result"), 15.0);
+    }
+
+    #[test]
+    fn upvalues() {
+        // local x = 5
+        // local f = function(y) return %x + y end
+        // x = 100
+        // result = f(3)
+        let function = TestFunction {
+            num_parameters: 1,
+            instructions: vec![
+                op_u(OP_PUSHUPVALUE, 0),
+                op_u(OP_GETLOCAL, 0),
+                op(OP_ADD),
+                op_u(OP_RETURN, 1),
+                op(OP_END),
+            ],
+            ..Default::default()
+        };
+
+        let mut vm = Lua4VM::new();
+        run(
+            &mut vm,
+            TestFunction {
+                strings: vec!["result"],
+                functions: vec![function],
+                instructions: vec![
+                    op_s(OP_PUSHINT, 5),
+                    op_u(OP_GETLOCAL, 0),
+                    op_ab(OP_CLOSURE, 0, 1),
+                    op_s(OP_PUSHINT, 100),
+                    op_u(OP_SETLOCAL, 0),
+                    op_u(OP_GETLOCAL, 1),
+                    op_s(OP_PUSHINT, 3),
+                    op_ab(OP_CALL, 2, 1),
+                    op_u(OP_SETGLOBAL, 0),
+                    op(OP_END),
+                ],
+                ..Default::default()
+            },
+            &[],
+        )
+        .unwrap();
+
+        // Upvalues are captured by value when the closure is created
+        assert_eq!(get_number(&mut vm, "result"), 8.0);
+    }
+
+    #[test]
+    fn upvalue_not_found() {
+        let mut vm = Lua4VM::new();
+        let result = run(
+            &mut vm,
+            TestFunction {
+                instructions: vec![op_u(OP_PUSHUPVALUE, 1), op(OP_END)],
+                ..Default::default()
+            },
+            &[],
+        );
+
+        assert!(matches!(
+            result.unwrap_err().downcast_ref::<Lua4VMError>(),
+            Some(Lua4VMError::UpvalueNotFound(1))
+        ));
+    }
+
+    fn for_loop(initial: i32, limit: i32, step: i32) -> (f64, Vec<Lua4Value>) {
+        // for i = initial, limit, step do sum = sum + i end
+        let mut vm = Lua4VM::new();
+        vm.set_global("sum".to_string(), Lua4Value::from(0));
+        let results = run(
+            &mut vm,
+            TestFunction {
+                strings: vec!["sum"],
+                instructions: vec![
+                    op_s(OP_PUSHINT, initial),
+                    op_s(OP_PUSHINT, limit),
+                    op_s(OP_PUSHINT, step),
+                    op_s(OP_FORPREP, 5),
+                    op_u(OP_GETGLOBAL, 0),
+                    op_u(OP_GETLOCAL, 0),
+                    op(OP_ADD),
+                    op_u(OP_SETGLOBAL, 0),
+                    op_s(OP_FORLOOP, -5),
+                    op_u(OP_RETURN, 0),
+                    op(OP_END),
+                ],
+                ..Default::default()
+            },
+            &[],
+        )
+        .unwrap();
+
+        (get_number(&mut vm, "sum"), results)
+    }
+
+    #[test]
+    fn numeric_for_loop() {
+        // The control variables are removed from the stack after the loop
+        assert_eq!(for_loop(1, 10, 2), (25.0, Vec::new()));
+        assert_eq!(for_loop(3, 1, -1), (6.0, Vec::new()));
+        assert_eq!(for_loop(1, 0, 1), (0.0, Vec::new()));
+    }
+
+    fn table_for_loop(table: Lua4Table) -> (f64, f64, Vec<Lua4Value>) {
+        // for k, v in t do keys = keys + k; values = values + v end
+        let mut vm = Lua4VM::new();
+        vm.set_global("t".to_string(), Lua4Value::new_table(table));
+        vm.set_global("keys".to_string(), Lua4Value::from(0));
+        vm.set_global("values".to_string(), Lua4Value::from(0));
+        let results = run(
+            &mut vm,
+            TestFunction {
+                strings: vec!["t", "keys", "values"],
+                instructions: vec![
+                    op_u(OP_GETGLOBAL, 0),
+                    op_s(OP_LFORPREP, 9),
+                    op_u(OP_GETGLOBAL, 1),
+                    op_u(OP_GETLOCAL, 1),
+                    op(OP_ADD),
+                    op_u(OP_SETGLOBAL, 1),
+                    op_u(OP_GETGLOBAL, 2),
+                    op_u(OP_GETLOCAL, 2),
+                    op(OP_ADD),
+                    op_u(OP_SETGLOBAL, 2),
+                    op_s(OP_LFORLOOP, -9),
+                    op_u(OP_RETURN, 0),
+                    op(OP_END),
+                ],
+                ..Default::default()
+            },
+            &[],
+        )
+        .unwrap();
+
+        (
+            get_number(&mut vm, "keys"),
+            get_number(&mut vm, "values"),
+            results,
+        )
+    }
+
+    #[test]
+    fn table_for_loop_entries() {
+        let mut table = Lua4Table::new();
+        table.set(Lua4Value::from(1), Lua4Value::from(10)).unwrap();
+        table.set(Lua4Value::from(2), Lua4Value::from(20)).unwrap();
+        table.set(Lua4Value::from(3), Lua4Value::from(30)).unwrap();
+        table.set(Lua4Value::from(2), Lua4Value::Nil).unwrap();
+
+        // The table, key and value are removed from the stack after the loop
+        assert_eq!(table_for_loop(table), (4.0, 40.0, Vec::new()));
+        assert_eq!(table_for_loop(Lua4Table::new()), (0.0, 0.0, Vec::new()));
+    }
+}
@@ -1,10 +1,28 @@
-use bevy::prelude::Resource;
+use bevy::{
+    math::Vec3,
+    prelude::{DespawnRecursiveExt, Resource, Transform},
+};
+use chrono::{Datelike, Timelike};
+use dolly::prelude::YawPitch;
+use enum_map::Enum;
+use rand::Rng;
 use std::collections::HashMap;
 
-use rose_game_common::{components::CharacterGender, messages::ClientEntityId};
+use rose_data::{EffectFileId, MotionId, WarpGateId};
+use rose_file_readers::VfsPathBuf;
+use rose_game_common::{
+    components::{CharacterGender, InventoryPageType, ItemSlot, INVENTORY_PAGE_SIZE},
+    messages::{client::ClientMessage, ClientEntityId},
+};
 
 use crate::{
-    events::{BankEvent, ClanDialogEvent, NpcStoreEvent},
+    audio::GlobalSound,
+    components::{NextCommand, Position, SoundCategory},
+    events::{
+        BankEvent, ClanDialogEvent, CraftingEvent, NpcStoreEvent, PlayerCommandEvent,
+        SpawnEffectData, SpawnEffectEvent,
+    },
+    resources::RepairMode,
     scripting::{
         lua4::Lua4Value,
         lua_game_constants::{
@@ -13,14 +31,42 @@ use crate::{
         },
         ScriptFunctionContext, ScriptFunctionResources,
     },
+    systems::get_item_equipment_index,
 };
 
+// The game version reported by the original client, matching the irose protocol
+const GAME_VERSION: i32 = 112;
+
 #[derive(Resource)]
 pub struct LuaGameFunctions {
     pub closures: HashMap<
         String,
         fn(&ScriptFunctionResources, &mut ScriptFunctionContext, Vec<Lua4Value>) -> Vec<Lua4Value>,
     >,
+    pub stubs: Vec<String>,
+}
+
+impl LuaGameFunctions {
+    pub fn is_stub(&self, name: &str) -> bool {
+        self.stubs.iter().any(|stub| stub == name)
+    }
+
+    /// Lists which GF_ functions are implemented and which are only stubbed.
+    pub fn coverage_report(&self) -> String {
+        let mut implemented: Vec<&str> = self.closures.keys().map(|name| name.as_str()).collect();
+        implemented.sort_unstable_by_key(|name| name.to_lowercase());
+
+        let mut stubbed: Vec<&str> = self.stubs.iter().map(|name| name.as_str()).collect();
+        stubbed.sort_unstable_by_key(|name| name.to_lowercase());
+
+        format!(
+            "Implemented {} / {} game functions\nImplemented: {}\nStubbed: {}",
+            implemented.len(),
+            implemented.len() + stubbed.len(),
+            implemented.join(", "),
+            stubbed.join(", ")
+        )
+    }
 }
 
 impl Default for LuaGameFunctions {
@@ -34,72 +80,341 @@ impl Default for LuaGameFunctions {
             ) -> Vec<Lua4Value>,
         > = HashMap::new();
 
+        closures.insert("GF_checkNumOfInvItem".into(), GF_checkNumOfInvItem);
+        closures.insert("GF_checkUserMoney".into(), GF_checkUserMoney);
+        closures.insert(
+            "GF_DeleteEffectFromObject".into(),
+            GF_DeleteEffectFromObject,
+        );
+        closures.insert("GF_EffectOnObject".into(), GF_EffectOnObject);
+        closures.insert("GF_error".into(), GF_error);
+        closures.insert("GF_getDate".into(), GF_getDate);
+        closures.insert("GF_GetEffectUseFile".into(), GF_GetEffectUseFile);
+        closures.insert("GF_GetEffectUseIndex".into(), GF_GetEffectUseIndex);
+        closures.insert("GF_getGameVersion".into(), GF_getGameVersion);
+        closures.insert("GF_getIDXOfInvItem".into(), GF_getIDXOfInvItem);
+        closures.insert("GF_getItemRate".into(), GF_getItemRate);
+        closures.insert("GF_getName".into(), GF_getName);
+        closures.insert("GF_GetTarget".into(), GF_GetTarget);
+        closures.insert("GF_getTownRate".into(), GF_getTownRate);
         closures.insert("GF_getVariable".into(), GF_getVariable);
+        closures.insert("GF_getWorldRate".into(), GF_getWorldRate);
+        closures.insert("GF_getZone".into(), GF_getZone);
+        closures.insert("GF_log".into(), GF_log);
+        closures.insert("GF_LogString".into(), GF_log);
+        closures.insert("GF_moveXY".into(), GF_moveXY);
         closures.insert("GF_openBank".into(), GF_openBank);
+        closures.insert("GF_openSeparate".into(), GF_openSeparate);
         closures.insert("GF_openStore".into(), GF_openStore);
+        closures.insert("GF_openUpgrade".into(), GF_openUpgrade);
         closures.insert("GF_organizeClan".into(), GF_organizeClan);
+        closures.insert("GF_playEffect".into(), GF_playEffect);
+        closures.insert("GF_playSound".into(), GF_playSound);
+        closures.insert("GF_putoffItem".into(), GF_putoffItem);
+        closures.insert("GF_putonItem".into(), GF_putonItem);
+        closures.insert("GF_Random".into(), GF_Random);
+        closures.insert("GF_repair".into(), GF_repair);
+        closures.insert("GF_rotateCamera".into(), GF_rotateCamera);
+        closures.insert("GF_setRevivePosition".into(), GF_setRevivePosition);
+        closures.insert("GF_SetMotion".into(), GF_SetMotion);
+        closures.insert("GF_warp".into(), GF_warp);
+        closures.insert("GF_WeatherEffectOnObject".into(), GF_EffectOnObject);
+        closures.insert("GF_zoomCamera".into(), GF_zoomCamera);
+
+        // These are either only meaningful on the server, or need client
+        // features we do not have yet. They are registered so scripts calling
+        // them do not fail, a warning is logged when one is called and nil is
+        // returned.
+        //
+        // GF_openDeliveryStore opens the item mall delivery storage, listing it
+        // needs the item mall packets which the server does not implement and
+        // there is no ClientMessage for.
+        let stubs = [
+            "GF_addUserMoney",
+            "GF_appraisal",
+            "GF_ChangeState",
+            "GF_checkTownItem",
+            "GF_disorganizeClan",
+            "GF_GetMotionUseFile",
+            "GF_getReviveZoneName",
+            "GF_getTownVar",
+            "GF_giveEquipItemIntoInv",
+            "GF_giveUsableItemIntoInv",
+            "GF_movableXY",
+            "GF_moveEvent",
+            "GF_openDeliveryStore",
+            "GF_setEquipedItem",
+            "GF_setTownRate",
+            "GF_setVariable",
+            "GF_setWorldRate",
+            "GF_spawnMonAtEvent",
+            "GF_spawnMonXY",
+            "GF_takeItemFromInv",
+            "GF_takeUserMoney",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
 
-        /*
-        GF_addUserMoney
-        GF_appraisal
-        GF_ChangeState
-        GF_checkNumOfInvItem
-        GF_checkTownItem
-        GF_checkUserMoney
-        GF_DeleteEffectFromObject
-        GF_disorganizeClan
-        GF_EffectOnObject
-        GF_error
-        GF_getDate
-        GF_GetEffectUseFile
-        GF_GetEffectUseIndex
-        GF_getGameVersion
-        GF_getIDXOfInvItem
-        GF_getItemRate
-        GF_GetMotionUseFile
-        GF_getName
-        GF_getReviveZoneName
-        GF_GetTarget
-        GF_getTownRate
-        GF_getTownVar
-        GF_getWorldRate
-        GF_getZone
-        GF_giveEquipItemIntoInv
-        GF_giveUsableItemIntoInv
-        GF_log
-        GF_LogString
-        GF_movableXY
-        GF_moveEvent
-        GF_moveXY
-        GF_openDeliveryStore
-        GF_openSeparate
-        GF_openUpgrade
-        GF_playEffect
-        GF_playSound
-        GF_putoffItem
-        GF_putonItem
-        GF_Random
-        GF_repair
-        GF_rotateCamera
-        GF_setEquipedItem
-        GF_SetMotion
-        GF_setRevivePosition
-        GF_setTownRate
-        GF_setVariable
-        GF_setWorldRate
-        GF_spawnMonAtEvent
-        GF_spawnMonXY
-        GF_takeItemFromInv
-        GF_takeUserMoney
-        GF_warp
-        GF_WeatherEffectOnObject
-        GF_zoomCamera
-        */
-
-        Self { closures }
+        Self { closures, stubs }
     }
 }
 
+fn get_inventory_item_slots() -> impl Iterator<Item = ItemSlot> {
+    (0..InventoryPageType::LENGTH).flat_map(|page| {
+        let page_type = InventoryPageType::from_usize(page);
+        (0..INVENTORY_PAGE_SIZE).map(move |index| ItemSlot::Inventory(page_type, index))
+    })
+}
+
+fn get_spawn_effect_data(value: &Lua4Value) -> Option<SpawnEffectData> {
+    match value {
+        Lua4Value::String(path) => Some(SpawnEffectData::with_path(VfsPathBuf::new(path))),
+        value => Some(SpawnEffectData::with_file_id(EffectFileId::new(
+            value.to_usize().ok()? as u16,
+        )?)),
+    }
+}
+
+#[allow(non_snake_case)]
+fn GF_checkNumOfInvItem(
+    resources: &ScriptFunctionResources,
+    context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    let result = (|| -> Option<i32> {
+        let item_base1000 = parameters.get(0)?.to_usize().ok()?;
+        let item_reference = resources
+            .game_data
+            .data_decoder
+            .decode_item_base1000(item_base1000)?;
+        let character = context.query_player.get_single().ok()?;
+
+        Some(
+            get_inventory_item_slots()
+                .filter_map(|item_slot| character.inventory.get_item(item_slot))
+                .filter(|item| item.get_item_reference() == item_reference)
+                .map(|item| item.get_quantity() as i32)
+                .sum(),
+        )
+    })()
+    .unwrap_or(0);
+
+    vec![result.into()]
+}
+
+#[allow(non_snake_case)]
+fn GF_checkUserMoney(
+    _resources: &ScriptFunctionResources,
+    context: &mut ScriptFunctionContext,
+    _parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    let money = context
+        .query_player
+        .get_single()
+        .map_or(0, |character| character.inventory.money.0);
+
+    vec![Lua4Value::Number(money as f64)]
+}
+
+#[allow(non_snake_case)]
+fn GF_DeleteEffectFromObject(
+    resources: &ScriptFunctionResources,
+    context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    (|| -> Option<()> {
+        // Parameter 1 is the effect to remove, spawned effects do not remember which
+        // file they came from so we remove every effect attached to the object.
+        let client_entity_id = ClientEntityId(parameters.get(0)?.to_usize().ok()?);
+        let entity = resources.client_entity_list.get(client_entity_id)?;
+
+        for (effect_entity, parent) in context.query_effects.iter() {
+            if parent.get() == entity {
+                context.commands.entity(effect_entity).despawn_recursive();
+            }
+        }
+        Some(())
+    })();
+    vec![]
+}
+
+#[allow(non_snake_case)]
+fn GF_EffectOnObject(
+    resources: &ScriptFunctionResources,
+    context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    (|| -> Option<()> {
+        let client_entity_id = ClientEntityId(parameters.get(0)?.to_usize().ok()?);
+        let entity = resources.client_entity_list.get(client_entity_id)?;
+        let effect_data = get_spawn_effect_data(parameters.get(1)?)?;
+
+        context
+            .spawn_effect_events
+            .send(SpawnEffectEvent::OnEntity(entity, None, effect_data));
+        Some(())
+    })();
+    vec![]
+}
+
+#[allow(non_snake_case)]
+fn GF_error(
+    _resources: &ScriptFunctionResources,
+    _context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    log::error!(target: "lua", "GF_error{:?}", parameters);
+    vec![]
+}
+
+#[allow(non_snake_case)]
+fn GF_getDate(
+    _resources: &ScriptFunctionResources,
+    _context: &mut ScriptFunctionContext,
+    _parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    let local_time = chrono::Local::now();
+
+    vec![
+        local_time.year().into(),
+        (local_time.month() as i32).into(),
+        (local_time.day() as i32).into(),
+        (local_time.hour() as i32).into(),
+        (local_time.minute() as i32).into(),
+    ]
+}
+
+#[allow(non_snake_case)]
+fn GF_GetEffectUseFile(
+    _resources: &ScriptFunctionResources,
+    _context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    // We spawn effects by path, so the path itself is used as the effect handle
+    vec![parameters.get(0).cloned().unwrap_or(Lua4Value::Nil)]
+}
+
+#[allow(non_snake_case)]
+fn GF_GetEffectUseIndex(
+    resources: &ScriptFunctionResources,
+    _context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    let result = (|| -> Option<i32> {
+        let effect_index = parameters.get(0)?.to_i32().ok()?;
+        resources
+            .game_data
+            .effect_database
+            .get_effect_file(EffectFileId::new(effect_index as u16)?)?;
+        Some(effect_index)
+    })()
+    .unwrap_or(0);
+
+    vec![result.into()]
+}
+
+#[allow(non_snake_case)]
+fn GF_getGameVersion(
+    _resources: &ScriptFunctionResources,
+    _context: &mut ScriptFunctionContext,
+    _parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    vec![GAME_VERSION.into()]
+}
+
+#[allow(non_snake_case)]
+fn GF_getIDXOfInvItem(
+    resources: &ScriptFunctionResources,
+    context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    let result = (|| -> Option<i32> {
+        let item_number = parameters.get(0)?.to_usize().ok()?;
+        let item_type = parameters.get(1)?.to_usize().ok()?;
+        let item_reference = resources
+            .game_data
+            .data_decoder
+            .decode_item_reference(item_number, item_type)?;
+        let character = context.query_player.get_single().ok()?;
+
+        get_inventory_item_slots().find_map(|item_slot| {
+            let item = character.inventory.get_item(item_slot)?;
+            if item.get_item_reference() != item_reference {
+                return None;
+            }
+
+            if let ItemSlot::Inventory(page_type, index) = item_slot {
+                Some((page_type.into_usize() * INVENTORY_PAGE_SIZE + index) as i32)
+            } else {
+                None
+            }
+        })
+    })()
+    .unwrap_or(-1);
+
+    vec![result.into()]
+}
+
+#[allow(non_snake_case)]
+fn GF_getItemRate(
+    resources: &ScriptFunctionResources,
+    _context: &mut ScriptFunctionContext,
+    _parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    // The server only sends us a single item rate for all item types
+    let rate = resources
+        .world_rates
+        .as_ref()
+        .map_or(100, |world_rates| world_rates.item_price_rate);
+
+    vec![rate.into()]
+}
+
+#[allow(non_snake_case)]
+fn GF_getName(
+    _resources: &ScriptFunctionResources,
+    context: &mut ScriptFunctionContext,
+    _parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    let name = context
+        .query_player
+        .get_single()
+        .map(|character| character.character_info.name.clone())
+        .unwrap_or_default();
+
+    vec![name.into()]
+}
+
+#[allow(non_snake_case)]
+fn GF_GetTarget(
+    resources: &ScriptFunctionResources,
+    context: &mut ScriptFunctionContext,
+    _parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    let client_entity_id = resources
+        .selected_target
+        .selected
+        .and_then(|entity| context.query_client_entity.get(entity).ok())
+        .map_or(0, |client_entity| client_entity.id.0);
+
+    vec![client_entity_id.into()]
+}
+
+#[allow(non_snake_case)]
+fn GF_getTownRate(
+    resources: &ScriptFunctionResources,
+    _context: &mut ScriptFunctionContext,
+    _parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    let rate = resources
+        .world_rates
+        .as_ref()
+        .map_or(100, |world_rates| world_rates.town_price_rate);
+
+    vec![rate.into()]
+}
+
 #[allow(non_snake_case)]
 fn GF_getVariable(
     _resources: &ScriptFunctionResources,
@@ -137,6 +452,75 @@ fn GF_getVariable(
     vec![value.into()]
 }
 
+#[allow(non_snake_case)]
+fn GF_getWorldRate(
+    resources: &ScriptFunctionResources,
+    _context: &mut ScriptFunctionContext,
+    _parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    let rate = resources
+        .world_rates
+        .as_ref()
+        .map_or(100, |world_rates| world_rates.world_price_rate);
+
+    vec![rate.into()]
+}
+
+#[allow(non_snake_case)]
+fn GF_getZone(
+    resources: &ScriptFunctionResources,
+    _context: &mut ScriptFunctionContext,
+    _parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    let zone_id = resources
+        .current_zone
+        .as_ref()
+        .map_or(0, |current_zone| current_zone.id.get() as i32);
+
+    vec![zone_id.into()]
+}
+
+#[allow(non_snake_case)]
+fn GF_log(
+    _resources: &ScriptFunctionResources,
+    _context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    let message: Vec<String> = parameters
+        .iter()
+        .map(|value| value.to_string().unwrap_or_else(|_| format!("{:?}", value)))
+        .collect();
+    log::info!(target: "lua", "{}", message.join(" "));
+    vec![]
+}
+
+#[allow(non_snake_case)]
+fn GF_moveXY(
+    resources: &ScriptFunctionResources,
+    context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    (|| -> Option<()> {
+        let zone_id = parameters.get(0)?.to_i32().ok()?;
+        let x = parameters.get(1)?.to_f32().ok()?;
+        let y = parameters.get(2)?.to_f32().ok()?;
+
+        // We can only walk to a position in the current zone
+        let current_zone = resources.current_zone.as_ref()?;
+        if current_zone.id.get() as i32 != zone_id {
+            return None;
+        }
+
+        // Script positions are in metres
+        context.player_command_events.send(PlayerCommandEvent::Move(
+            Position::new(Vec3::new(x * 100.0, y * 100.0, 0.0)),
+            None,
+        ));
+        Some(())
+    })();
+    vec![]
+}
+
 #[allow(non_snake_case)]
 fn GF_openBank(
     _resources: &ScriptFunctionResources,
@@ -156,6 +540,22 @@ fn GF_openBank(
     vec![]
 }
 
+#[allow(non_snake_case)]
+fn GF_openSeparate(
+    _resources: &ScriptFunctionResources,
+    context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    (|| -> Option<()> {
+        let client_entity_id = ClientEntityId(parameters.get(0)?.to_usize().ok()?);
+        context
+            .crafting_events
+            .send(CraftingEvent::OpenSeparateFromClientEntity { client_entity_id });
+        Some(())
+    })();
+    vec![]
+}
+
 #[allow(non_snake_case)]
 fn GF_openStore(
     _resources: &ScriptFunctionResources,
@@ -172,6 +572,22 @@ fn GF_openStore(
     vec![]
 }
 
+#[allow(non_snake_case)]
+fn GF_openUpgrade(
+    _resources: &ScriptFunctionResources,
+    context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    (|| -> Option<()> {
+        let client_entity_id = ClientEntityId(parameters.get(0)?.to_usize().ok()?);
+        context
+            .crafting_events
+            .send(CraftingEvent::OpenUpgradeFromClientEntity { client_entity_id });
+        Some(())
+    })();
+    vec![]
+}
+
 #[allow(non_snake_case)]
 fn GF_organizeClan(
     _resources: &ScriptFunctionResources,
@@ -182,3 +598,240 @@ fn GF_organizeClan(
 
     vec![]
 }
+
+#[allow(non_snake_case)]
+fn GF_playEffect(
+    _resources: &ScriptFunctionResources,
+    context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    (|| -> Option<()> {
+        let effect_data = get_spawn_effect_data(parameters.get(0)?)?;
+        let x = parameters.get(1)?.to_f32().ok()?;
+        let y = parameters.get(2)?.to_f32().ok()?;
+        let z = parameters
+            .get(3)
+            .and_then(|z| z.to_f32().ok())
+            .unwrap_or(0.0);
+
+        // Script positions are in metres
+        context
+            .spawn_effect_events
+            .send(SpawnEffectEvent::WithTransform(
+                Transform::from_translation(Vec3::new(x, z, -y)),
+                effect_data,
+            ));
+        Some(())
+    })();
+    vec![]
+}
+
+#[allow(non_snake_case)]
+fn GF_playSound(
+    resources: &ScriptFunctionResources,
+    context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    (|| -> Option<()> {
+        // TODO: Support the repeat count in parameter 1
+        let path = parameters.get(0)?.to_string().ok()?;
+
+        context.commands.spawn((
+            SoundCategory::Ui,
+            resources.sound_settings.gain(SoundCategory::Ui),
+            GlobalSound::new(resources.asset_server.load(path)),
+        ));
+        Some(())
+    })();
+    vec![]
+}
+
+#[allow(non_snake_case)]
+fn GF_putoffItem(
+    resources: &ScriptFunctionResources,
+    _context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    (|| -> Option<()> {
+        let equipment_index = resources
+            .game_data
+            .data_decoder
+            .decode_equipment_index(parameters.get(0)?.to_usize().ok()?)?;
+
+        resources
+            .game_connection
+            .as_ref()?
+            .client_message_tx
+            .send(ClientMessage::ChangeEquipment {
+                equipment_index,
+                item_slot: None,
+            })
+            .ok();
+        Some(())
+    })();
+    vec![]
+}
+
+#[allow(non_snake_case)]
+fn GF_putonItem(
+    resources: &ScriptFunctionResources,
+    context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    (|| -> Option<()> {
+        // Parameter 0 is an inventory index as returned by GF_getIDXOfInvItem
+        let inventory_index = parameters.get(0)?.to_usize().ok()?;
+        let page = inventory_index / INVENTORY_PAGE_SIZE;
+        if page >= InventoryPageType::LENGTH {
+            return None;
+        }
+        let item_slot = ItemSlot::Inventory(
+            InventoryPageType::from_usize(page),
+            inventory_index % INVENTORY_PAGE_SIZE,
+        );
+
+        let character = context.query_player.get_single().ok()?;
+        let item = character.inventory.get_item(item_slot)?;
+        let equipment_index = get_item_equipment_index(&resources.game_data, item)?;
+
+        resources
+            .game_connection
+            .as_ref()?
+            .client_message_tx
+            .send(ClientMessage::ChangeEquipment {
+                equipment_index,
+                item_slot: Some(item_slot),
+            })
+            .ok();
+        Some(())
+    })();
+    vec![]
+}
+
+#[allow(non_snake_case)]
+fn GF_Random(
+    _resources: &ScriptFunctionResources,
+    _context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    // Returns a random number in the range 1..=max
+    let max = parameters
+        .get(0)
+        .and_then(|max| max.to_i32().ok())
+        .unwrap_or(1)
+        .max(1);
+
+    vec![rand::thread_rng().gen_range(1..=max).into()]
+}
+
+#[allow(non_snake_case)]
+fn GF_repair(
+    _resources: &ScriptFunctionResources,
+    context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    (|| -> Option<()> {
+        let npc_entity_id = ClientEntityId(parameters.get(0)?.to_usize().ok()?);
+        context
+            .repair_state
+            .start(RepairMode::UsingNpc { npc_entity_id });
+        Some(())
+    })();
+    vec![]
+}
+
+#[allow(non_snake_case)]
+fn GF_rotateCamera(
+    _resources: &ScriptFunctionResources,
+    context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    (|| -> Option<()> {
+        // Parameter 0 is the object owning the camera, we only have the player camera
+        let operation = parameters.get(1)?.to_i32().ok()?;
+        let angle = parameters.get(2)?.to_f32().ok()?;
+        let mut orbit_camera = context.query_orbit_camera.get_single_mut().ok()?;
+        let yaw_pitch = orbit_camera.rig.driver_mut::<YawPitch>();
+
+        if operation == 0 {
+            yaw_pitch.yaw_degrees = angle;
+        } else {
+            yaw_pitch.rotate_yaw_pitch(angle, 0.0);
+        }
+        Some(())
+    })();
+    vec![]
+}
+
+#[allow(non_snake_case)]
+fn GF_setRevivePosition(
+    resources: &ScriptFunctionResources,
+    _context: &mut ScriptFunctionContext,
+    _parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    if let Some(game_connection) = resources.game_connection.as_ref() {
+        game_connection
+            .client_message_tx
+            .send(ClientMessage::SetReviveSaveZone)
+            .ok();
+    }
+    vec![]
+}
+
+#[allow(non_snake_case)]
+fn GF_SetMotion(
+    resources: &ScriptFunctionResources,
+    context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    (|| -> Option<()> {
+        let client_entity_id = ClientEntityId(parameters.get(0)?.to_usize().ok()?);
+        let entity = resources.client_entity_list.get(client_entity_id)?;
+        let motion_id = MotionId::new(parameters.get(1)?.to_usize().ok()? as u16);
+
+        context
+            .commands
+            .entity(entity)
+            .insert(NextCommand::with_emote(motion_id, true));
+        Some(())
+    })();
+    vec![]
+}
+
+#[allow(non_snake_case)]
+fn GF_warp(
+    resources: &ScriptFunctionResources,
+    _context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    (|| -> Option<()> {
+        let warp_gate_id = WarpGateId::new(parameters.get(0)?.to_usize().ok()? as u16);
+
+        resources
+            .game_connection
+            .as_ref()?
+            .client_message_tx
+            .send(ClientMessage::WarpGateRequest { warp_gate_id })
+            .ok();
+        Some(())
+    })();
+    vec![]
+}
+
+#[allow(non_snake_case)]
+fn GF_zoomCamera(
+    _resources: &ScriptFunctionResources,
+    context: &mut ScriptFunctionContext,
+    parameters: Vec<Lua4Value>,
+) -> Vec<Lua4Value> {
+    (|| -> Option<()> {
+        // Parameter 0 is the object owning the camera, we only have the player camera
+        let percent = parameters.get(1)?.to_f32().ok()?;
+        let mut orbit_camera = context.query_orbit_camera.get_single_mut().ok()?;
+
+        orbit_camera.follow_distance = (orbit_camera.follow_distance * percent / 100.0)
+            .clamp(orbit_camera.min_distance, orbit_camera.max_distance);
+        Some(())
+    })();
+    vec![]
+}
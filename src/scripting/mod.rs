@@ -9,6 +9,7 @@ mod quest;
 mod quest_condition_functions;
 mod quest_function_context;
 mod quest_reward_functions;
+mod script_disassembly;
 mod script_function_context;
 mod script_function_resources;
 
@@ -19,10 +20,16 @@ pub struct LuaUserValueEntity {
 pub use lua_game_constants::LuaGameConstants;
 pub use lua_game_functions::LuaGameFunctions;
 pub use lua_quest_functions::LuaQuestFunctions;
-pub use quest::{quest_apply_rewards, quest_check_conditions};
-pub use quest_condition_functions::quest_trigger_check_conditions;
+pub use quest::{
+    quest_apply_rewards, quest_check_conditions, quest_dry_run, QuestError, QuestTriggerDryRun,
+};
+pub use quest_condition_functions::{
+    quest_trigger_check_conditions, quest_trigger_evaluate_conditions, QuestConditionResult,
+    QuestConditionStatus,
+};
 pub use quest_function_context::QuestFunctionContext;
 pub use quest_reward_functions::{quest_triggers_apply_rewards, quest_triggers_skip_rewards};
+pub use script_disassembly::disassemble_script_file;
 pub use script_function_context::ScriptFunctionContext;
 pub use script_function_resources::ScriptFunctionResources;
 
@@ -32,7 +39,9 @@ pub struct RoseScriptingPlugin;
 impl Plugin for RoseScriptingPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<LuaGameConstants>();
-        app.init_resource::<LuaGameFunctions>();
+        let lua_game_functions = LuaGameFunctions::default();
+        log::debug!(target: "lua", "{}", lua_game_functions.coverage_report());
+        app.insert_resource(lua_game_functions);
         app.init_resource::<LuaQuestFunctions>();
     }
 }
@@ -2,14 +2,95 @@ use rose_data::QuestTriggerHash;
 use rose_file_readers::QsdVariableType;
 
 use crate::scripting::{
-    quest_trigger_check_conditions, quest_triggers_apply_rewards, quest_triggers_skip_rewards,
+    quest_trigger_check_conditions, quest_trigger_evaluate_conditions,
+    quest_triggers_apply_rewards, quest_triggers_skip_rewards, QuestConditionResult,
     QuestFunctionContext, ScriptFunctionContext, ScriptFunctionResources,
 };
 
+#[derive(Debug)]
 pub enum QuestError {
     TriggerNotFound,
 }
 
+/// The result of evaluating one trigger in a chain with [`quest_dry_run`].
+pub struct QuestTriggerDryRun {
+    pub trigger_name: String,
+    pub conditions: Vec<QuestConditionResult>,
+    /// The rewards which would be applied if all conditions passed
+    pub rewards: Vec<String>,
+    pub passed: bool,
+}
+
+/// Evaluates a quest trigger chain against the current player state without
+/// applying any rewards, following the same path as [`quest_check_conditions`].
+pub fn quest_dry_run(
+    script_resources: &ScriptFunctionResources,
+    script_context: &mut ScriptFunctionContext,
+    trigger_hash: QuestTriggerHash,
+) -> Result<Vec<QuestTriggerDryRun>, QuestError> {
+    let mut trigger = script_resources
+        .game_data
+        .quests
+        .get_trigger_by_hash(trigger_hash);
+    if trigger.is_none() {
+        return Err(QuestError::TriggerNotFound);
+    }
+
+    let mut quest_context = QuestFunctionContext::default();
+    let mut results = Vec::new();
+
+    while let Some(quest_trigger) = trigger {
+        // Guard against triggers which loop back to an earlier trigger
+        if results
+            .iter()
+            .any(|result: &QuestTriggerDryRun| result.trigger_name == quest_trigger.name)
+        {
+            break;
+        }
+
+        let conditions = quest_trigger_evaluate_conditions(
+            script_resources,
+            script_context,
+            &mut quest_context,
+            quest_trigger,
+        );
+        let passed = conditions
+            .iter()
+            .all(|condition| condition.status.is_passed());
+
+        results.push(QuestTriggerDryRun {
+            trigger_name: quest_trigger.name.clone(),
+            conditions,
+            rewards: quest_trigger
+                .rewards
+                .iter()
+                .map(|reward| format!("{:?}", reward))
+                .collect(),
+            passed,
+        });
+
+        if passed {
+            quest_triggers_skip_rewards(
+                script_resources,
+                script_context,
+                &mut quest_context,
+                quest_trigger,
+            );
+            trigger = quest_context
+                .next_quest_trigger
+                .take()
+                .and_then(|name| script_resources.game_data.quests.get_trigger_by_name(&name));
+        } else {
+            trigger = quest_trigger
+                .next_trigger_name
+                .as_ref()
+                .and_then(|name| script_resources.game_data.quests.get_trigger_by_name(name));
+        }
+    }
+
+    Ok(results)
+}
+
 pub fn quest_check_conditions(
     script_resources: &ScriptFunctionResources,
     script_context: &mut ScriptFunctionContext,
@@ -198,3 +279,572 @@ pub fn set_quest_variable(
         QsdVariableType::Timer => None, // Does nothing
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        asset::AssetPlugin,
+        ecs::system::SystemState,
+        math::Vec3,
+        prelude::{App, Entity, MinimalPlugins, World},
+    };
+    use enum_map::enum_map;
+    use std::path::Path;
+
+    use rose_data::{QuestTrigger, SkillId, WorldTicks, ZoneId};
+    use rose_file_readers::{
+        QsdCondition, QsdConditionOperator, QsdItem, QsdReward, QsdTeamNumberSource,
+        QsdVariableType, VfsIndex, VirtualFilesystem, VirtualFilesystemDevice,
+    };
+    use rose_game_common::components::{
+        ActiveQuest, BasicStats, CharacterInfo, Equipment, ExperiencePoints, HealthPoints,
+        Inventory, Level, ManaPoints, MoveMode, MoveSpeed, QuestState, SkillList, SkillPoints,
+        Stamina, StatPoints, StatusEffects, Team, UnionMembership, MAX_STAMINA,
+    };
+
+    use crate::{
+        components::{PartyInfo, PartyOwner, PlayerCharacter, Position, SoundCategory},
+        events::{
+            BankEvent, ChatboxEvent, ClanDialogEvent, CraftingEvent, NpcStoreEvent,
+            PlayerCommandEvent, SpawnEffectEvent, SystemFuncEvent,
+        },
+        get_game_data_irose,
+        resources::{
+            ClientEntityList, GameData, RepairState, SelectedTarget, SoundSettings, WorldTime,
+        },
+        scripting::{
+            quest_trigger_evaluate_conditions, quest_triggers_apply_rewards, QuestConditionResult,
+            QuestConditionStatus, QuestFunctionContext, ScriptFunctionContext,
+            ScriptFunctionResources,
+        },
+    };
+
+    /// A synthetic player state which quest triggers are run against.
+    struct TestPlayer {
+        level: u32,
+        quest_switches: &'static [usize],
+        active_quests: &'static [usize],
+        /// `Some(true)` for a party leader, `Some(false)` for a party member
+        party_leader: Option<bool>,
+    }
+
+    fn new_player() -> TestPlayer {
+        TestPlayer {
+            level: 1,
+            quest_switches: &[],
+            active_quests: &[],
+            party_leader: None,
+        }
+    }
+
+    fn veteran_player() -> TestPlayer {
+        TestPlayer {
+            level: 80,
+            quest_switches: &[5],
+            active_quests: &[2],
+            party_leader: Some(true),
+        }
+    }
+
+    fn test_players() -> Vec<(&'static str, TestPlayer)> {
+        vec![("new", new_player()), ("veteran", veteran_player())]
+    }
+
+    fn load_game_data() -> GameData {
+        let vfs_path = std::env::var("ROSE_VFS_PATH")
+            .expect("ROSE_VFS_PATH must be set to the path of data.idx to run this test");
+        let vfs_devices: Vec<Box<dyn VirtualFilesystemDevice + Send + Sync>> =
+            vec![Box::new(VfsIndex::load(Path::new(&vfs_path)).unwrap())];
+        get_game_data_irose(&VirtualFilesystem::new(vfs_devices))
+    }
+
+    fn create_app(game_data: GameData) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .add_event::<BankEvent>()
+            .add_event::<ChatboxEvent>()
+            .add_event::<ClanDialogEvent>()
+            .add_event::<CraftingEvent>()
+            .add_event::<NpcStoreEvent>()
+            .add_event::<PlayerCommandEvent>()
+            .add_event::<SystemFuncEvent>()
+            .add_event::<SpawnEffectEvent>()
+            .insert_resource(game_data)
+            .insert_resource(ClientEntityList::default())
+            .insert_resource(RepairState::default())
+            .insert_resource(SelectedTarget::default())
+            .insert_resource(SoundSettings {
+                enabled: false,
+                global_gain: 0.0,
+                gains: enum_map! {
+                    SoundCategory::BackgroundMusic => 0.0,
+                    SoundCategory::PlayerFootstep => 0.0,
+                    SoundCategory::PlayerCombat => 0.0,
+                    SoundCategory::OtherFootstep => 0.0,
+                    SoundCategory::OtherCombat => 0.0,
+                    SoundCategory::NpcSounds => 0.0,
+                    SoundCategory::Ui => 0.0,
+                },
+            })
+            .insert_resource(WorldTime::new(WorldTicks(0)));
+        app
+    }
+
+    fn spawn_player(world: &mut World, player: &TestPlayer) -> Entity {
+        let character_info = CharacterInfo {
+            name: "QuestTest".to_string(),
+            gender: 0,
+            race: 0,
+            birth_stone: 0,
+            job: 0,
+            face: 1,
+            hair: 0,
+            rank: 0,
+            fame: 0,
+            fame_b: 0,
+            fame_g: 0,
+            revive_zone_id: ZoneId::new(1).unwrap(),
+            revive_position: Vec3::new(520000.0, 520000.0, 0.0),
+            unique_id: 1,
+        };
+        let level = Level::new(player.level);
+        let equipment = Equipment::new();
+        let basic_stats = BasicStats::default();
+        let skill_list = SkillList::default();
+        let ability_values = world
+            .resource::<GameData>()
+            .ability_value_calculator
+            .calculate(
+                &character_info,
+                &level,
+                &equipment,
+                &basic_stats,
+                &skill_list,
+                &StatusEffects::default(),
+            );
+        let move_speed = MoveSpeed::new(ability_values.get_move_speed(&MoveMode::Run));
+
+        let mut quest_state = QuestState::default();
+        for &switch_id in player.quest_switches {
+            quest_state.quest_switches.set(switch_id, true);
+        }
+        for &quest_id in player.active_quests {
+            quest_state.try_add_quest(ActiveQuest::new(quest_id, None));
+        }
+
+        let mut entity = world.spawn((
+            (
+                PlayerCharacter {},
+                character_info,
+                basic_stats,
+                level,
+                equipment,
+                skill_list,
+                ExperiencePoints::default(),
+                HealthPoints::new(100),
+                ManaPoints::new(100),
+                Inventory::default(),
+            ),
+            (
+                ability_values,
+                move_speed,
+                Position::new(Vec3::new(520000.0, 520000.0, 0.0)),
+                SkillPoints::default(),
+                Stamina::new(MAX_STAMINA),
+                StatPoints::default(),
+                Team::default_character(),
+                UnionMembership::default(),
+                quest_state,
+            ),
+        ));
+        if let Some(party_leader) = player.party_leader {
+            entity.insert(PartyInfo {
+                owner: if party_leader {
+                    PartyOwner::Player
+                } else {
+                    PartyOwner::Unknown
+                },
+                ..Default::default()
+            });
+        }
+        entity.id()
+    }
+
+    fn statuses(conditions: &[QuestConditionResult]) -> Vec<QuestConditionStatus> {
+        conditions
+            .iter()
+            .map(|condition| condition.status)
+            .collect()
+    }
+
+    fn test_trigger(conditions: Vec<QsdCondition>, rewards: Vec<QsdReward>) -> QuestTrigger {
+        QuestTrigger {
+            name: "test".to_string(),
+            conditions,
+            rewards,
+            next_trigger_name: None,
+        }
+    }
+
+    /// Runs `quest_trigger` against the player in `world`, returning the
+    /// condition results and, when they all passed, the reward result.
+    fn run_trigger(
+        world: &mut World,
+        quest_trigger: &QuestTrigger,
+    ) -> (
+        Vec<QuestConditionResult>,
+        Option<bool>,
+        QuestFunctionContext,
+    ) {
+        let mut system_state: SystemState<(ScriptFunctionResources, ScriptFunctionContext)> =
+            SystemState::new(world);
+        let (script_resources, mut script_context) = system_state.get_mut(world);
+        let mut quest_context = QuestFunctionContext::default();
+
+        let conditions = quest_trigger_evaluate_conditions(
+            &script_resources,
+            &mut script_context,
+            &mut quest_context,
+            quest_trigger,
+        );
+        let rewards = conditions
+            .iter()
+            .all(|condition| condition.status.is_passed())
+            .then(|| {
+                quest_triggers_apply_rewards(
+                    &script_resources,
+                    &mut script_context,
+                    &mut quest_context,
+                    quest_trigger,
+                )
+            });
+
+        system_state.apply(world);
+        (conditions, rewards, quest_context)
+    }
+
+    #[test]
+    #[ignore = "requires game data, set ROSE_VFS_PATH to the path of data.idx"]
+    fn quest_conditions_against_synthetic_players() {
+        use QuestConditionStatus::{Failed, Passed, ServerSide};
+
+        let skill_id = SkillId::new(1).unwrap();
+        let character_team_id = Team::DEFAULT_CHARACTER_TEAM_ID;
+        let cases: Vec<(&str, QsdCondition, [QuestConditionStatus; 2])> = vec![
+            (
+                "switch",
+                QsdCondition::QuestSwitch { id: 5, value: true },
+                [Failed, Passed],
+            ),
+            (
+                "switch off",
+                QsdCondition::QuestSwitch {
+                    id: 5,
+                    value: false,
+                },
+                [Passed, Failed],
+            ),
+            (
+                "select quest",
+                QsdCondition::SelectQuest { id: 2 },
+                [Failed, Passed],
+            ),
+            (
+                "has clan",
+                QsdCondition::HasClan { has_clan: true },
+                [Failed, Failed],
+            ),
+            (
+                "no clan",
+                QsdCondition::HasClan { has_clan: false },
+                [Passed, Passed],
+            ),
+            (
+                "clan level",
+                QsdCondition::ClanLevel {
+                    operator: QsdConditionOperator::GreaterThanEqual,
+                    value: 0,
+                },
+                [Failed, Failed],
+            ),
+            (
+                "not has skill",
+                QsdCondition::Skill {
+                    id: skill_id,
+                    has_skill: false,
+                },
+                [Passed, Passed],
+            ),
+            (
+                "has skill",
+                QsdCondition::Skill {
+                    id: skill_id,
+                    has_skill: true,
+                },
+                [Failed, Failed],
+            ),
+            (
+                "team",
+                QsdCondition::TeamNumber {
+                    range: character_team_id as _..=character_team_id as _,
+                },
+                [Passed, Passed],
+            ),
+            (
+                "world time",
+                QsdCondition::WorldTime { range: 0..=0 },
+                [Passed, Passed],
+            ),
+            (
+                "month day time",
+                QsdCondition::MonthDayTime {
+                    month_day: None,
+                    day_minutes_range: 0..=0,
+                },
+                [ServerSide, ServerSide],
+            ),
+        ];
+
+        let mut app = create_app(load_game_data());
+        let mut failures = Vec::new();
+        for (case_name, condition, expected) in cases {
+            let quest_trigger = test_trigger(vec![condition], Vec::new());
+
+            for ((player_name, player), expected) in test_players().iter().zip(expected) {
+                let player_entity = spawn_player(&mut app.world, player);
+                let (conditions, _, _) = run_trigger(&mut app.world, &quest_trigger);
+                app.world.despawn(player_entity);
+
+                let statuses = statuses(&conditions);
+                if statuses != [expected] {
+                    failures.push(format!(
+                        "{} for {} player: expected {:?}, got {:?}",
+                        case_name, player_name, expected, statuses
+                    ));
+                }
+            }
+        }
+
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    #[test]
+    #[ignore = "requires game data, set ROSE_VFS_PATH to the path of data.idx"]
+    fn quest_variables_use_selected_quest() {
+        let mut app = create_app(load_game_data());
+        let quest_trigger = test_trigger(
+            vec![
+                QsdCondition::SelectQuest { id: 2 },
+                QsdCondition::QuestVariable {
+                    variable_type: QsdVariableType::Variable,
+                    variable_id: 0,
+                    operator: QsdConditionOperator::Equals,
+                    value: 0,
+                },
+            ],
+            vec![QsdReward::QuestVariable {
+                variable_type: QsdVariableType::Variable,
+                variable_id: 0,
+                operator: rose_file_readers::QsdRewardOperator::Add,
+                value: 3,
+            }],
+        );
+
+        let player_entity = spawn_player(&mut app.world, &veteran_player());
+        let (conditions, rewards, _) = run_trigger(&mut app.world, &quest_trigger);
+        assert_eq!(
+            statuses(&conditions),
+            [QuestConditionStatus::Passed, QuestConditionStatus::Passed]
+        );
+        assert_eq!(rewards, Some(true));
+
+        // The variable is now 3, so the condition no longer passes
+        let (conditions, rewards, _) = run_trigger(&mut app.world, &quest_trigger);
+        assert_eq!(
+            statuses(&conditions),
+            [QuestConditionStatus::Passed, QuestConditionStatus::Failed]
+        );
+        assert_eq!(rewards, None);
+
+        let quest_state = app.world.get::<QuestState>(player_entity).unwrap();
+        let active_quest = quest_state.find_active_quest(2).unwrap();
+        assert_eq!(active_quest.variables[0], 3);
+    }
+
+    #[test]
+    #[ignore = "requires game data, set ROSE_VFS_PATH to the path of data.idx"]
+    fn quest_rewards_against_synthetic_players() {
+        let cases: Vec<(&str, QsdReward, [bool; 2])> = vec![
+            (
+                "set switch",
+                QsdReward::SetQuestSwitch { id: 7, value: true },
+                [true, true],
+            ),
+            (
+                "team from clan",
+                QsdReward::SetTeamNumber {
+                    source: QsdTeamNumberSource::Clan,
+                },
+                [false, false],
+            ),
+            (
+                "team from party",
+                QsdReward::SetTeamNumber {
+                    source: QsdTeamNumberSource::Party,
+                },
+                [false, true],
+            ),
+            (
+                "unique team",
+                QsdReward::SetTeamNumber {
+                    source: QsdTeamNumberSource::Unique,
+                },
+                [true, true],
+            ),
+            (
+                "variable without selected quest",
+                QsdReward::QuestVariable {
+                    variable_type: QsdVariableType::Variable,
+                    variable_id: 0,
+                    operator: rose_file_readers::QsdRewardOperator::Set,
+                    value: 1,
+                },
+                [false, false],
+            ),
+            (
+                "next trigger",
+                QsdReward::Trigger {
+                    name: "next".to_string(),
+                },
+                [true, true],
+            ),
+        ];
+
+        let mut app = create_app(load_game_data());
+        let mut failures = Vec::new();
+        for (case_name, reward, expected) in cases {
+            let quest_trigger = test_trigger(Vec::new(), vec![reward]);
+
+            for ((player_name, player), expected) in test_players().iter().zip(expected) {
+                let player_entity = spawn_player(&mut app.world, player);
+                let (_, rewards, _) = run_trigger(&mut app.world, &quest_trigger);
+                app.world.despawn(player_entity);
+
+                if rewards != Some(expected) {
+                    failures.push(format!(
+                        "{} for {} player: expected {:?}, got {:?}",
+                        case_name, player_name, expected, rewards
+                    ));
+                }
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+
+        // Check the rewards changed the player state
+        let player_entity = spawn_player(&mut app.world, &new_player());
+        let (_, rewards, quest_context) = run_trigger(
+            &mut app.world,
+            &test_trigger(
+                Vec::new(),
+                vec![
+                    QsdReward::SetQuestSwitch { id: 7, value: true },
+                    QsdReward::AddQuest { id: 2 },
+                    QsdReward::Trigger {
+                        name: "next".to_string(),
+                    },
+                ],
+            ),
+        );
+        assert_eq!(rewards, Some(true));
+        assert_eq!(quest_context.next_quest_trigger.as_deref(), Some("next"));
+
+        let quest_state = app.world.get::<QuestState>(player_entity).unwrap();
+        assert!(quest_state.quest_switches[7]);
+        assert!(quest_state.find_active_quest_index(2).is_some());
+    }
+
+    /// Runs every quest trigger in the game data against each synthetic player,
+    /// checking no condition fails because its data could not be decoded.
+    #[test]
+    #[ignore = "requires game data, set ROSE_VFS_PATH to the path of data.idx"]
+    fn quest_triggers_run_against_synthetic_players() {
+        let game_data = load_game_data();
+        let quests = game_data.quests.clone();
+        let mut app = create_app(game_data);
+
+        let mut failures = Vec::new();
+        let mut passed_triggers = 0;
+        for quest_trigger in quests.triggers.values() {
+            if let Some(next_trigger_name) = quest_trigger.next_trigger_name.as_ref() {
+                if quests.get_trigger_by_name(next_trigger_name).is_none() {
+                    failures.push(format!(
+                        "{}: next trigger {} not found",
+                        quest_trigger.name, next_trigger_name
+                    ));
+                }
+            }
+
+            // Items which can not be decoded are silently treated as a quantity of 0
+            let data_decoder = &app.world.resource::<GameData>().data_decoder;
+            let decode_item = |item: &QsdItem| {
+                data_decoder
+                    .decode_item_reference(item.item_number, item.item_type)
+                    .is_some()
+            };
+            for condition in quest_trigger.conditions.iter() {
+                if let QsdCondition::QuestItem {
+                    item: Some(item), ..
+                } = condition
+                {
+                    if !decode_item(item) {
+                        failures.push(format!("{}: {:?}", quest_trigger.name, condition));
+                    }
+                }
+            }
+
+            for reward in quest_trigger.rewards.iter() {
+                match reward {
+                    QsdReward::Trigger { name } => {
+                        if quests.get_trigger_by_name(name).is_none() {
+                            failures
+                                .push(format!("{}: {:?} not found", quest_trigger.name, reward));
+                        }
+                    }
+                    QsdReward::AddItem { item, .. } | QsdReward::RemoveItem { item, .. } => {
+                        if !decode_item(item) {
+                            failures.push(format!("{}: {:?}", quest_trigger.name, reward));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            for (player_name, player) in test_players().iter() {
+                let player_entity = spawn_player(&mut app.world, player);
+                let (conditions, rewards, _) = run_trigger(&mut app.world, quest_trigger);
+                app.world.despawn(player_entity);
+
+                assert_eq!(conditions.len(), quest_trigger.conditions.len());
+                for condition in conditions.iter() {
+                    if matches!(
+                        condition.observed.as_deref(),
+                        Some("\"Unknown ability type\"" | "\"Invalid switch id\"")
+                    ) {
+                        failures.push(format!(
+                            "{} for {} player: {}",
+                            quest_trigger.name, player_name, condition.condition
+                        ));
+                    }
+                }
+
+                if rewards.is_some() {
+                    passed_triggers += 1;
+                }
+            }
+        }
+
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+        // Some triggers, such as those starting quests, must pass for the players
+        assert!(passed_triggers > 0);
+    }
+}
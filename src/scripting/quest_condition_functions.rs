@@ -1,3 +1,6 @@
+use bevy::math::{Vec2, Vec3Swizzles};
+use std::{fmt::Debug, ops::RangeInclusive};
+
 use rose_data::QuestTrigger;
 use rose_file_readers::{
     QsdAbilityType, QsdClanPosition, QsdCondition, QsdConditionOperator, QsdEquipmentIndex,
@@ -6,24 +9,73 @@ use rose_file_readers::{
 
 use crate::{
     bundles::ability_values_get_value,
+    components::{Clan, ClanMembership, PartyOwner},
     scripting::{
         quest::get_quest_variable, QuestFunctionContext, ScriptFunctionContext,
         ScriptFunctionResources,
     },
 };
 
-fn quest_condition_operator<T: PartialEq + PartialOrd>(
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum QuestConditionStatus {
+    Passed,
+    Failed,
+    /// Checked by the server, the client assumes these pass
+    ServerSide,
+}
+
+impl QuestConditionStatus {
+    pub fn is_passed(self) -> bool {
+        matches!(
+            self,
+            QuestConditionStatus::Passed | QuestConditionStatus::ServerSide
+        )
+    }
+}
+
+/// The result of checking a single `QsdCondition` of a quest trigger.
+#[derive(Clone, Debug)]
+pub struct QuestConditionResult {
+    pub condition: String,
+    pub status: QuestConditionStatus,
+    pub observed: Option<String>,
+    pub required: Option<String>,
+}
+
+struct QuestConditionCheck {
+    passed: bool,
+    observed: String,
+    required: String,
+}
+
+impl QuestConditionCheck {
+    fn new(passed: bool, observed: impl Debug, required: impl Debug) -> Self {
+        Self {
+            passed,
+            observed: format!("{:?}", observed),
+            required: format!("{:?}", required),
+        }
+    }
+}
+
+fn quest_condition_operator<T: PartialEq + PartialOrd + Debug>(
     operator: QsdConditionOperator,
     value_lhs: T,
     value_rhs: T,
-) -> bool {
-    match operator {
+) -> QuestConditionCheck {
+    let passed = match operator {
         QsdConditionOperator::Equals => value_lhs == value_rhs,
         QsdConditionOperator::GreaterThan => value_lhs > value_rhs,
         QsdConditionOperator::GreaterThanEqual => value_lhs >= value_rhs,
         QsdConditionOperator::LessThan => value_lhs < value_rhs,
         QsdConditionOperator::LessThanEqual => value_lhs <= value_rhs,
         QsdConditionOperator::NotEqual => value_lhs != value_rhs,
+    };
+
+    QuestConditionCheck {
+        passed,
+        observed: format!("{:?}", value_lhs),
+        required: format!("{:?} {:?}", operator, value_rhs),
     }
 }
 
@@ -34,19 +86,19 @@ fn quest_condition_ability_value(
     ability_type: QsdAbilityType,
     operator: QsdConditionOperator,
     compare_value: i32,
-) -> bool {
+) -> QuestConditionCheck {
     let character = script_context.query_player.single();
 
-    let ability_type = script_resources
+    let Some(ability_type) = script_resources
         .game_data
         .data_decoder
-        .decode_ability_type(ability_type.get());
-    if ability_type.is_none() {
-        return false;
-    }
+        .decode_ability_type(ability_type.get())
+    else {
+        return QuestConditionCheck::new(false, "Unknown ability type", ability_type);
+    };
 
     let current_value = ability_values_get_value(
-        ability_type.unwrap(),
+        ability_type,
         character.ability_values,
         Some(character.character_info),
         Some(character.experience_points),
@@ -72,14 +124,14 @@ fn quest_condition_check_switch(
     _quest_context: &mut QuestFunctionContext,
     switch_id: usize,
     value: bool,
-) -> bool {
+) -> QuestConditionCheck {
     let quest_state = script_context.query_quest.single();
 
     if let Some(switch_value) = quest_state.quest_switches.get(switch_id) {
-        return *switch_value == value;
+        return QuestConditionCheck::new(*switch_value == value, *switch_value, value);
     }
 
-    false
+    QuestConditionCheck::new(false, "Invalid switch id", value)
 }
 
 fn quest_condition_quest_item(
@@ -90,7 +142,7 @@ fn quest_condition_quest_item(
     equipment_index: Option<QsdEquipmentIndex>,
     required_count: u32,
     operator: QsdConditionOperator,
-) -> bool {
+) -> QuestConditionCheck {
     let item_reference = item.and_then(|item| {
         script_resources
             .game_data
@@ -109,11 +161,15 @@ fn quest_condition_quest_item(
     let character = script_context.query_player.single();
 
     if let Some(equipment_index) = equipment_index {
-        item_reference
-            == character
-                .equipment
-                .get_equipment_item(equipment_index)
-                .map(|item| item.item)
+        let equipped_item = character
+            .equipment
+            .get_equipment_item(equipment_index)
+            .map(|item| item.item);
+        QuestConditionCheck::new(
+            item_reference == equipped_item,
+            equipped_item,
+            item_reference,
+        )
     } else {
         let quantity = if let Some(item_reference) = item_reference {
             if item_reference.item_type.is_quest_item() {
@@ -152,7 +208,7 @@ fn quest_condition_quest_variable(
     variable_id: usize,
     operator: QsdConditionOperator,
     value: i32,
-) -> bool {
+) -> QuestConditionCheck {
     if let Some(variable_value) = get_quest_variable(
         script_resources,
         script_context,
@@ -162,7 +218,7 @@ fn quest_condition_quest_variable(
     ) {
         quest_condition_operator(operator, variable_value, value)
     } else {
-        false
+        QuestConditionCheck::new(false, "No selected quest", value)
     }
 }
 
@@ -171,15 +227,15 @@ fn quest_condition_select_quest(
     script_context: &mut ScriptFunctionContext,
     quest_context: &mut QuestFunctionContext,
     quest_id: usize,
-) -> bool {
+) -> QuestConditionCheck {
     let quest_state = script_context.query_quest.single();
 
     if let Some(quest_index) = quest_state.find_active_quest_index(quest_id) {
         quest_context.selected_quest_index = Some(quest_index);
-        return true;
+        return QuestConditionCheck::new(true, "Active", "Active");
     }
 
-    false
+    QuestConditionCheck::new(false, "Not active", "Active")
 }
 
 fn quest_condition_clan_position(
@@ -188,7 +244,7 @@ fn quest_condition_clan_position(
     _quest_context: &mut QuestFunctionContext,
     operator: QsdConditionOperator,
     compare_value: QsdClanPosition,
-) -> bool {
+) -> QuestConditionCheck {
     let character = script_context.query_player.single();
     let value = character
         .clan_membership
@@ -207,91 +263,275 @@ fn quest_condition_in_clan(
     script_context: &mut ScriptFunctionContext,
     _quest_context: &mut QuestFunctionContext,
     in_clan: bool,
-) -> bool {
+) -> QuestConditionCheck {
     let character = script_context.query_player.single();
-    character.clan_membership.is_some() == in_clan
+    let has_clan = character.clan_membership.is_some();
+    QuestConditionCheck::new(has_clan == in_clan, has_clan, in_clan)
 }
 
-pub fn quest_trigger_check_conditions(
+fn quest_condition_in_range<T: PartialOrd + Debug>(
+    range: &RangeInclusive<T>,
+    value: T,
+) -> QuestConditionCheck {
+    QuestConditionCheck::new(range.contains(&value), value, range)
+}
+
+fn quest_condition_clan<T: PartialEq + PartialOrd + Debug>(
+    script_context: &mut ScriptFunctionContext,
+    operator: QsdConditionOperator,
+    get_value: impl FnOnce(&Clan, &ClanMembership) -> T,
+    compare_value: T,
+) -> QuestConditionCheck {
+    let character = script_context.query_player.single();
+
+    if let (Some(clan), Some(clan_membership)) = (character.clan, character.clan_membership) {
+        quest_condition_operator(operator, get_value(clan, clan_membership), compare_value)
+    } else {
+        QuestConditionCheck::new(false, "No clan", compare_value)
+    }
+}
+
+fn quest_condition_party(
+    script_context: &mut ScriptFunctionContext,
+    is_leader: bool,
+) -> QuestConditionCheck {
+    // The client is not sent the party level, so only membership is checked
+    let character = script_context.query_player.single();
+
+    match character.party_info {
+        Some(party_info) => {
+            let is_party_leader = matches!(party_info.owner, PartyOwner::Player);
+            QuestConditionCheck::new(
+                !is_leader || is_party_leader,
+                if is_party_leader { "Leader" } else { "Member" },
+                if is_leader { "Leader" } else { "Member" },
+            )
+        }
+        None => QuestConditionCheck::new(
+            false,
+            "No party",
+            if is_leader { "Leader" } else { "Member" },
+        ),
+    }
+}
+
+fn quest_trigger_check_condition(
     script_resources: &ScriptFunctionResources,
     script_context: &mut ScriptFunctionContext,
     quest_context: &mut QuestFunctionContext,
-    quest_trigger: &QuestTrigger,
-) -> bool {
-    for condition in quest_trigger.conditions.iter() {
-        let result = match *condition {
-            QsdCondition::AbilityValue {
-                ability_type,
-                operator,
-                value,
-            } => quest_condition_ability_value(
-                script_resources,
-                script_context,
-                quest_context,
-                ability_type,
-                operator,
-                value,
-            ),
-            QsdCondition::QuestItem {
-                item,
-                equipment_index,
-                required_count,
-                operator,
-            } => quest_condition_quest_item(
-                script_resources,
-                script_context,
-                quest_context,
-                item,
-                equipment_index,
-                required_count,
-                operator,
-            ),
-            QsdCondition::QuestVariable {
-                variable_type,
-                variable_id,
-                operator,
-                value,
-            } => quest_condition_quest_variable(
-                script_resources,
-                script_context,
-                quest_context,
-                variable_type,
-                variable_id,
-                operator,
-                value,
-            ),
-            QsdCondition::QuestSwitch { id, value } => quest_condition_check_switch(
-                script_resources,
-                script_context,
-                quest_context,
-                id,
-                value,
-            ),
-            QsdCondition::SelectQuest { id } => {
-                quest_condition_select_quest(script_resources, script_context, quest_context, id)
+    condition: &QsdCondition,
+) -> QuestConditionResult {
+    let check = match *condition {
+        QsdCondition::AbilityValue {
+            ability_type,
+            operator,
+            value,
+        } => quest_condition_ability_value(
+            script_resources,
+            script_context,
+            quest_context,
+            ability_type,
+            operator,
+            value,
+        ),
+        QsdCondition::QuestItem {
+            item,
+            equipment_index,
+            required_count,
+            operator,
+        } => quest_condition_quest_item(
+            script_resources,
+            script_context,
+            quest_context,
+            item,
+            equipment_index,
+            required_count,
+            operator,
+        ),
+        QsdCondition::QuestVariable {
+            variable_type,
+            variable_id,
+            operator,
+            value,
+        } => quest_condition_quest_variable(
+            script_resources,
+            script_context,
+            quest_context,
+            variable_type,
+            variable_id,
+            operator,
+            value,
+        ),
+        QsdCondition::QuestSwitch { id, value } => {
+            quest_condition_check_switch(script_resources, script_context, quest_context, id, value)
+        }
+        QsdCondition::SelectQuest { id } => {
+            quest_condition_select_quest(script_resources, script_context, quest_context, id)
+        }
+        QsdCondition::ClanPosition { operator, value } => quest_condition_clan_position(
+            script_resources,
+            script_context,
+            quest_context,
+            operator,
+            value,
+        ),
+        QsdCondition::HasClan { has_clan } => {
+            quest_condition_in_clan(script_resources, script_context, quest_context, has_clan)
+        }
+        QsdCondition::ClanLevel { operator, value } => quest_condition_clan(
+            script_context,
+            operator,
+            |clan, _| u32::from(clan.level.0),
+            value as _,
+        ),
+        QsdCondition::ClanPoints { operator, value } => quest_condition_clan(
+            script_context,
+            operator,
+            |clan, _| clan.points.0,
+            value as _,
+        ),
+        QsdCondition::ClanMoney { operator, value } => {
+            quest_condition_clan(script_context, operator, |clan, _| clan.money.0, value as _)
+        }
+        QsdCondition::ClanMemberCount { operator, value } => quest_condition_clan(
+            script_context,
+            operator,
+            |clan, _| clan.members.len(),
+            value as _,
+        ),
+        QsdCondition::ClanPointContribution { operator, value } => quest_condition_clan(
+            script_context,
+            operator,
+            |_, clan_membership| clan_membership.contribution.0,
+            value as _,
+        ),
+        QsdCondition::HasClanSkill { id, has_skill } => {
+            let character = script_context.query_player.single();
+            let clan_has_skill = character
+                .clan
+                .map_or(false, |clan| clan.skills.contains(&id));
+            QuestConditionCheck::new(clan_has_skill == has_skill, clan_has_skill, has_skill)
+        }
+        QsdCondition::Skill { id, has_skill } => {
+            let character = script_context.query_player.single();
+            let player_has_skill = character
+                .skill_list
+                .find_skill_level(&script_resources.game_data.skills, id)
+                .is_some();
+            QuestConditionCheck::new(player_has_skill == has_skill, player_has_skill, has_skill)
+        }
+        QsdCondition::Position {
+            zone,
+            position,
+            distance,
+        } => {
+            let character = script_context.query_player.single();
+            let current_zone = script_resources
+                .current_zone
+                .as_ref()
+                .map(|current_zone| current_zone.id);
+
+            if current_zone != Some(zone) {
+                QuestConditionCheck::new(false, current_zone, zone)
+            } else {
+                let current_distance = character
+                    .position
+                    .position
+                    .xy()
+                    .distance(Vec2::new(position.x as f32, position.y as f32));
+                QuestConditionCheck {
+                    passed: current_distance <= distance as f32,
+                    observed: format!("{} from {:?}", current_distance, position),
+                    required: format!("<= {}", distance),
+                }
             }
-            QsdCondition::ClanPosition { operator, value } => quest_condition_clan_position(
+        }
+        QsdCondition::WorldTime { ref range } => quest_condition_in_range(
+            range,
+            script_resources.world_time.ticks.get_world_time() as _,
+        ),
+        QsdCondition::TeamNumber { ref range } => {
+            let character = script_context.query_player.single();
+            quest_condition_in_range(range, character.team.id as _)
+        }
+        QsdCondition::PartyMemberCount { ref range } => {
+            let character = script_context.query_player.single();
+            let party_member_count = character
+                .party_info
+                .map_or(0, |party_info| party_info.members.len());
+            quest_condition_in_range(range, party_member_count as _)
+        }
+        QsdCondition::Party { is_leader, .. } => quest_condition_party(script_context, is_leader),
+        // Server side only conditions, these depend on state the client does not have:
+        QsdCondition::RandomPercent { .. }
+        | QsdCondition::ObjectVariable { .. }
+        | QsdCondition::SelectEventObject { .. }
+        | QsdCondition::SelectNpc { .. }
+        | QsdCondition::ObjectZoneTime { .. }
+        | QsdCondition::ObjectDistance { .. }
+        | QsdCondition::CompareNpcVariables { .. }
+        | QsdCondition::ServerChannelNumber { .. }
+        // Checked against the server's clock, which may be in another timezone:
+        | QsdCondition::MonthDayTime { .. }
+        | QsdCondition::WeekDayTime { .. } => {
+            return QuestConditionResult {
+                condition: format!("{:?}", condition),
+                status: QuestConditionStatus::ServerSide,
+                observed: None,
+                required: None,
+            };
+        }
+    };
+
+    QuestConditionResult {
+        condition: format!("{:?}", condition),
+        status: if check.passed {
+            QuestConditionStatus::Passed
+        } else {
+            QuestConditionStatus::Failed
+        },
+        observed: Some(check.observed),
+        required: Some(check.required),
+    }
+}
+
+/// Checks every condition of the trigger, unlike [`quest_trigger_check_conditions`]
+/// this does not stop at the first failed condition.
+pub fn quest_trigger_evaluate_conditions(
+    script_resources: &ScriptFunctionResources,
+    script_context: &mut ScriptFunctionContext,
+    quest_context: &mut QuestFunctionContext,
+    quest_trigger: &QuestTrigger,
+) -> Vec<QuestConditionResult> {
+    quest_trigger
+        .conditions
+        .iter()
+        .map(|condition| {
+            quest_trigger_check_condition(
                 script_resources,
                 script_context,
                 quest_context,
-                operator,
-                value,
-            ),
-            QsdCondition::HasClan { has_clan } => {
-                quest_condition_in_clan(script_resources, script_context, quest_context, has_clan)
-            }
-            // Server side only conditions:
-            QsdCondition::RandomPercent { .. }
-            | QsdCondition::ObjectVariable { .. }
-            | QsdCondition::SelectEventObject { .. }
-            | QsdCondition::SelectNpc { .. } => true,
-            _ => {
-                log::warn!("Unimplemented quest condition: {:?}", condition);
-                false
-            }
-        };
+                condition,
+            )
+        })
+        .collect()
+}
+
+pub fn quest_trigger_check_conditions(
+    script_resources: &ScriptFunctionResources,
+    script_context: &mut ScriptFunctionContext,
+    quest_context: &mut QuestFunctionContext,
+    quest_trigger: &QuestTrigger,
+) -> bool {
+    for condition in quest_trigger.conditions.iter() {
+        let result = quest_trigger_check_condition(
+            script_resources,
+            script_context,
+            quest_context,
+            condition,
+        );
 
-        if !result {
+        if !result.status.is_passed() {
             log::debug!(target: "quest", "Condition Failed: {:?}", condition);
             return false;
         } else {
@@ -1,5 +1,7 @@
-use rose_data::{QuestTrigger, StackableItem, WorldTicks};
-use rose_file_readers::{QsdItem, QsdReward, QsdRewardOperator, QsdVariableType};
+use rose_data::{QuestTrigger, SkillId, StackableItem, WorldTicks};
+use rose_file_readers::{
+    QsdItem, QsdReward, QsdRewardOperator, QsdTeamNumberSource, QsdVariableType,
+};
 use rose_game_common::components::ActiveQuest;
 
 use crate::{
@@ -10,6 +12,8 @@ use crate::{
     },
 };
 
+const QUEST_SWITCH_GROUP_SIZE: usize = 32;
+
 fn quest_reward_operator(operator: QsdRewardOperator, variable_value: i32, value: i32) -> i32 {
     match operator {
         QsdRewardOperator::Set => value,
@@ -270,6 +274,116 @@ fn quest_reward_set_quest_switch(
     false
 }
 
+fn quest_reward_clear_switch_group(
+    _script_resources: &ScriptFunctionResources,
+    script_context: &mut ScriptFunctionContext,
+    _quest_context: &mut QuestFunctionContext,
+    group: usize,
+) -> bool {
+    let mut quest_state = script_context.query_quest.single_mut();
+
+    for switch_id in group * QUEST_SWITCH_GROUP_SIZE..(group + 1) * QUEST_SWITCH_GROUP_SIZE {
+        if let Some(mut switch) = quest_state.quest_switches.get_mut(switch_id) {
+            *switch = false;
+        }
+    }
+
+    true
+}
+
+fn quest_reward_clear_all_switches(
+    _script_resources: &ScriptFunctionResources,
+    script_context: &mut ScriptFunctionContext,
+    _quest_context: &mut QuestFunctionContext,
+) -> bool {
+    let mut quest_state = script_context.query_quest.single_mut();
+    quest_state.quest_switches.fill(false);
+    true
+}
+
+// The server applies the skill, team and clan rewards and sends us the updated
+// state, so the client only checks that they would succeed on the server.
+
+fn quest_reward_add_skill(
+    script_resources: &ScriptFunctionResources,
+    script_context: &mut ScriptFunctionContext,
+    _quest_context: &mut QuestFunctionContext,
+    skill_id: SkillId,
+) -> bool {
+    let character = script_context.query_player.single();
+
+    script_resources
+        .game_data
+        .skills
+        .get_skill(skill_id)
+        .is_some()
+        && character
+            .skill_list
+            .find_skill_level(&script_resources.game_data.skills, skill_id)
+            .is_none()
+}
+
+fn quest_reward_remove_skill(
+    script_resources: &ScriptFunctionResources,
+    script_context: &mut ScriptFunctionContext,
+    _quest_context: &mut QuestFunctionContext,
+    skill_id: SkillId,
+) -> bool {
+    let character = script_context.query_player.single();
+
+    character
+        .skill_list
+        .find_skill_level(&script_resources.game_data.skills, skill_id)
+        .is_some()
+}
+
+fn quest_reward_reset_skills(
+    _script_resources: &ScriptFunctionResources,
+    _script_context: &mut ScriptFunctionContext,
+    _quest_context: &mut QuestFunctionContext,
+) -> bool {
+    // Resetting skills can not fail
+    true
+}
+
+fn quest_reward_set_team_number(
+    _script_resources: &ScriptFunctionResources,
+    script_context: &mut ScriptFunctionContext,
+    _quest_context: &mut QuestFunctionContext,
+    source: QsdTeamNumberSource,
+) -> bool {
+    let character = script_context.query_player.single();
+
+    match source {
+        QsdTeamNumberSource::Unique => true,
+        QsdTeamNumberSource::Clan => character.clan.is_some(),
+        QsdTeamNumberSource::Party => character.party_info.is_some(),
+    }
+}
+
+fn quest_reward_clan(
+    _script_resources: &ScriptFunctionResources,
+    script_context: &mut ScriptFunctionContext,
+    _quest_context: &mut QuestFunctionContext,
+) -> bool {
+    let character = script_context.query_player.single();
+    character.clan.is_some()
+}
+
+fn quest_reward_clan_skill(
+    _script_resources: &ScriptFunctionResources,
+    script_context: &mut ScriptFunctionContext,
+    _quest_context: &mut QuestFunctionContext,
+    skill_id: SkillId,
+    add_skill: bool,
+) -> bool {
+    let character = script_context.query_player.single();
+
+    character
+        .clan
+        .map_or(false, |clan| clan.skills.contains(&skill_id) != add_skill)
+}
+
 fn quest_reward_quest_variable(
     script_resources: &ScriptFunctionResources,
     script_context: &mut ScriptFunctionContext,
@@ -424,6 +538,15 @@ pub fn quest_triggers_apply_rewards(
                 id,
                 value,
             ),
+            QsdReward::ClearSwitchGroup { group } => quest_reward_clear_switch_group(
+                script_resources,
+                script_context,
+                quest_context,
+                group,
+            ),
+            QsdReward::ClearAllSwitches { .. } => {
+                quest_reward_clear_all_switches(script_resources, script_context, quest_context)
+            }
             QsdReward::Trigger { ref name } => quest_reward_set_next_trigger(
                 script_resources,
                 script_context,
@@ -436,16 +559,50 @@ pub fn quest_triggers_apply_rewards(
                 quest_context,
                 name.clone(),
             ),
-            // Server side only rewards:
+            QsdReward::AddSkill { id, .. } => {
+                quest_reward_add_skill(script_resources, script_context, quest_context, id)
+            }
+            QsdReward::RemoveSkill { id, .. } => {
+                quest_reward_remove_skill(script_resources, script_context, quest_context, id)
+            }
+            QsdReward::ResetSkills { .. } => {
+                quest_reward_reset_skills(script_resources, script_context, quest_context)
+            }
+            QsdReward::SetTeamNumber { source } => quest_reward_set_team_number(
+                script_resources,
+                script_context,
+                quest_context,
+                source,
+            ),
+            QsdReward::ClanLevel { .. }
+            | QsdReward::ClanMoney { .. }
+            | QsdReward::ClanPoints { .. }
+            | QsdReward::ClanPointContribution { .. }
+            | QsdReward::TeleportNearbyClanMembers { .. } => {
+                quest_reward_clan(script_resources, script_context, quest_context)
+            }
+            QsdReward::AddClanSkill { id, .. } => {
+                quest_reward_clan_skill(script_resources, script_context, quest_context, id, true)
+            }
+            QsdReward::RemoveClanSkill { id, .. } => {
+                quest_reward_clan_skill(script_resources, script_context, quest_context, id, false)
+            }
+            // Server side only rewards, these update state the client does not have
+            // or which the server sends to us:
             QsdReward::AbilityValue { .. }
             | QsdReward::CalculatedExperiencePoints { .. }
             | QsdReward::CalculatedItem { .. }
             | QsdReward::CalculatedMoney { .. }
-            | QsdReward::Teleport { .. } => true,
-            _ => {
-                log::warn!("Unimplemented quest reward: {:?}", reward);
-                true
-            }
+            | QsdReward::Teleport { .. }
+            | QsdReward::ResetBasicStats { .. }
+            | QsdReward::SpawnMonster { .. }
+            | QsdReward::SetRevivePosition { .. }
+            | QsdReward::SetMonsterSpawnState { .. }
+            | QsdReward::ObjectVariable { .. }
+            | QsdReward::NpcMessage { .. }
+            | QsdReward::FormatAnnounceMessage { .. }
+            | QsdReward::TriggerAfterDelayForObject { .. }
+            | QsdReward::TriggerForZoneTeam { .. } => true,
         };
 
         if !result {
@@ -0,0 +1,44 @@
+use rose_file_readers::{ConFile, VfsFile, VirtualFilesystem};
+
+use crate::scripting::{
+    lua4::{Lua4Disassembler, Lua4Disassembly, Lua4Function},
+    LuaGameConstants, LuaGameFunctions, LuaQuestFunctions,
+};
+
+/// Disassembles a .CON conversation script or a compiled lua chunk, using the
+/// same globals the client provides to `Lua4VM` when running conversations.
+pub fn disassemble_script_file(
+    vfs: &VirtualFilesystem,
+    path: &str,
+) -> Result<Lua4Disassembly, anyhow::Error> {
+    let function = if path.to_uppercase().ends_with(".CON") {
+        let con_file = vfs.read_file::<ConFile, _>(path)?;
+        Lua4Function::from_bytes(&con_file.script_binary)?
+    } else {
+        let bytes: Vec<u8> = match vfs.open_file(path)? {
+            VfsFile::Buffer(buffer) => buffer,
+            VfsFile::View(view) => view.into(),
+        };
+        Lua4Function::from_bytes(&bytes)?
+    };
+
+    let game_constants = LuaGameConstants::default();
+    let game_functions = LuaGameFunctions::default();
+    let quest_functions = LuaQuestFunctions::default();
+
+    let mut disassembler = Lua4Disassembler::new();
+    disassembler
+        .defined_globals
+        .extend(game_constants.constants.keys().cloned());
+    disassembler
+        .defined_globals
+        .extend(game_functions.closures.keys().cloned());
+    disassembler
+        .defined_globals
+        .extend(quest_functions.closures.keys().cloned());
+    disassembler
+        .stubbed_globals
+        .extend(game_functions.stubs.iter().cloned());
+
+    Ok(disassembler.disassemble(&function))
+}
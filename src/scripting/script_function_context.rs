@@ -1,17 +1,24 @@
 use bevy::{
     ecs::{query::WorldQuery, system::SystemParam},
-    prelude::{EventWriter, Query, With},
+    prelude::{Commands, Entity, EventWriter, Parent, Query, ResMut, With},
 };
 
 use rose_game_common::components::{
     AbilityValues, BasicStats, CharacterInfo, Equipment, ExperiencePoints, HealthPoints, Inventory,
-    Level, ManaPoints, MoveSpeed, Npc, QuestState, SkillPoints, Stamina, StatPoints, Team,
-    UnionMembership,
+    Level, ManaPoints, MoveSpeed, Npc, QuestState, SkillList, SkillPoints, Stamina, StatPoints,
+    Team, UnionMembership,
 };
 
 use crate::{
-    components::{ClanMembership, ClientEntity, PlayerCharacter},
-    events::{BankEvent, ChatboxEvent, ClanDialogEvent, NpcStoreEvent, SystemFuncEvent},
+    components::{
+        Clan, ClanMembership, ClientEntity, Effect, PartyInfo, PlayerCharacter, Position,
+    },
+    events::{
+        BankEvent, ChatboxEvent, ClanDialogEvent, CraftingEvent, NpcStoreEvent, PlayerCommandEvent,
+        SpawnEffectEvent, SystemFuncEvent,
+    },
+    resources::RepairState,
+    systems::OrbitCamera,
 };
 
 #[derive(WorldQuery)]
@@ -28,12 +35,16 @@ pub struct ScriptCharacterQuery<'w> {
     pub level: &'w Level,
     pub mana_points: &'w mut ManaPoints,
     pub move_speed: &'w MoveSpeed,
+    pub position: &'w Position,
+    pub skill_list: &'w SkillList,
     pub skill_points: &'w SkillPoints,
     pub stamina: &'w Stamina,
     pub stat_points: &'w StatPoints,
     pub team: &'w Team,
     pub union_membership: &'w UnionMembership,
+    pub clan: Option<&'w Clan>,
     pub clan_membership: Option<&'w ClanMembership>,
+    pub party_info: Option<&'w PartyInfo>,
 }
 
 #[derive(SystemParam)]
@@ -42,9 +53,16 @@ pub struct ScriptFunctionContext<'w, 's> {
     pub query_client_entity: Query<'w, 's, &'static ClientEntity>,
     pub query_player: Query<'w, 's, ScriptCharacterQuery<'static>, With<PlayerCharacter>>,
     pub query_npc: Query<'w, 's, &'static Npc>,
+    pub query_orbit_camera: Query<'w, 's, &'static mut OrbitCamera>,
+    pub query_effects: Query<'w, 's, (Entity, &'static Parent), With<Effect>>,
+    pub commands: Commands<'w, 's>,
     pub bank_events: EventWriter<'w, BankEvent>,
     pub chatbox_events: EventWriter<'w, ChatboxEvent>,
     pub clan_dialog_events: EventWriter<'w, ClanDialogEvent>,
+    pub crafting_events: EventWriter<'w, CraftingEvent>,
     pub npc_store_events: EventWriter<'w, NpcStoreEvent>,
+    pub player_command_events: EventWriter<'w, PlayerCommandEvent>,
     pub script_system_events: EventWriter<'w, SystemFuncEvent>,
+    pub spawn_effect_events: EventWriter<'w, SpawnEffectEvent>,
+    pub repair_state: ResMut<'w, RepairState>,
 }
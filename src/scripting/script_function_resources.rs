@@ -1,11 +1,23 @@
-use bevy::{ecs::system::SystemParam, prelude::Res};
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::{AssetServer, Res},
+};
 
-use crate::resources::{GameConnection, GameData, WorldTime};
+use crate::resources::{
+    ClientEntityList, CurrentZone, GameConnection, GameData, SelectedTarget, SoundSettings,
+    WorldRates, WorldTime,
+};
 
 #[derive(SystemParam)]
 pub struct ScriptFunctionResources<'w, 's> {
+    pub asset_server: Res<'w, AssetServer>,
+    pub client_entity_list: Res<'w, ClientEntityList>,
+    pub current_zone: Option<Res<'w, CurrentZone>>,
     pub game_connection: Option<Res<'w, GameConnection>>,
     pub game_data: Res<'w, GameData>,
+    pub selected_target: Res<'w, SelectedTarget>,
+    pub sound_settings: Res<'w, SoundSettings>,
+    pub world_rates: Option<Res<'w, WorldRates>>,
     pub world_time: Res<'w, WorldTime>,
 
     #[system_param(ignore)]
@@ -0,0 +1,62 @@
+use bevy::{
+    prelude::{Entity, EventWriter, Local, Query, Res, With},
+    utils::HashSet,
+};
+
+use rose_game_common::components::ItemDrop;
+
+use crate::{
+    components::{Command, NextCommand, PlayerCharacter, Position},
+    events::PlayerCommandEvent,
+    resources::GameplaySettings,
+};
+
+/// Item drops within this distance of the player are picked up automatically
+const AUTO_PICKUP_DISTANCE: f32 = 500.0;
+
+pub fn auto_pickup_system(
+    mut attempted_item_drops: Local<HashSet<Entity>>,
+    gameplay_settings: Res<GameplaySettings>,
+    query_player: Query<(&Command, &NextCommand, &Position), With<PlayerCharacter>>,
+    query_item_drops: Query<(Entity, &Position), With<ItemDrop>>,
+    mut player_command_events: EventWriter<PlayerCommandEvent>,
+) {
+    // Forget about item drops which no longer exist
+    attempted_item_drops.retain(|entity| query_item_drops.contains(*entity));
+
+    if !gameplay_settings.auto_pickup {
+        return;
+    }
+
+    let Ok((command, next_command, player_position)) = query_player.get_single() else {
+        return;
+    };
+
+    // Only pickup items when idle, so we do not interrupt what the player is doing
+    if !command.is_stop() || next_command.is_some() {
+        return;
+    }
+
+    let nearest_item_drop = query_item_drops
+        .iter()
+        .filter(|(entity, _)| !attempted_item_drops.contains(entity))
+        .map(|(entity, position)| {
+            (
+                entity,
+                position,
+                position
+                    .position
+                    .xy()
+                    .distance(player_position.position.xy()),
+            )
+        })
+        .filter(|(_, _, distance)| *distance < AUTO_PICKUP_DISTANCE)
+        .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b));
+
+    if let Some((entity, position, _)) = nearest_item_drop {
+        // Each item drop is only attempted once, as the pickup may be rejected by the
+        // server e.g. when our inventory is full or the item belongs to someone else
+        attempted_item_drops.insert(entity);
+        player_command_events.send(PlayerCommandEvent::Move(position.clone(), Some(entity)));
+    }
+}
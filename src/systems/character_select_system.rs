@@ -24,8 +24,8 @@ use crate::{
     },
     events::{CharacterSelectEvent, GameConnectionEvent, LoadZoneEvent, WorldConnectionEvent},
     resources::{
-        AppState, CharacterList, CharacterSelectState, GameData, ServerConfiguration,
-        WorldConnection,
+        AppState, CharacterList, CharacterSelectState, ConnectionState, GameData,
+        ServerConfiguration, WorldConnection,
     },
     systems::{FreeCamera, OrbitCamera},
 };
@@ -166,9 +166,11 @@ pub fn character_select_system(
     world_connection: Option<Res<WorldConnection>>,
     mut character_list: Option<ResMut<CharacterList>>,
     server_configuration: Res<ServerConfiguration>,
+    connection_state: Res<ConnectionState>,
     asset_server: Res<AssetServer>,
 ) {
     let character_select_state = &mut *character_select_state;
+    let skip_animations = server_configuration.auto_login || connection_state.is_reconnecting();
     let world_connection = if let Some(world_connection) = world_connection {
         world_connection
     } else {
@@ -249,15 +251,14 @@ pub fn character_select_system(
             WorldConnectionEvent::DeleteCharacterError { name: _ } => {
                 // TODO: Show delete character error message
             }
+            WorldConnectionEvent::ReturnToCharacterSelect => {}
         }
     }
 
     match character_select_state {
         CharacterSelectState::Entering => {
             let (_, _, _, camera_motion) = query_camera.single();
-            if camera_motion.map_or(true, |animation| animation.completed())
-                || server_configuration.auto_login
-            {
+            if camera_motion.map_or(true, |animation| animation.completed()) || skip_animations {
                 *character_select_state = CharacterSelectState::CharacterSelect(None);
             }
         }
@@ -280,7 +281,9 @@ pub fn character_select_system(
                 });
 
             for event in game_connection_events.iter() {
-                let &GameConnectionEvent::Connected(zone_id) = event;
+                let &GameConnectionEvent::Connected(zone_id) = event else {
+                    continue;
+                };
 
                 // Start camera animation
                 let (camera_entity, _, _, _) = query_camera.single();
@@ -294,9 +297,7 @@ pub fn character_select_system(
         }
         CharacterSelectState::Leaving => {
             let (_, _, _, camera_motion) = query_camera.single();
-            if camera_motion.map_or(true, |animation| animation.completed())
-                || server_configuration.auto_login
-            {
+            if camera_motion.map_or(true, |animation| animation.completed()) || skip_animations {
                 // Wait until camera motion complete, then load the zone!
                 *character_select_state = CharacterSelectState::Loading;
                 load_zone_events.send(LoadZoneEvent::new(join_zone_id.take().unwrap()));
@@ -312,6 +313,7 @@ pub fn character_select_event_system(
     mut character_select_events: EventReader<CharacterSelectEvent>,
     character_list: Option<Res<CharacterList>>,
     world_connection: Option<Res<WorldConnection>>,
+    mut connection_state: ResMut<ConnectionState>,
 ) {
     for event in character_select_events.iter() {
         match event {
@@ -342,6 +344,8 @@ pub fn character_select_event_system(
                                         .ok();
                                 }
 
+                                connection_state.character_name =
+                                    Some(selected_character.info.name.clone());
                                 *character_select_state =
                                     CharacterSelectState::ConnectingGameServer;
                             }
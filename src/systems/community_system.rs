@@ -0,0 +1,149 @@
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::{DetectChanges, Local, Query, Res, ResMut, With};
+use serde::{Deserialize, Serialize};
+
+use rose_game_common::components::CharacterInfo;
+
+use crate::{
+    components::PlayerCharacter,
+    resources::{ChatIgnoreList, ConnectionState, FriendList, ServerConfiguration},
+};
+
+/// The friends and block lists, saved separately for each character on each server
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct CommunityFile {
+    friends: Vec<String>,
+    blocked: Vec<String>,
+}
+
+fn sanitise_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+// Character names are only unique within a world server, so the files are
+// kept in a directory for the login server address and world server name.
+fn community_file_path(server: &str, character_name: &str) -> Option<PathBuf> {
+    directories::ProjectDirs::from("", "", "rose-offline-client").map(|project_dirs| {
+        project_dirs
+            .data_dir()
+            .join("characters")
+            .join(sanitise_file_name(server))
+            .join(format!("{}.toml", sanitise_file_name(character_name)))
+    })
+}
+
+fn load_community_file(path: &Path) -> Result<CommunityFile, anyhow::Error> {
+    match std::fs::read_to_string(path) {
+        Ok(toml_str) => Ok(toml::from_str(&toml_str)?),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(CommunityFile::default()),
+        Err(error) => Err(error.into()),
+    }
+}
+
+fn save_community_file(path: &Path, file: &CommunityFile) -> Result<(), anyhow::Error> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, toml::to_string(file)?)?;
+    Ok(())
+}
+
+#[derive(Default)]
+pub struct CommunityState {
+    server: Option<String>,
+    character_name: Option<String>,
+    path: Option<PathBuf>,
+    saved: CommunityFile,
+}
+
+pub fn community_system(
+    mut state: Local<CommunityState>,
+    query_player: Query<&CharacterInfo, With<PlayerCharacter>>,
+    connection_state: Res<ConnectionState>,
+    server_configuration: Res<ServerConfiguration>,
+    mut friend_list: ResMut<FriendList>,
+    mut chat_ignore_list: ResMut<ChatIgnoreList>,
+) {
+    let Ok(character_info) = query_player.get_single() else {
+        return;
+    };
+
+    let server = format!(
+        "{}_{}_{}",
+        server_configuration.ip,
+        server_configuration.port,
+        connection_state
+            .server_name
+            .clone()
+            .unwrap_or_else(|| connection_state
+                .server_id
+                .map_or_else(|| "unknown".to_string(), |id| id.to_string()))
+    );
+
+    if state.server.as_ref() != Some(&server)
+        || state.character_name.as_deref() != Some(character_info.name.as_str())
+    {
+        let path = community_file_path(&server, &character_info.name);
+        let file = match path.as_deref().map(load_community_file) {
+            Some(Ok(file)) => file,
+            Some(Err(error)) => {
+                log::error!(
+                    "Failed to load friends list for {} with error: {}",
+                    character_info.name,
+                    error
+                );
+                CommunityFile::default()
+            }
+            None => CommunityFile::default(),
+        };
+
+        *friend_list = FriendList::default();
+        for name in file.friends.iter() {
+            friend_list.add(name);
+        }
+
+        *chat_ignore_list = ChatIgnoreList::default();
+        for name in file.blocked.iter() {
+            chat_ignore_list.add(name);
+        }
+
+        state.server = Some(server);
+        state.character_name = Some(character_info.name.clone());
+        state.path = path;
+        state.saved = file;
+        return;
+    }
+
+    if !friend_list.is_changed() && !chat_ignore_list.is_changed() {
+        return;
+    }
+
+    let file = CommunityFile {
+        friends: friend_list.iter().map(String::from).collect(),
+        blocked: chat_ignore_list.iter().map(String::from).collect(),
+    };
+    if file == state.saved {
+        return;
+    }
+
+    if let Some(path) = state.path.as_ref() {
+        if let Err(error) = save_community_file(path, &file) {
+            log::error!(
+                "Failed to save friends list to {} with error: {}",
+                path.to_string_lossy(),
+                error
+            );
+        }
+    }
+    state.saved = file;
+}
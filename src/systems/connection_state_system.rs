@@ -0,0 +1,184 @@
+use std::time::Instant;
+
+use bevy::prelude::{Commands, EventWriter, NextState, Res, ResMut, State, World};
+
+use crate::{
+    events::{CharacterSelectEvent, LoginEvent, MessageBoxEvent},
+    resources::{
+        AppState, CharacterList, CharacterSelectState, ConnectionState, ConnectionType,
+        ReconnectAttempt, ReconnectSettings, ReconnectStage, ServerList, WorldConnection,
+    },
+};
+
+fn connection_type_name(connection_type: ConnectionType) -> &'static str {
+    match connection_type {
+        ConnectionType::Login => "login",
+        ConnectionType::World => "world",
+        ConnectionType::Game => "game",
+    }
+}
+
+fn cancel_reconnect(commands: &mut Commands) {
+    commands.add(|world: &mut World| {
+        world.resource_mut::<ConnectionState>().cancel_reconnect();
+    });
+}
+
+pub fn connection_state_system(
+    mut commands: Commands,
+    mut connection_state: ResMut<ConnectionState>,
+    reconnect_settings: Res<ReconnectSettings>,
+    app_state_current: Res<State<AppState>>,
+    mut app_state_next: ResMut<NextState<AppState>>,
+    character_list: Option<Res<CharacterList>>,
+    character_select_state: Option<Res<CharacterSelectState>>,
+    server_list: Option<Res<ServerList>>,
+    mut character_select_events: EventWriter<CharacterSelectEvent>,
+    mut login_events: EventWriter<LoginEvent>,
+    mut message_box_events: EventWriter<MessageBoxEvent>,
+) {
+    let connection_state = &mut *connection_state;
+    let now = Instant::now();
+
+    if let Some((connection_type, error)) = connection_state.lost_connection.take() {
+        let in_game = matches!(app_state_current.get(), AppState::Game);
+
+        if let Some(reconnect) = connection_state.reconnect.as_mut() {
+            // A reconnect attempt failed, try again after a delay
+            reconnect.attempt += 1;
+
+            if reconnect.attempt >= reconnect_settings.max_attempts {
+                connection_state.reconnect = None;
+                message_box_events.send(MessageBoxEvent::Show {
+                    message: format!(
+                        "Unable to reconnect to the server after {} attempts.",
+                        reconnect_settings.max_attempts
+                    ),
+                    modal: true,
+                    ok: None,
+                    cancel: None,
+                });
+            } else {
+                reconnect.stage = ReconnectStage::Waiting;
+                reconnect.next_attempt_time = now + reconnect_settings.delay(reconnect.attempt);
+                log::info!(
+                    "Reconnect attempt {} failed with error: {}",
+                    reconnect.attempt,
+                    error
+                );
+            }
+
+            if !matches!(app_state_current.get(), AppState::GameLogin) {
+                app_state_next.set(AppState::GameLogin);
+            }
+        } else if in_game && connection_type == ConnectionType::Game {
+            commands.remove_resource::<WorldConnection>();
+            commands.remove_resource::<ServerList>();
+            app_state_next.set(AppState::GameLogin);
+
+            if reconnect_settings.enabled && connection_state.can_reconnect() {
+                connection_state.reconnect = Some(ReconnectAttempt {
+                    attempt: 0,
+                    next_attempt_time: now + reconnect_settings.delay(0),
+                    stage: ReconnectStage::Waiting,
+                });
+
+                message_box_events.send(MessageBoxEvent::Show {
+                    message: format!(
+                        "Lost connection to the game server: {}\nReconnecting...",
+                        error
+                    ),
+                    modal: false,
+                    ok: None,
+                    cancel: Some(Box::new(cancel_reconnect)),
+                });
+            } else {
+                message_box_events.send(MessageBoxEvent::Show {
+                    message: format!("Lost connection to the game server: {}", error),
+                    modal: true,
+                    ok: None,
+                    cancel: None,
+                });
+            }
+        } else {
+            message_box_events.send(MessageBoxEvent::Show {
+                message: format!(
+                    "Disconnected from the {} server: {}",
+                    connection_type_name(connection_type),
+                    error
+                ),
+                modal: !in_game,
+                ok: None,
+                cancel: None,
+            });
+        }
+    }
+
+    let Some(reconnect) = connection_state.reconnect.as_mut() else {
+        return;
+    };
+
+    match reconnect.stage {
+        ReconnectStage::Waiting => {
+            if now >= reconnect.next_attempt_time
+                && matches!(app_state_current.get(), AppState::GameLogin)
+            {
+                if let (Some(username), Some(password)) = (
+                    connection_state.username.clone(),
+                    connection_state.password.clone(),
+                ) {
+                    log::info!("Reconnecting to server, attempt {}", reconnect.attempt + 1);
+                    login_events.send(LoginEvent::Login { username, password });
+                    reconnect.stage = ReconnectStage::WaitServerList;
+                }
+            }
+        }
+        ReconnectStage::WaitServerList => {
+            if server_list.is_some() {
+                if let (Some(server_id), Some(channel_id)) =
+                    (connection_state.server_id, connection_state.channel_id)
+                {
+                    login_events.send(LoginEvent::SelectServer {
+                        server_id,
+                        channel_id,
+                    });
+                    reconnect.stage = ReconnectStage::WaitCharacterList;
+                }
+            }
+        }
+        ReconnectStage::WaitCharacterList => {
+            if !matches!(
+                character_select_state.as_deref(),
+                Some(CharacterSelectState::CharacterSelect(_))
+            ) {
+                return;
+            }
+
+            let Some(character_list) = character_list.as_ref() else {
+                return;
+            };
+
+            if let Some(index) = character_list.characters.iter().position(|character| {
+                Some(&character.info.name) == connection_state.character_name.as_ref()
+            }) {
+                character_select_events.send(CharacterSelectEvent::SelectCharacter(index));
+                character_select_events.send(CharacterSelectEvent::PlaySelected);
+                reconnect.stage = ReconnectStage::SelectedCharacter;
+            } else {
+                connection_state.reconnect = None;
+                message_box_events.send(MessageBoxEvent::Show {
+                    message: "Unable to reconnect, the character no longer exists.".into(),
+                    modal: true,
+                    ok: None,
+                    cancel: None,
+                });
+            }
+        }
+        ReconnectStage::SelectedCharacter => {
+            if matches!(app_state_current.get(), AppState::Game) {
+                log::info!("Reconnected to server");
+                connection_state.reconnect = None;
+            }
+        }
+    }
+}
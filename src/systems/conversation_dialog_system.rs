@@ -1,8 +1,8 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use bevy::{
     math::Vec3Swizzles,
-    prelude::{Assets, Entity, EventReader, Local, Query, Res, With},
+    prelude::{Assets, Entity, EventReader, Local, Query, Res, ResMut, With},
 };
 use bevy_egui::{egui, EguiContexts};
 use rose_file_readers::{ConFile, ConMessageType};
@@ -10,9 +10,9 @@ use rose_file_readers::{ConFile, ConMessageType};
 use crate::{
     components::{ClientEntityName, PlayerCharacter, Position},
     events::ConversationDialogEvent,
-    resources::{GameData, UiResources, UiSprite},
+    resources::{ConversationTrace, GameData, UiResources, UiSprite},
     scripting::{
-        lua4::{Lua4Function, Lua4VM, Lua4VMError, Lua4VMRustClosures, Lua4Value},
+        lua4::{Lua4Function, Lua4VM, Lua4VMError, Lua4VMRustClosures, Lua4VMTrace, Lua4Value},
         LuaGameConstants, LuaGameFunctions, LuaQuestFunctions, LuaUserValueEntity,
         ScriptFunctionContext, ScriptFunctionResources,
     },
@@ -67,6 +67,9 @@ impl<'a, 'w1, 's1, 'w2, 's2> Lua4VMRustClosures for LuaVMContext<'a, 'w1, 's1, '
                 self.function_context,
                 parameters,
             ))
+        } else if self.game_functions.is_stub(name) {
+            log::warn!(target: "lua", "Unimplemented game function {}{:?}", name, parameters);
+            Ok(vec![Lua4Value::Nil])
         } else {
             Err(Lua4VMError::GlobalNotFound(name.to_string()))
         }
@@ -77,8 +80,10 @@ fn create_conversation_dialog(
     con_file: ConFile,
     user_context: &mut LuaVMContext,
     owner_entity: Option<Entity>,
+    trace: Option<Arc<Mutex<Lua4VMTrace>>>,
 ) -> Option<ConversationDialogState> {
     let mut lua_vm = Lua4VM::new();
+    lua_vm.trace = trace;
 
     for (name, value) in user_context.game_constants.constants.iter() {
         lua_vm.set_global(name.clone(), value.clone());
@@ -88,6 +93,10 @@ fn create_conversation_dialog(
         lua_vm.set_global(name.clone(), Lua4Value::RustClosure(name.clone()));
     }
 
+    for name in user_context.game_functions.stubs.iter() {
+        lua_vm.set_global(name.clone(), Lua4Value::RustClosure(name.clone()));
+    }
+
     for (name, _) in user_context.quest_functions.closures.iter() {
         lua_vm.set_global(name.clone(), Lua4Value::RustClosure(name.clone()));
     }
@@ -370,8 +379,7 @@ pub fn conversation_dialog_system(
     mut ui_state: Local<UiConversationDialogState>,
     script_function_resources: ScriptFunctionResources,
     query_player_position: Query<&Position, With<PlayerCharacter>>,
-    query_position: Query<&Position>,
-    query_name: Query<&ClientEntityName>,
+    query_owner: Query<(Option<&Position>, Option<&ClientEntityName>)>,
     lua_game_constants: Res<LuaGameConstants>,
     lua_game_functions: Res<LuaGameFunctions>,
     lua_quest_functions: Res<LuaQuestFunctions>,
@@ -379,6 +387,7 @@ pub fn conversation_dialog_system(
     vfs_resource: Res<VfsResource>,
     ui_resources: Res<UiResources>,
     dialog_assets: Res<Assets<Dialog>>,
+    mut conversation_trace: ResMut<ConversationTrace>,
 ) {
     let ui_state = &mut *ui_state;
     let dialog = if let Some(dialog) = ui_state
@@ -429,9 +438,16 @@ pub fn conversation_dialog_system(
             .read_file::<ConFile, _>(con_file_path)
             .ok()
             .and_then(|con_file| {
-                create_conversation_dialog(con_file, &mut user_context, owner_entity)
+                let trace = conversation_trace
+                    .enabled
+                    .then(|| conversation_trace.begin(con_file_path, &con_file, &game_data));
+                create_conversation_dialog(con_file, &mut user_context, owner_entity, trace)
             })
         {
+            if conversation_trace.enabled {
+                conversation_trace.set_functions(&next_dialog_state.lua_vm);
+            }
+
             let check_open_function =
                 &next_dialog_state.con_file.initial_messages[0].condition_function;
 
@@ -489,7 +505,8 @@ pub fn conversation_dialog_system(
             query_player_position.get_single(),
             dialog_state
                 .owner_entity
-                .and_then(|entity| query_position.get(entity).ok()),
+                .and_then(|entity| query_owner.get(entity).ok())
+                .and_then(|(position, _)| position),
         ) {
             if npc_position.position.xy().distance(player_position.xy()) > 400.0 {
                 *current_dialog_state = None;
@@ -531,7 +548,8 @@ pub fn conversation_dialog_system(
 
         let title = dialog_state
             .owner_entity
-            .and_then(|entity| query_name.get(entity).ok())
+            .and_then(|entity| query_owner.get(entity).ok())
+            .and_then(|(_, name)| name)
             .map(|name| name.as_str())
             .unwrap_or("Event Dialog");
 
@@ -13,7 +13,7 @@ use rose_game_common::{components::*, messages::ClientEntityId};
 use crate::{
     components::*,
     render::{ObjectMaterialBlend, ObjectMaterialGlow},
-    resources::DebugInspector,
+    resources::{DebugInspector, KeyAction, KeyBindings},
 };
 
 pub struct DebugInspectorPlugin;
@@ -98,6 +98,7 @@ fn debug_inspector_picking_system(
     mut debug_inspector_state: ResMut<DebugInspector>,
     mut egui_ctx: EguiContexts,
     key_code_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
     rapier_context: Res<RapierContext>,
     query_window: Query<&Window, With<PrimaryWindow>>,
     query_camera: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
@@ -118,7 +119,7 @@ fn debug_inspector_picking_system(
     }
     let cursor_position = cursor_position.unwrap();
 
-    if key_code_input.just_pressed(KeyCode::P) {
+    if key_bindings.just_pressed(KeyAction::DebugInspectorPick, &key_code_input) {
         for (camera, camera_transform) in query_camera.iter() {
             if let Some(ray) = camera.viewport_to_world(camera_transform, cursor_position) {
                 if let Some((collider_entity, _distance)) = rapier_context.cast_ray(
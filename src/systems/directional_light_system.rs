@@ -3,9 +3,8 @@ use bevy::{
     prelude::{Camera, DirectionalLight, Entity, GlobalTransform, Mat4, Query, Res, Vec3, With},
 };
 
-use crate::components::PlayerCharacter;
+use crate::{components::PlayerCharacter, resources::RenderConfiguration};
 
-const PROJECTION_HALF_SIZE: f32 = 40.0;
 const PROJECTION_HALF_DEPTH: f32 = 100.0;
 
 pub fn directional_light_system(
@@ -13,7 +12,9 @@ pub fn directional_light_system(
     mut query_light: Query<(&GlobalTransform, &mut Cascades), With<DirectionalLight>>,
     views: Query<(Entity, &GlobalTransform), With<Camera>>,
     shadow_map: Res<DirectionalLightShadowMap>,
+    render_configuration: Res<RenderConfiguration>,
 ) {
+    let projection_half_size = render_configuration.shadow_distance;
     let lookat_position = if let Ok(player_transform) = query_player.get_single() {
         player_transform.translation()
     } else if let Ok((_, camera_transform)) = views.get_single() {
@@ -28,10 +29,10 @@ pub fn directional_light_system(
         let projected = view.mul_vec4(lookat_position.extend(1.0));
 
         let projection = Mat4::orthographic_rh(
-            projected.x - PROJECTION_HALF_SIZE,
-            projected.x + PROJECTION_HALF_SIZE,
-            projected.y + PROJECTION_HALF_SIZE,
-            projected.y - PROJECTION_HALF_SIZE,
+            projected.x - projection_half_size,
+            projected.x + projection_half_size,
+            projected.y + projection_half_size,
+            projected.y - projection_half_size,
             -projected.z + PROJECTION_HALF_DEPTH,
             -projected.z - PROJECTION_HALF_DEPTH,
         );
@@ -47,7 +48,7 @@ pub fn directional_light_system(
                     view_transform,
                     projection,
                     view_projection,
-                    texel_size: (PROJECTION_HALF_SIZE * 2.0) / (shadow_map.size as f32),
+                    texel_size: (projection_half_size * 2.0) / (shadow_map.size as f32),
                 }],
             );
         }
@@ -12,6 +12,8 @@ use bevy::{
 use bevy_egui::EguiContexts;
 use dolly::prelude::{CameraRig, LeftHanded, Position, Smooth, YawPitch};
 
+use crate::resources::{KeyAction, KeyBindings};
+
 #[derive(Component)]
 pub struct FreeCamera {
     pub rig: CameraRig<LeftHanded>,
@@ -48,6 +50,7 @@ pub fn free_camera_system(
     mut mouse_motion_events: EventReader<MouseMotion>,
     mut mouse_wheel_reader: EventReader<MouseWheel>,
     keyboard: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
     mouse_buttons: Res<Input<MouseButton>>,
     mut query_window: Query<&mut Window, With<PrimaryWindow>>,
     mut egui_ctx: EguiContexts,
@@ -101,17 +104,28 @@ pub fn free_camera_system(
     let mut move_vec = Vec3::ZERO;
     let mut speed_boost_multiplier = 1.0f32;
     if allow_keyboard_input {
-        for key in keyboard.get_pressed() {
-            match key {
-                KeyCode::W => move_vec.z -= 1.0,      // Forward
-                KeyCode::S => move_vec.z += 1.0,      // Backward
-                KeyCode::A => move_vec.x -= 1.0,      // Left
-                KeyCode::D => move_vec.x += 1.0,      // Right
-                KeyCode::Q => translate_vec.y -= 1.0, // Down
-                KeyCode::E => translate_vec.y += 1.0, // Up
-                KeyCode::ShiftLeft => speed_boost_multiplier = 4.0,
-                _ => {}
-            }
+        let pressed =
+            |action| key_bindings.pressed_with(action, KeyAction::FreeCameraSpeedBoost, &keyboard);
+        if pressed(KeyAction::FreeCameraForward) {
+            move_vec.z -= 1.0;
+        }
+        if pressed(KeyAction::FreeCameraBackward) {
+            move_vec.z += 1.0;
+        }
+        if pressed(KeyAction::FreeCameraLeft) {
+            move_vec.x -= 1.0;
+        }
+        if pressed(KeyAction::FreeCameraRight) {
+            move_vec.x += 1.0;
+        }
+        if pressed(KeyAction::FreeCameraDown) {
+            translate_vec.y -= 1.0;
+        }
+        if pressed(KeyAction::FreeCameraUp) {
+            translate_vec.y += 1.0;
+        }
+        if pressed(KeyAction::FreeCameraSpeedBoost) {
+            speed_boost_multiplier = 4.0;
         }
     }
 
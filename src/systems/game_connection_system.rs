@@ -43,7 +43,10 @@ use crate::{
         BankEvent, ChatboxEvent, ClientEntityEvent, GameConnectionEvent, LoadZoneEvent,
         MessageBoxEvent, PartyEvent, PersonalStoreEvent, QuestTriggerEvent, UseItemEvent,
     },
-    resources::{AppState, ClientEntityList, GameConnection, GameData, WorldRates, WorldTime},
+    resources::{
+        AppState, ClientEntityList, ConnectionState, ConnectionType, GameConnection, GameData,
+        WorldRates, WorldTime,
+    },
 };
 
 fn to_next_command(
@@ -128,6 +131,7 @@ pub fn game_connection_system(
     app_state_current: Res<State<AppState>>,
     mut app_state_next: ResMut<NextState<AppState>>,
     mut client_entity_list: ResMut<ClientEntityList>,
+    mut connection_state: ResMut<ConnectionState>,
     mut chatbox_events: EventWriter<ChatboxEvent>,
     mut game_connection_events: EventWriter<GameConnectionEvent>,
     mut load_zone_events: EventWriter<LoadZoneEvent>,
@@ -2296,23 +2300,82 @@ pub fn game_connection_system(
                     });
                 }
             }
-            Ok(ServerMessage::CraftInsertGem { .. }) => {
-                log::warn!("Received unimplemented ServerMessage::CraftInsertGem");
+            Ok(ServerMessage::CraftInsertGem { update_items }) => {
+                if let Some(player_entity) = client_entity_list.player_entity {
+                    chatbox_events.send(ChatboxEvent::System(
+                        "The gem was successfully socketed.".to_string(),
+                    ));
+
+                    commands.add(move |world: &mut World| {
+                        update_inventory_and_money(world, player_entity, update_items, None);
+                    });
+                }
             }
-            Ok(ServerMessage::CraftInsertGemError { .. }) => {
-                log::warn!("Received unimplemented ServerMessage::CraftInsertGemError");
+            Ok(ServerMessage::CraftInsertGemError { error }) => {
+                message_box_events.send(MessageBoxEvent::Show {
+                    message: format!("Failed to socket the gem: {:?}", error),
+                    modal: false,
+                    ok: None,
+                    cancel: None,
+                });
             }
-            Ok(ServerMessage::RepairedItemUsingNpc { .. }) => {
-                log::warn!("Received unimplemented ServerMessage::RepairedItemUsingNpc");
+            Ok(ServerMessage::RepairedItemUsingNpc {
+                item_slot,
+                item,
+                updated_money,
+            }) => {
+                if let Some(player_entity) = client_entity_list.player_entity {
+                    let item_name = game_data
+                        .items
+                        .get_base_item(item.get_item_reference())
+                        .map(|item_data| item_data.name.to_string());
+
+                    commands.add(move |world: &mut World| {
+                        // The server only tells us our updated money, so the
+                        // repair cost is the difference from our current money
+                        let previous_money = world
+                            .get::<Inventory>(player_entity)
+                            .map(|inventory| inventory.money);
+
+                        update_inventory_and_money(
+                            world,
+                            player_entity,
+                            vec![(item_slot, Some(item))],
+                            Some(updated_money),
+                        );
+
+                        if let Some(item_name) = item_name {
+                            world.resource_mut::<Events<ChatboxEvent>>().send(
+                                ChatboxEvent::System(match previous_money {
+                                    Some(previous_money) if previous_money.0 > updated_money.0 => {
+                                        format!(
+                                            "{} has been repaired for {} Zuly.",
+                                            item_name,
+                                            previous_money.0 - updated_money.0
+                                        )
+                                    }
+                                    _ => format!("{} has been repaired.", item_name),
+                                }),
+                            );
+                        }
+                    });
+                }
             }
             Ok(ServerMessage::LogoutSuccess) => {
-                log::warn!("Received unimplemented ServerMessage::LogoutSuccess");
+                game_connection_events.send(GameConnectionEvent::LogoutSuccess);
+
+                // The server will now close the connection, so we stop reading
+                // messages to avoid treating that as a lost connection
+                commands.remove_resource::<GameConnection>();
+                break Ok(());
             }
-            Ok(ServerMessage::LogoutFailed { .. }) => {
-                log::warn!("Received unimplemented ServerMessage::LogoutFailed");
+            Ok(ServerMessage::LogoutFailed { wait_duration }) => {
+                game_connection_events.send(GameConnectionEvent::LogoutFailed { wait_duration });
             }
             Ok(ServerMessage::ReturnToCharacterSelect) => {
-                log::warn!("Received unimplemented ServerMessage::ReturnToCharacterSelect");
+                game_connection_events.send(GameConnectionEvent::ReturnToCharacterSelect);
+                commands.remove_resource::<GameConnection>();
+                break Ok(());
             }
             Ok(ServerMessage::LoginError { .. }) |
             Ok(ServerMessage::LoginSuccess { .. }) |
@@ -2340,8 +2403,8 @@ pub fn game_connection_system(
     };
 
     if let Err(error) = result {
-        // TODO: Store error somewhere to display to user
         log::warn!("Game server connection error: {}", error);
+        connection_state.connection_lost(ConnectionType::Game, error.to_string());
         commands.remove_resource::<GameConnection>();
     }
 }
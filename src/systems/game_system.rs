@@ -1,14 +1,16 @@
 use bevy::{
     math::Vec3,
-    prelude::{Camera3d, Commands, Entity, EventReader, Query, Res, With},
+    prelude::{
+        Camera3d, Commands, DespawnRecursiveExt, Entity, EventReader, Or, Query, Res, ResMut, With,
+    },
 };
 use rose_game_common::messages::client::ClientMessage;
 
 use crate::{
     animation::CameraAnimation,
-    components::PlayerCharacter,
+    components::{ClientEntity, PlayerCharacter},
     events::ZoneEvent,
-    resources::GameConnection,
+    resources::{ClientEntityList, GameConnection, LogoutState},
     systems::{FreeCamera, OrbitCamera},
 };
 
@@ -32,6 +34,26 @@ pub fn game_state_enter_system(
     }
 }
 
+pub fn game_state_exit_system(
+    mut commands: Commands,
+    mut client_entity_list: ResMut<ClientEntityList>,
+    mut logout_state: ResMut<LogoutState>,
+    query_client_entities: Query<Entity, Or<(With<ClientEntity>, With<PlayerCharacter>)>>,
+) {
+    // Despawn all entities which belong to the game server
+    for entity in query_client_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    client_entity_list.clear();
+    client_entity_list.player_entity = None;
+    client_entity_list.player_entity_id = None;
+    client_entity_list.zone_id = None;
+
+    logout_state.cancel();
+    commands.remove_resource::<GameConnection>();
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn game_zone_change_system(
     mut zone_events: EventReader<ZoneEvent>,
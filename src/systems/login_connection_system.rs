@@ -9,7 +9,8 @@ use rose_network_common::ConnectionError;
 use crate::{
     events::NetworkEvent,
     resources::{
-        Account, LoginConnection, ServerList, ServerListGameServer, ServerListWorldServer,
+        Account, ConnectionState, ConnectionType, LoginConnection, ServerList,
+        ServerListGameServer, ServerListWorldServer,
     },
 };
 
@@ -18,6 +19,7 @@ pub fn login_connection_system(
     account: Option<Res<Account>>,
     login_connection: Option<Res<LoginConnection>>,
     mut server_list: Option<ResMut<ServerList>>,
+    mut connection_state: ResMut<ConnectionState>,
     mut network_events: EventWriter<NetworkEvent>,
 ) {
     if login_connection.is_none() {
@@ -118,8 +120,8 @@ pub fn login_connection_system(
     };
 
     if let Err(error) = result {
-        // TODO: Store error somewhere to display to user
         log::warn!("Login server connection error: {}", error);
+        connection_state.connection_lost(ConnectionType::Login, error.to_string());
         commands.remove_resource::<LoginConnection>();
     }
 }
@@ -12,7 +12,9 @@ use rose_game_common::messages::client::ClientMessage;
 use crate::{
     animation::CameraAnimation,
     events::{LoadZoneEvent, LoginEvent, NetworkEvent},
-    resources::{Account, LoginConnection, LoginState, ServerConfiguration, ServerList},
+    resources::{
+        Account, ConnectionState, LoginConnection, LoginState, ServerConfiguration, ServerList,
+    },
     systems::{FreeCamera, OrbitCamera},
 };
 
@@ -50,6 +52,7 @@ pub fn login_state_enter_system(
 pub fn login_state_exit_system(mut commands: Commands) {
     commands.remove_resource::<LoginConnection>();
     commands.remove_resource::<LoginState>();
+    commands.remove_resource::<ServerList>();
 }
 
 pub fn login_system(
@@ -95,7 +98,9 @@ pub fn login_event_system(
     mut login_events: EventReader<LoginEvent>,
     login_connection: Option<Res<LoginConnection>>,
     server_configuration: Res<ServerConfiguration>,
+    mut connection_state: ResMut<ConnectionState>,
     mut network_events: EventWriter<NetworkEvent>,
+    server_list: Option<Res<ServerList>>,
 ) {
     for event in login_events.iter() {
         match event {
@@ -103,6 +108,8 @@ pub fn login_event_system(
                 if matches!(*login_state, LoginState::Input) {
                     *login_state = LoginState::WaitServerList;
 
+                    connection_state.username = Some(username.clone());
+                    connection_state.password = Some(password.clone());
                     commands.insert_resource(Account {
                         username: username.clone(),
                         password: password.clone(),
@@ -127,6 +134,25 @@ pub fn login_event_system(
                         })
                         .ok();
                 }
+                connection_state.server_id = Some(server_id);
+                connection_state.channel_id = Some(channel_id);
+
+                let world_server = server_list.as_ref().and_then(|server_list| {
+                    server_list
+                        .world_servers
+                        .iter()
+                        .find(|world_server| world_server.id == server_id)
+                });
+                connection_state.server_name =
+                    world_server.map(|world_server| world_server.name.clone());
+                connection_state.channel_name = world_server
+                    .and_then(|world_server| {
+                        world_server
+                            .game_servers
+                            .iter()
+                            .find(|game_server| game_server.id == channel_id)
+                    })
+                    .map(|game_server| game_server.name.clone());
                 *login_state = LoginState::JoiningServer;
             }
         }
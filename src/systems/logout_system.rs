@@ -0,0 +1,120 @@
+use std::time::Instant;
+
+use bevy::{
+    app::AppExit,
+    prelude::{Commands, EventReader, EventWriter, NextState, Res, ResMut},
+};
+
+use rose_data::ZoneId;
+use rose_game_common::messages::client::{ClientMessage, LogoutRequest};
+
+use crate::{
+    events::{GameConnectionEvent, LoadZoneEvent, WorldConnectionEvent},
+    resources::{AppState, GameConnection, LogoutState, LogoutType, WorldConnection},
+    ui::UiStateWindows,
+};
+
+#[allow(clippy::too_many_arguments)]
+pub fn logout_system(
+    mut commands: Commands,
+    mut logout_state: ResMut<LogoutState>,
+    mut ui_state_windows: ResMut<UiStateWindows>,
+    game_connection: Option<Res<GameConnection>>,
+    world_connection: Option<Res<WorldConnection>>,
+    mut app_state_next: ResMut<NextState<AppState>>,
+    mut game_connection_events: EventReader<GameConnectionEvent>,
+    mut world_connection_events: EventReader<WorldConnectionEvent>,
+    mut app_exit_events: EventWriter<AppExit>,
+    mut load_zone_events: EventWriter<LoadZoneEvent>,
+) {
+    let mut completed_logout = None;
+
+    for event in game_connection_events.iter() {
+        match *event {
+            GameConnectionEvent::LogoutSuccess => {
+                // If we did not request the logout, the best we can do is go
+                // back to character select
+                completed_logout = Some(
+                    logout_state
+                        .logout_type
+                        .unwrap_or(LogoutType::CharacterSelect),
+                );
+            }
+            GameConnectionEvent::LogoutFailed { wait_duration } => {
+                // The server wants us to wait before we can logout, request
+                // again once the wait is over
+                logout_state.request_sent = false;
+                logout_state.wait_until = Some(Instant::now() + wait_duration);
+            }
+            GameConnectionEvent::ReturnToCharacterSelect => {
+                completed_logout = Some(LogoutType::CharacterSelect);
+            }
+            GameConnectionEvent::Connected(_) => {}
+        }
+    }
+
+    for event in world_connection_events.iter() {
+        if let WorldConnectionEvent::ReturnToCharacterSelect = event {
+            completed_logout = Some(LogoutType::CharacterSelect);
+        }
+    }
+
+    if completed_logout.is_none() && !logout_state.request_sent {
+        if let Some(logout_type) = logout_state.logout_type {
+            if logout_state
+                .remaining_wait()
+                .map_or(true, |remaining| remaining.is_zero())
+            {
+                if let Some(game_connection) = game_connection.as_ref() {
+                    game_connection
+                        .client_message_tx
+                        .send(ClientMessage::LogoutRequest(match logout_type {
+                            LogoutType::CharacterSelect => LogoutRequest::ReturnToCharacterSelect,
+                            LogoutType::Exit => LogoutRequest::Logout,
+                        }))
+                        .ok();
+                    logout_state.request_sent = true;
+                    logout_state.wait_until = None;
+                } else {
+                    // Without a game connection there is nothing to wait for
+                    completed_logout = Some(logout_type);
+                }
+            }
+        }
+    }
+
+    let Some(logout_type) = completed_logout else {
+        return;
+    };
+
+    // The game and world servers can each report the logout in different
+    // frames, once the first has been handled there is no logout in progress
+    // and no game connection left, so ignore any later completion.
+    if !logout_state.is_pending() && game_connection.is_none() {
+        return;
+    }
+
+    logout_state.cancel();
+    ui_state_windows.exit_open = false;
+    commands.remove_resource::<GameConnection>();
+
+    match logout_type {
+        LogoutType::Exit => {
+            app_exit_events.send(AppExit);
+        }
+        LogoutType::CharacterSelect => {
+            if let Some(world_connection) = world_connection.as_ref() {
+                // Replace the game zone with the character select zone
+                load_zone_events.send(LoadZoneEvent::new(ZoneId::new(4).unwrap()));
+
+                // We will move to character select once the character list arrives
+                world_connection
+                    .client_message_tx
+                    .send(ClientMessage::GetCharacterList)
+                    .ok();
+            } else {
+                app_state_next.set(AppState::GameLogin);
+            }
+        }
+    }
+}
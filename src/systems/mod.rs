@@ -2,6 +2,7 @@ mod ability_values_system;
 mod animation_effect_system;
 mod animation_sound_system;
 mod auto_login_system;
+mod auto_pickup_system;
 mod background_music_system;
 mod character_model_add_collider_system;
 mod character_model_blink_system;
@@ -11,6 +12,8 @@ mod clan_system;
 mod client_entity_event_system;
 mod collision_system;
 mod command_system;
+mod community_system;
+mod connection_state_system;
 mod conversation_dialog_system;
 mod cooldown_system;
 mod damage_digit_render_system;
@@ -29,12 +32,14 @@ mod hit_event_system;
 mod item_drop_model_system;
 mod login_connection_system;
 mod login_system;
+mod logout_system;
 mod model_viewer_system;
 mod move_destination_effect_system;
 mod name_tag_system;
 mod name_tag_update_color_system;
 mod name_tag_update_healthbar_system;
 mod name_tag_visibility_system;
+mod network_diagnostics_system;
 mod network_thread_system;
 mod npc_idle_sound_system;
 mod npc_model_add_collider_system;
@@ -49,6 +54,7 @@ mod personal_store_model_system;
 mod player_command_system;
 mod projectile_system;
 mod quest_trigger_system;
+mod settings_save_system;
 mod spawn_effect_system;
 mod spawn_projectile_system;
 mod status_effect_system;
@@ -67,6 +73,7 @@ pub use ability_values_system::ability_values_system;
 pub use animation_effect_system::animation_effect_system;
 pub use animation_sound_system::animation_sound_system;
 pub use auto_login_system::auto_login_system;
+pub use auto_pickup_system::auto_pickup_system;
 pub use background_music_system::background_music_system;
 pub use character_model_add_collider_system::character_model_add_collider_system;
 pub use character_model_blink_system::character_model_blink_system;
@@ -81,6 +88,8 @@ pub use collision_system::{
     collision_height_only_system, collision_player_system, collision_player_system_join_zoin,
 };
 pub use command_system::command_system;
+pub use community_system::community_system;
+pub use connection_state_system::connection_state_system;
 pub use conversation_dialog_system::conversation_dialog_system;
 pub use cooldown_system::cooldown_system;
 pub use damage_digit_render_system::damage_digit_render_system;
@@ -94,13 +103,14 @@ pub use facing_direction_system::facing_direction_system;
 pub use free_camera_system::{free_camera_system, FreeCamera};
 pub use game_connection_system::game_connection_system;
 pub use game_mouse_input_system::game_mouse_input_system;
-pub use game_system::{game_state_enter_system, game_zone_change_system};
+pub use game_system::{game_state_enter_system, game_state_exit_system, game_zone_change_system};
 pub use hit_event_system::hit_event_system;
 pub use item_drop_model_system::{item_drop_model_add_collider_system, item_drop_model_system};
 pub use login_connection_system::login_connection_system;
 pub use login_system::{
     login_event_system, login_state_enter_system, login_state_exit_system, login_system,
 };
+pub use logout_system::logout_system;
 pub use model_viewer_system::{
     model_viewer_enter_system, model_viewer_exit_system, model_viewer_system,
 };
@@ -109,6 +119,7 @@ pub use name_tag_system::name_tag_system;
 pub use name_tag_update_color_system::name_tag_update_color_system;
 pub use name_tag_update_healthbar_system::name_tag_update_healthbar_system;
 pub use name_tag_visibility_system::name_tag_visibility_system;
+pub use network_diagnostics_system::{network_diagnostics_system, NetworkDiagnosticsPlugin};
 pub use network_thread_system::network_thread_system;
 pub use npc_idle_sound_system::npc_idle_sound_system;
 pub use npc_model_add_collider_system::npc_model_add_collider_system;
@@ -120,9 +131,10 @@ pub use pending_damage_system::pending_damage_system;
 pub use pending_skill_effect_system::pending_skill_effect_system;
 pub use personal_store_model_add_collider_system::personal_store_model_add_collider_system;
 pub use personal_store_model_system::personal_store_model_system;
-pub use player_command_system::player_command_system;
+pub use player_command_system::{get_item_equipment_index, player_command_system};
 pub use projectile_system::projectile_system;
 pub use quest_trigger_system::quest_trigger_system;
+pub use settings_save_system::settings_save_system;
 pub use spawn_effect_system::spawn_effect_system;
 pub use spawn_projectile_system::spawn_projectile_system;
 pub use status_effect_system::status_effect_system;
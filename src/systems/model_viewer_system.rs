@@ -42,6 +42,8 @@ pub struct ModelViewerState {
     characters: Vec<Entity>,
     num_characters: usize,
     max_num_characters: usize,
+
+    previous_name_tag_show_all: EnumMap<NameTagType, bool>,
 }
 
 pub fn model_viewer_enter_system(
@@ -90,6 +92,8 @@ pub fn model_viewer_enter_system(
         characters: Vec::new(),
         num_characters: 1,
         max_num_characters: 500,
+
+        previous_name_tag_show_all: name_tag_settings.show_all,
     });
 
     // Reset ambient light
@@ -123,8 +127,8 @@ pub fn model_viewer_exit_system(
         commands.entity(*entity).despawn_recursive();
     }
 
-    // Restore default NameTagSettings
-    *name_tag_settings = NameTagSettings::default();
+    // Restore previous NameTagSettings
+    name_tag_settings.show_all = model_viewer_state.previous_name_tag_show_all;
 }
 
 pub fn model_viewer_system(
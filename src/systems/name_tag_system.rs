@@ -17,6 +17,7 @@ use bevy::{
     window::PrimaryWindow,
 };
 use bevy_egui::{egui, EguiContexts};
+use enum_map::EnumMap;
 
 use rose_game_common::components::{Level, Npc, Team};
 
@@ -53,6 +54,7 @@ pub struct NameTagCache {
     pub cache: HashMap<String, NameTagData>,
     pub pending: HashMap<Entity, NameTagPendingData>,
     pub pixels_per_point: f32,
+    pub font_size: EnumMap<NameTagType, f32>,
 }
 
 #[derive(WorldQuery)]
@@ -406,9 +408,11 @@ pub fn name_tag_system(
 
     if load_zone_events.iter().last().is_some()
         || pixels_per_point != name_tag_cache.pixels_per_point
+        || name_tag_settings.font_size != name_tag_cache.font_size
     {
         // When the zone changes, we flush all cached name tag textures to avoid leaking
         // If pixels_per_point has changed then we need to regenerate name tags using new DPI
+        // If font_size has changed then we need to regenerate name tags using new font size
         for (entity, name_tag_entity) in query_nametags.iter() {
             commands.entity(entity).remove::<NameTagEntity>();
             commands.entity(name_tag_entity.0).despawn_recursive();
@@ -417,6 +421,7 @@ pub fn name_tag_system(
         name_tag_cache.cache.clear();
         name_tag_cache.pending.clear();
         name_tag_cache.pixels_per_point = pixels_per_point;
+        name_tag_cache.font_size = name_tag_settings.font_size;
         return;
     }
 
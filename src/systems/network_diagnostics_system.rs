@@ -0,0 +1,156 @@
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticId, Diagnostics, RegisterDiagnostic},
+    prelude::{App, First, Local, Plugin, Res},
+};
+use std::time::{Duration, Instant};
+
+use crate::{
+    protocol::{NetworkStatistics, NetworkStatisticsTotals},
+    resources::{GameConnection, LoginConnection, WorldConnection},
+};
+
+/// Traffic rates are measured over this interval to smooth out the per frame noise
+const RATE_MEASUREMENT_INTERVAL: Duration = Duration::from_secs(1);
+
+pub struct NetworkDiagnosticsPlugin;
+
+impl NetworkDiagnosticsPlugin {
+    pub const ROUND_TRIP_TIME: DiagnosticId =
+        DiagnosticId::from_u128(191244185542745358946409231718458436627);
+    pub const BYTES_SENT: DiagnosticId =
+        DiagnosticId::from_u128(88227139521404339498547035627519263413);
+    pub const BYTES_RECEIVED: DiagnosticId =
+        DiagnosticId::from_u128(251823063271427542213632474311880148850);
+    pub const PACKETS_SENT: DiagnosticId =
+        DiagnosticId::from_u128(161504339542087424924361728917468017224);
+    pub const PACKETS_RECEIVED: DiagnosticId =
+        DiagnosticId::from_u128(33290981339254860283306591427187305016);
+    pub const LOGIN_QUEUE_DEPTH: DiagnosticId =
+        DiagnosticId::from_u128(312046286823373232917330938262815702359);
+    pub const WORLD_QUEUE_DEPTH: DiagnosticId =
+        DiagnosticId::from_u128(117380573845223563960349286829931713104);
+    pub const GAME_QUEUE_DEPTH: DiagnosticId =
+        DiagnosticId::from_u128(230139476112578870911512837052946574631);
+
+    pub const ALL: [DiagnosticId; 8] = [
+        Self::ROUND_TRIP_TIME,
+        Self::BYTES_SENT,
+        Self::BYTES_RECEIVED,
+        Self::PACKETS_SENT,
+        Self::PACKETS_RECEIVED,
+        Self::LOGIN_QUEUE_DEPTH,
+        Self::WORLD_QUEUE_DEPTH,
+        Self::GAME_QUEUE_DEPTH,
+    ];
+}
+
+impl Plugin for NetworkDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NetworkStatistics>()
+            .register_diagnostic(
+                Diagnostic::new(Self::ROUND_TRIP_TIME, "net_round_trip_time", 20).with_suffix("ms"),
+            )
+            .register_diagnostic(
+                Diagnostic::new(Self::BYTES_SENT, "net_bytes_sent", 20).with_suffix("B/s"),
+            )
+            .register_diagnostic(
+                Diagnostic::new(Self::BYTES_RECEIVED, "net_bytes_received", 20).with_suffix("B/s"),
+            )
+            .register_diagnostic(
+                Diagnostic::new(Self::PACKETS_SENT, "net_packets_sent", 20).with_suffix("/s"),
+            )
+            .register_diagnostic(
+                Diagnostic::new(Self::PACKETS_RECEIVED, "net_packets_received", 20)
+                    .with_suffix("/s"),
+            )
+            .register_diagnostic(Diagnostic::new(
+                Self::LOGIN_QUEUE_DEPTH,
+                "net_login_message_queue",
+                20,
+            ))
+            .register_diagnostic(Diagnostic::new(
+                Self::WORLD_QUEUE_DEPTH,
+                "net_world_message_queue",
+                20,
+            ))
+            .register_diagnostic(Diagnostic::new(
+                Self::GAME_QUEUE_DEPTH,
+                "net_game_message_queue",
+                20,
+            ))
+            // Run before the connection systems empty the server message queues
+            .add_systems(First, network_diagnostics_system);
+    }
+}
+
+pub struct NetworkDiagnosticsState {
+    last_measurement: Instant,
+    last_totals: NetworkStatisticsTotals,
+}
+
+impl Default for NetworkDiagnosticsState {
+    fn default() -> Self {
+        Self {
+            last_measurement: Instant::now(),
+            last_totals: NetworkStatisticsTotals::default(),
+        }
+    }
+}
+
+pub fn network_diagnostics_system(
+    mut diagnostics: Diagnostics,
+    mut state: Local<NetworkDiagnosticsState>,
+    network_statistics: Res<NetworkStatistics>,
+    login_connection: Option<Res<LoginConnection>>,
+    world_connection: Option<Res<WorldConnection>>,
+    game_connection: Option<Res<GameConnection>>,
+) {
+    for round_trip_time in network_statistics.take_round_trip_times() {
+        diagnostics.add_measurement(NetworkDiagnosticsPlugin::ROUND_TRIP_TIME, || {
+            round_trip_time.as_secs_f64() * 1000.0
+        });
+    }
+
+    if let Some(login_connection) = login_connection {
+        diagnostics.add_measurement(NetworkDiagnosticsPlugin::LOGIN_QUEUE_DEPTH, || {
+            login_connection.server_message_rx.len() as f64
+        });
+    }
+
+    if let Some(world_connection) = world_connection {
+        diagnostics.add_measurement(NetworkDiagnosticsPlugin::WORLD_QUEUE_DEPTH, || {
+            world_connection.server_message_rx.len() as f64
+        });
+    }
+
+    if let Some(game_connection) = game_connection {
+        diagnostics.add_measurement(NetworkDiagnosticsPlugin::GAME_QUEUE_DEPTH, || {
+            game_connection.server_message_rx.len() as f64
+        });
+    }
+
+    let elapsed = state.last_measurement.elapsed();
+    if elapsed < RATE_MEASUREMENT_INTERVAL {
+        return;
+    }
+
+    let totals = network_statistics.totals();
+    let last_totals = state.last_totals;
+    let seconds = elapsed.as_secs_f64();
+
+    diagnostics.add_measurement(NetworkDiagnosticsPlugin::BYTES_SENT, || {
+        (totals.bytes_sent - last_totals.bytes_sent) as f64 / seconds
+    });
+    diagnostics.add_measurement(NetworkDiagnosticsPlugin::BYTES_RECEIVED, || {
+        (totals.bytes_received - last_totals.bytes_received) as f64 / seconds
+    });
+    diagnostics.add_measurement(NetworkDiagnosticsPlugin::PACKETS_SENT, || {
+        (totals.packets_sent - last_totals.packets_sent) as f64 / seconds
+    });
+    diagnostics.add_measurement(NetworkDiagnosticsPlugin::PACKETS_RECEIVED, || {
+        (totals.packets_received - last_totals.packets_received) as f64 / seconds
+    });
+
+    state.last_measurement = Instant::now();
+    state.last_totals = totals;
+}
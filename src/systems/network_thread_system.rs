@@ -7,17 +7,62 @@ use rose_game_common::{
 
 use crate::{
     events::NetworkEvent,
-    protocol::irose,
+    protocol::{
+        ChatMessage, NetworkProtocolClient, NetworkStatistics, PacketInspector, ProtocolClient,
+        ProtocolRegistry, ReplayClient, SessionConnectionType, SessionProtocolClient,
+    },
     resources::{
-        GameConnection, LoginConnection, NetworkThread, NetworkThreadMessage, WorldConnection,
+        GameConnection, LoginConnection, NetworkSession, NetworkThread, NetworkThreadMessage,
+        WorldConnection,
     },
 };
 
+fn create_protocol_client(
+    mut client: Box<dyn NetworkProtocolClient>,
+    network_session: &NetworkSession,
+    packet_inspector: &PacketInspector,
+    network_statistics: &NetworkStatistics,
+    connection_type: SessionConnectionType,
+) -> Box<dyn ProtocolClient + Send + Sync> {
+    client.set_packet_inspector(packet_inspector.connection(connection_type));
+    client.set_network_statistics(network_statistics.clone());
+
+    match network_session {
+        NetworkSession::Live => Box::new(client),
+        NetworkSession::Record(session_recorder) => {
+            client.set_session_recorder(session_recorder.connection(connection_type));
+            Box::new(client)
+        }
+        NetworkSession::Replay(session_replay) => {
+            Box::new(ReplayClient::new(client, session_replay, connection_type))
+        }
+    }
+}
+
 pub fn network_thread_system(
     mut commands: Commands,
     network_thread: Res<NetworkThread>,
+    network_session: Res<NetworkSession>,
+    packet_inspector: Res<PacketInspector>,
+    network_statistics: Res<NetworkStatistics>,
+    protocol_registry: Res<ProtocolRegistry>,
     mut network_events: EventReader<NetworkEvent>,
 ) {
+    let Some(network_protocol) = protocol_registry.selected() else {
+        for event in network_events.iter() {
+            let connection_type = match event {
+                NetworkEvent::ConnectLogin { .. } => SessionConnectionType::Login,
+                NetworkEvent::ConnectWorld { .. } => SessionConnectionType::World,
+                NetworkEvent::ConnectGame { .. } => SessionConnectionType::Game,
+            };
+            log::error!(
+                "Unable to create {:?} connection, no network protocol is selected",
+                connection_type
+            );
+        }
+        return;
+    };
+
     for event in network_events.iter() {
         match *event {
             NetworkEvent::ConnectLogin { ref ip, port } => {
@@ -29,13 +74,19 @@ pub fn network_thread_system(
 
                 network_thread
                     .control_tx
-                    .send(NetworkThreadMessage::RunProtocolClient(Box::new(
-                        irose::LoginClient::new(
-                            server_address,
-                            client_message_rx,
-                            server_message_tx,
+                    .send(NetworkThreadMessage::RunProtocolClient(
+                        create_protocol_client(
+                            (network_protocol.login_client)(
+                                server_address,
+                                client_message_rx,
+                                server_message_tx,
+                            ),
+                            &network_session,
+                            &packet_inspector,
+                            &network_statistics,
+                            SessionConnectionType::Login,
                         ),
-                    )))
+                    ))
                     .ok();
 
                 commands
@@ -56,14 +107,20 @@ pub fn network_thread_system(
 
                 network_thread
                     .control_tx
-                    .send(NetworkThreadMessage::RunProtocolClient(Box::new(
-                        irose::WorldClient::new(
-                            server_address,
-                            packet_codec_seed,
-                            client_message_rx,
-                            server_message_tx,
+                    .send(NetworkThreadMessage::RunProtocolClient(
+                        create_protocol_client(
+                            (network_protocol.world_client)(
+                                server_address,
+                                packet_codec_seed,
+                                client_message_rx,
+                                server_message_tx,
+                            ),
+                            &network_session,
+                            &packet_inspector,
+                            &network_statistics,
+                            SessionConnectionType::World,
                         ),
-                    )))
+                    ))
                     .ok();
 
                 commands.insert_resource(WorldConnection::new(
@@ -84,22 +141,32 @@ pub fn network_thread_system(
                     crossbeam_channel::unbounded::<ServerMessage>();
                 let (client_message_tx, client_message_rx) =
                     tokio::sync::mpsc::unbounded_channel::<ClientMessage>();
+                let (chat_message_tx, chat_message_rx) =
+                    tokio::sync::mpsc::unbounded_channel::<ChatMessage>();
                 let server_address = format!("{}:{}", ip, port).parse().unwrap();
 
                 network_thread
                     .control_tx
-                    .send(NetworkThreadMessage::RunProtocolClient(Box::new(
-                        irose::GameClient::new(
-                            server_address,
-                            packet_codec_seed,
-                            client_message_rx,
-                            server_message_tx,
+                    .send(NetworkThreadMessage::RunProtocolClient(
+                        create_protocol_client(
+                            (network_protocol.game_client)(
+                                server_address,
+                                packet_codec_seed,
+                                client_message_rx,
+                                chat_message_rx,
+                                server_message_tx,
+                            ),
+                            &network_session,
+                            &packet_inspector,
+                            &network_statistics,
+                            SessionConnectionType::Game,
                         ),
-                    )))
+                    ))
                     .ok();
 
                 commands.insert_resource(GameConnection::new(
                     client_message_tx,
+                    chat_message_tx,
                     server_message_rx,
                     login_token,
                     Password::Plaintext(password.clone()),
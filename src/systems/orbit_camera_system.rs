@@ -17,8 +17,11 @@ use bevy_rapier3d::{
 };
 use dolly::prelude::{Arm, CameraRig, LeftHanded, Position, Smooth, YawPitch};
 
-use crate::components::{
-    COLLISION_FILTER_COLLIDABLE, COLLISION_FILTER_MOVEABLE, COLLISION_GROUP_PHYSICS_TOY,
+use crate::{
+    components::{
+        COLLISION_FILTER_COLLIDABLE, COLLISION_FILTER_MOVEABLE, COLLISION_GROUP_PHYSICS_TOY,
+    },
+    resources::GameplaySettings,
 };
 
 #[derive(Component)]
@@ -70,6 +73,7 @@ pub fn orbit_camera_system(
     mouse_buttons: Res<Input<MouseButton>>,
     time: Res<Time>,
     rapier_context: Res<RapierContext>,
+    gameplay_settings: Res<GameplaySettings>,
 ) {
     let Ok(mut window) = query_window.get_single_mut() else {
         return;
@@ -175,7 +179,7 @@ pub fn orbit_camera_system(
 
     // Rotate with mouse drag
     if right_pressed {
-        let sensitivity = 0.1;
+        let sensitivity = 0.1 * gameplay_settings.camera_sensitivity;
         orbit_camera
             .rig
             .driver_mut::<YawPitch>()
@@ -3,15 +3,18 @@ use std::time::Duration;
 use bevy::{
     ecs::query::WorldQuery,
     math::Vec3Swizzles,
-    prelude::{Entity, EventReader, EventWriter, Query, Res, With},
+    prelude::{Entity, EventReader, EventWriter, Query, Res, ResMut, With},
 };
 
 use rose_data::{
-    AmmoIndex, EquipmentIndex, ItemClass, ItemType, SkillBasicCommand, SkillCooldown,
+    AmmoIndex, EquipmentIndex, Item, ItemClass, ItemType, SkillBasicCommand, SkillCooldown,
     SkillTargetFilter, SkillType, VehiclePartIndex,
 };
 use rose_game_common::{
-    components::{CharacterInfo, Hotbar, HotbarSlot, Inventory, ItemDrop, SkillList, Team},
+    components::{
+        CharacterInfo, Equipment, Hotbar, HotbarSlot, Inventory, ItemDrop, ItemSlot, SkillList,
+        Team,
+    },
     messages::client::ClientMessage,
 };
 
@@ -21,7 +24,7 @@ use crate::{
         PartyInfo, PlayerCharacter, Position,
     },
     events::{ChatboxEvent, PlayerCommandEvent},
-    resources::{GameConnection, GameData, SelectedTarget},
+    resources::{GameConnection, GameData, RepairMode, RepairState, SelectedTarget},
 };
 
 #[derive(WorldQuery)]
@@ -33,6 +36,7 @@ pub struct PlayerQuery<'w> {
 
     bank: Option<&'w Bank>,
     cooldowns: &'w mut Cooldowns,
+    equipment: &'w Equipment,
     hotbar: &'w mut Hotbar,
     inventory: &'w Inventory,
     position: &'w Position,
@@ -53,6 +57,34 @@ pub struct SkillTargetQuery<'w> {
 }
 
 #[allow(clippy::too_many_arguments)]
+/// Returns which equipment slot an inventory item is equipped into, if any.
+pub fn get_item_equipment_index(game_data: &GameData, item: &Item) -> Option<EquipmentIndex> {
+    match item.get_item_type() {
+        ItemType::Face => Some(EquipmentIndex::Face),
+        ItemType::Head => Some(EquipmentIndex::Head),
+        ItemType::Body => Some(EquipmentIndex::Body),
+        ItemType::Hands => Some(EquipmentIndex::Hands),
+        ItemType::Feet => Some(EquipmentIndex::Feet),
+        ItemType::Back => Some(EquipmentIndex::Back),
+        ItemType::Jewellery => {
+            match game_data
+                .items
+                .get_jewellery_item(item.get_item_number())?
+                .item_data
+                .class
+            {
+                ItemClass::Ring => Some(EquipmentIndex::Ring),
+                ItemClass::Necklace => Some(EquipmentIndex::Necklace),
+                ItemClass::Earring => Some(EquipmentIndex::Earring),
+                _ => None,
+            }
+        }
+        ItemType::Weapon => Some(EquipmentIndex::Weapon),
+        ItemType::SubWeapon => Some(EquipmentIndex::SubWeapon),
+        _ => None,
+    }
+}
+
 pub fn player_command_system(
     mut player_command_events: EventReader<PlayerCommandEvent>,
     mut query_player: Query<PlayerQuery>,
@@ -64,6 +96,7 @@ pub fn player_command_system(
     game_connection: Option<Res<GameConnection>>,
     game_data: Res<GameData>,
     selected_target: Res<SelectedTarget>,
+    mut repair_state: ResMut<RepairState>,
 ) {
     let query_player_result = query_player.get_single_mut();
     if query_player_result.is_err() {
@@ -441,10 +474,12 @@ pub fn player_command_system(
                                 None => todo!(),
                             };
 
-                            // TODO: If item is a repair item, we need to handle this client side
+                            // Repair tools are used on the item selected in repair mode
                             if matches!(consumable_item_data.item_data.class, ItemClass::RepairTool)
                             {
-                                log::info!("TODO: Implement using ItemClass::RepairTool");
+                                repair_state.start(RepairMode::UsingItem {
+                                    use_item_slot: item_slot,
+                                });
                                 continue;
                             }
 
@@ -554,33 +589,7 @@ pub fn player_command_system(
             }
             PlayerCommandEvent::EquipEquipment(item_slot) => {
                 if let Some(item) = player.inventory.get_item(item_slot) {
-                    let equipment_index = match item.get_item_type() {
-                        ItemType::Face => Some(EquipmentIndex::Face),
-                        ItemType::Head => Some(EquipmentIndex::Head),
-                        ItemType::Body => Some(EquipmentIndex::Body),
-                        ItemType::Hands => Some(EquipmentIndex::Hands),
-                        ItemType::Feet => Some(EquipmentIndex::Feet),
-                        ItemType::Back => Some(EquipmentIndex::Back),
-                        ItemType::Jewellery => {
-                            if let Some(jewellery_item) =
-                                game_data.items.get_jewellery_item(item.get_item_number())
-                            {
-                                match jewellery_item.item_data.class {
-                                    ItemClass::Ring => Some(EquipmentIndex::Ring),
-                                    ItemClass::Necklace => Some(EquipmentIndex::Necklace),
-                                    ItemClass::Earring => Some(EquipmentIndex::Earring),
-                                    _ => None,
-                                }
-                            } else {
-                                None
-                            }
-                        }
-                        ItemType::Weapon => Some(EquipmentIndex::Weapon),
-                        ItemType::SubWeapon => Some(EquipmentIndex::SubWeapon),
-                        _ => None,
-                    };
-
-                    if let Some(equipment_index) = equipment_index {
+                    if let Some(equipment_index) = get_item_equipment_index(&game_data, item) {
                         if let Some(game_connection) = game_connection.as_ref() {
                             game_connection
                                 .client_message_tx
@@ -769,6 +778,90 @@ pub fn player_command_system(
                     }
                 }
             }
+            PlayerCommandEvent::InsertGem(equipment_index, item_slot) => {
+                let Some(equipment_item) = player.equipment.get_equipment_item(equipment_index)
+                else {
+                    continue;
+                };
+
+                if player
+                    .inventory
+                    .get_item(item_slot)
+                    .map_or(true, |item| item.get_item_type() != ItemType::Gem)
+                {
+                    continue;
+                }
+
+                if !equipment_item.has_socket {
+                    chatbox_events.send(ChatboxEvent::System(
+                        "This item does not have a socket.".to_string(),
+                    ));
+                } else if equipment_item.gem > 300 {
+                    chatbox_events.send(ChatboxEvent::System(
+                        "This item already has a gem socketed.".to_string(),
+                    ));
+                } else if let Some(game_connection) = game_connection.as_ref() {
+                    game_connection
+                        .client_message_tx
+                        .send(ClientMessage::CraftInsertGem {
+                            equipment_index,
+                            item_slot,
+                        })
+                        .ok();
+                }
+            }
+            PlayerCommandEvent::RepairItem(item_slot) => {
+                let Some(repair_mode) = repair_state.mode else {
+                    continue;
+                };
+
+                let equipment_item = match item_slot {
+                    ItemSlot::Equipment(equipment_index) => {
+                        player.equipment.get_equipment_item(equipment_index)
+                    }
+                    ItemSlot::Inventory(_, _) => player
+                        .inventory
+                        .get_item(item_slot)
+                        .and_then(|item| item.as_equipment()),
+                    _ => None,
+                };
+                let Some(equipment_item) = equipment_item else {
+                    continue;
+                };
+
+                if equipment_item.life >= 1000 {
+                    chatbox_events.send(ChatboxEvent::System(
+                        "This item does not need to be repaired.".to_string(),
+                    ));
+                    continue;
+                }
+
+                if let Some(game_connection) = game_connection.as_ref() {
+                    match repair_mode {
+                        RepairMode::UsingNpc { npc_entity_id } => {
+                            game_connection
+                                .client_message_tx
+                                .send(ClientMessage::RepairItemUsingNpc {
+                                    npc_entity_id,
+                                    item_slot,
+                                })
+                                .ok();
+                        }
+                        RepairMode::UsingItem { use_item_slot } => {
+                            game_connection
+                                .client_message_tx
+                                .send(ClientMessage::RepairItemUsingItem {
+                                    use_item_slot,
+                                    item_slot,
+                                })
+                                .ok();
+
+                            // A repair tool is consumed after repairing a single item
+                            repair_state.cancel();
+                        }
+                    }
+                }
+            }
             PlayerCommandEvent::UseHotbar(_, _) => {} // Handled above
         }
     }
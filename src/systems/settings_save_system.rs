@@ -0,0 +1,319 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use bevy::{
+    ecs::system::SystemParam,
+    math::Vec2,
+    prelude::{DetectChanges, Local, Res},
+};
+use bevy_egui::EguiSettings;
+use enum_map::EnumMap;
+
+use crate::{
+    components::{NameTagType, SoundCategory},
+    resources::{
+        GameplaySettings, KeyAction, KeyBindings, NameTagSettings, RenderConfiguration,
+        SettingsFile, SoundSettings,
+    },
+};
+
+/// Wait for settings to stop changing before saving, e.g. whilst dragging a slider
+const SAVE_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(SystemParam)]
+pub struct SettingsResources<'w, 's> {
+    pub sound_settings: Res<'w, SoundSettings>,
+    pub key_bindings: Res<'w, KeyBindings>,
+    pub render_configuration: Res<'w, RenderConfiguration>,
+    pub name_tag_settings: Res<'w, NameTagSettings>,
+    pub gameplay_settings: Res<'w, GameplaySettings>,
+    pub egui_settings: Res<'w, EguiSettings>,
+
+    #[system_param(ignore)]
+    pub phantom: std::marker::PhantomData<&'s ()>,
+}
+
+impl SettingsResources<'_, '_> {
+    fn is_changed(&self) -> bool {
+        self.sound_settings.is_changed()
+            || self.key_bindings.is_changed()
+            || self.render_configuration.is_changed()
+            || self.name_tag_settings.is_changed()
+            || self.gameplay_settings.is_changed()
+            || self.egui_settings.is_changed()
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct SavedSettings {
+    fullscreen: bool,
+    window_size: Vec2,
+    disable_vsync: bool,
+    shadow_distance: f32,
+    fog_distance_multiplier: f32,
+    trail_effect_duration_multiplier: f32,
+    sound_enabled: bool,
+    sound_global_gain: f32,
+    sound_gains: EnumMap<SoundCategory, f32>,
+    ui_scale: f32,
+    name_tag_show_all: EnumMap<NameTagType, bool>,
+    name_tag_font_size: EnumMap<NameTagType, f32>,
+    auto_pickup: bool,
+    camera_sensitivity: f32,
+    keybindings: HashMap<KeyAction, Vec<String>>,
+}
+
+impl SavedSettings {
+    fn new(settings: &SettingsResources) -> Self {
+        let render_configuration = &settings.render_configuration;
+        let sound_settings = &settings.sound_settings;
+        let name_tag_settings = &settings.name_tag_settings;
+        let gameplay_settings = &settings.gameplay_settings;
+        let default_key_bindings = KeyBindings::default();
+
+        Self {
+            fullscreen: render_configuration.fullscreen,
+            window_size: render_configuration.window_size,
+            disable_vsync: render_configuration.disable_vsync,
+            shadow_distance: render_configuration.shadow_distance,
+            fog_distance_multiplier: render_configuration.fog_distance_multiplier,
+            trail_effect_duration_multiplier: render_configuration.trail_effect_duration_multiplier,
+            sound_enabled: sound_settings.enabled,
+            sound_global_gain: sound_settings.global_gain,
+            sound_gains: sound_settings.gains,
+            ui_scale: settings.egui_settings.scale_factor as f32,
+            name_tag_show_all: name_tag_settings.show_all,
+            name_tag_font_size: name_tag_settings.font_size,
+            auto_pickup: gameplay_settings.auto_pickup,
+            camera_sensitivity: gameplay_settings.camera_sensitivity,
+            // Only bindings which differ from the defaults are saved
+            keybindings: settings
+                .key_bindings
+                .bindings
+                .iter()
+                .filter(|(action, bindings)| *bindings != &default_key_bindings.bindings[*action])
+                .map(|(action, bindings)| {
+                    (
+                        action,
+                        bindings.iter().map(|binding| binding.to_string()).collect(),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Writes only the settings which differ from `saved`, so values the
+    /// user has not changed in game are left exactly as they are in the file.
+    fn write(&self, saved: &SavedSettings, document: &mut toml_edit::Document) {
+        // Avoid writing f32 rounding noise such as 0.6000000238418579
+        let float = |value: f32| toml_edit::value((value as f64 * 1000.0).round() / 1000.0);
+
+        if self.fullscreen != saved.fullscreen {
+            document["graphics"]["mode"]["type"] = toml_edit::value(if self.fullscreen {
+                "fullscreen"
+            } else {
+                "window"
+            });
+        }
+        if self.window_size != saved.window_size {
+            document["graphics"]["mode"]["width"] = float(self.window_size.x);
+            document["graphics"]["mode"]["height"] = float(self.window_size.y);
+        }
+        if self.disable_vsync != saved.disable_vsync {
+            document["graphics"]["disable_vsync"] = toml_edit::value(self.disable_vsync);
+        }
+        if self.shadow_distance != saved.shadow_distance {
+            document["graphics"]["shadow_distance"] = float(self.shadow_distance);
+        }
+        if self.fog_distance_multiplier != saved.fog_distance_multiplier {
+            document["graphics"]["fog_distance_multiplier"] = float(self.fog_distance_multiplier);
+        }
+        if self.trail_effect_duration_multiplier != saved.trail_effect_duration_multiplier {
+            document["graphics"]["trail_effect_duration_multiplier"] =
+                float(self.trail_effect_duration_multiplier);
+        }
+
+        if self.sound_enabled != saved.sound_enabled {
+            document["sound"]["enabled"] = toml_edit::value(self.sound_enabled);
+        }
+        if self.sound_global_gain != saved.sound_global_gain {
+            document["sound"]["volume"]["global"] = float(self.sound_global_gain);
+        }
+        for (category, gain) in self.sound_gains.iter() {
+            if *gain == saved.sound_gains[category] {
+                continue;
+            }
+
+            let key = match category {
+                SoundCategory::BackgroundMusic => "background_music",
+                SoundCategory::PlayerFootstep => "player_footstep",
+                SoundCategory::PlayerCombat => "player_combat",
+                SoundCategory::OtherFootstep => "other_footstep",
+                SoundCategory::OtherCombat => "other_combat",
+                SoundCategory::NpcSounds => "npc_sounds",
+                SoundCategory::Ui => "ui_sounds",
+            };
+            document["sound"]["volume"][key] = float(*gain);
+        }
+
+        if self.ui_scale != saved.ui_scale {
+            document["interface"]["ui_scale"] = float(self.ui_scale);
+        }
+        for (name_tag_type, show_all) in self.name_tag_show_all.iter() {
+            let key = match name_tag_type {
+                NameTagType::Character => "character_name_tags",
+                NameTagType::Npc => "npc_name_tags",
+                NameTagType::Monster => "monster_name_tags",
+            };
+            if *show_all != saved.name_tag_show_all[name_tag_type] {
+                document["interface"][key]["show_all"] = toml_edit::value(*show_all);
+            }
+            if self.name_tag_font_size[name_tag_type] != saved.name_tag_font_size[name_tag_type] {
+                document["interface"][key]["font_size"] =
+                    float(self.name_tag_font_size[name_tag_type]);
+            }
+        }
+
+        if self.auto_pickup != saved.auto_pickup {
+            document["gameplay"]["auto_pickup"] = toml_edit::value(self.auto_pickup);
+        }
+        if self.camera_sensitivity != saved.camera_sensitivity {
+            document["gameplay"]["camera_sensitivity"] = float(self.camera_sensitivity);
+        }
+
+        for action in KeyAction::iter() {
+            let bindings = self.keybindings.get(&action);
+            if bindings == saved.keybindings.get(&action) {
+                continue;
+            }
+
+            if let Some(bindings) = bindings {
+                document["keybindings"][action.config_name()] = toml_edit::value(
+                    bindings
+                        .iter()
+                        .map(|binding| binding.as_str())
+                        .collect::<toml_edit::Array>(),
+                );
+            } else if let Some(keybindings) = document
+                .get_mut("keybindings")
+                .and_then(|item| item.as_table_like_mut())
+            {
+                keybindings.remove(action.config_name());
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SettingsSaveState {
+    saved: Option<SavedSettings>,
+    changed_at: Option<Instant>,
+}
+
+pub fn settings_save_system(
+    mut state: Local<SettingsSaveState>,
+    settings_file: Option<Res<SettingsFile>>,
+    settings: SettingsResources,
+) {
+    let Some(settings_file) = settings_file else {
+        return;
+    };
+
+    if state.saved.is_none() {
+        // The initial settings were loaded from the file, so do not need saving
+        state.saved = Some(SavedSettings::new(&settings));
+        return;
+    }
+
+    if settings.is_changed() {
+        state.changed_at = Some(Instant::now());
+    }
+
+    let Some(changed_at) = state.changed_at else {
+        return;
+    };
+    if changed_at.elapsed() < SAVE_DELAY {
+        return;
+    }
+    state.changed_at = None;
+
+    let saved_settings = SavedSettings::new(&settings);
+    let Some(previous_settings) = state
+        .saved
+        .as_ref()
+        .filter(|saved| **saved != saved_settings)
+    else {
+        return;
+    };
+
+    match settings_file.update(|document| saved_settings.write(previous_settings, document)) {
+        Ok(_) => {
+            log::info!("Saved settings to {}", settings_file.path.to_string_lossy());
+        }
+        Err(error) => {
+            log::error!(
+                "Failed to save settings to {} with error: {}",
+                settings_file.path.to_string_lossy(),
+                error
+            );
+        }
+    }
+    state.saved = Some(saved_settings);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn saved_settings() -> SavedSettings {
+        SavedSettings {
+            fullscreen: false,
+            window_size: Vec2::new(1920.0, 1080.0),
+            disable_vsync: false,
+            shadow_distance: 1000.0,
+            fog_distance_multiplier: 1.0,
+            trail_effect_duration_multiplier: 1.0,
+            sound_enabled: true,
+            sound_global_gain: 0.5,
+            sound_gains: EnumMap::default(),
+            ui_scale: 1.0,
+            name_tag_show_all: EnumMap::default(),
+            name_tag_font_size: EnumMap::default(),
+            auto_pickup: false,
+            camera_sensitivity: 1.0,
+            keybindings: HashMap::default(),
+        }
+    }
+
+    #[test]
+    fn write_only_changed_settings() {
+        // The file does not match the loaded settings, e.g. it was edited by hand
+        let mut document = r#"[graphics]
+shadow_distance = 2000.0
+
+[sound]
+enabled = true
+"#
+        .parse::<toml_edit::Document>()
+        .unwrap();
+
+        let saved = saved_settings();
+        let mut changed = saved.clone();
+        changed.sound_enabled = false;
+        changed.auto_pickup = true;
+        changed.write(&saved, &mut document);
+
+        assert_eq!(
+            document["graphics"]["shadow_distance"].as_float(),
+            Some(2000.0)
+        );
+        assert!(document["graphics"].get("disable_vsync").is_none());
+        assert!(document.get("interface").is_none());
+        assert!(document.get("keybindings").is_none());
+        assert_eq!(document["sound"]["enabled"].as_bool(), Some(false));
+        assert!(document["sound"].get("volume").is_none());
+        assert_eq!(document["gameplay"]["auto_pickup"].as_bool(), Some(true));
+    }
+}
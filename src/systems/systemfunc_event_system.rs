@@ -1,62 +1,71 @@
-use bevy::prelude::{EventReader, EventWriter};
+use bevy::prelude::{EventReader, EventWriter, Res};
+use rose_data::{QuestTriggerHash, WarpGateId};
 use rose_file_readers::VfsPathBuf;
+use rose_game_common::messages::client::ClientMessage;
 
-use crate::events::{ConversationDialogEvent, SystemFuncEvent};
+use crate::{
+    components::ClientEntityId,
+    events::{
+        ConversationDialogEvent, QuestTriggerEvent, SpawnEffectData, SpawnEffectEvent,
+        SystemFuncEvent,
+    },
+    resources::{ClientEntityList, GameConnection, SystemFuncAction, SystemFuncTable},
+};
 
 pub fn system_func_event_system(
     mut events: EventReader<SystemFuncEvent>,
     mut conversation_dialog_events: EventWriter<ConversationDialogEvent>,
+    mut quest_trigger_events: EventWriter<QuestTriggerEvent>,
+    mut spawn_effect_events: EventWriter<SpawnEffectEvent>,
+    client_entity_list: Res<ClientEntityList>,
+    game_connection: Option<Res<GameConnection>>,
+    system_func_table: Res<SystemFuncTable>,
 ) {
     for event in events.iter() {
-        let SystemFuncEvent::CallFunction(function_name, _parameters) = event;
+        let SystemFuncEvent::CallFunction(function_name, parameters) = event;
 
-        match function_name.as_str() {
-            "Lunar_Warp_Gate01" => {
-                conversation_dialog_events.send(ConversationDialogEvent::OpenEventDialog(
-                    VfsPathBuf::new("3DDATA/EVENT/OBJECT001.CON"),
-                ));
-            }
-            "mushroom" => {
-                conversation_dialog_events.send(ConversationDialogEvent::OpenEventDialog(
-                    VfsPathBuf::new("3DDATA/EVENT/OBJECT002.CON"),
-                ));
-            }
-            "sandglass" => {
-                conversation_dialog_events.send(ConversationDialogEvent::OpenEventDialog(
-                    VfsPathBuf::new("3DDATA/EVENT/OBJECT003.CON"),
-                ));
-            }
-            "horriblebook" => {
-                conversation_dialog_events.send(ConversationDialogEvent::OpenEventDialog(
-                    VfsPathBuf::new("3DDATA/EVENT/OBJECT004.CON"),
-                ));
-            }
-            "piramid01" | "piramid03" => {
-                conversation_dialog_events.send(ConversationDialogEvent::OpenEventDialog(
-                    VfsPathBuf::new("3DDATA/EVENT/OBJECT005.CON"),
-                ));
-            }
-            "piramid02" => {
-                conversation_dialog_events.send(ConversationDialogEvent::OpenEventDialog(
-                    VfsPathBuf::new("3DDATA/EVENT/OBJECT006.CON"),
-                ));
-            }
-            "owl" => {
-                conversation_dialog_events.send(ConversationDialogEvent::OpenEventDialog(
-                    VfsPathBuf::new("3DDATA/EVENT/OBJECT007.CON"),
-                ));
-            }
-            "mana" => {
-                conversation_dialog_events.send(ConversationDialogEvent::OpenEventDialog(
-                    VfsPathBuf::new("3DDATA/EVENT/OBJECT008.CON"),
-                ));
-            }
-            "genzistone" => {
-                conversation_dialog_events.send(ConversationDialogEvent::OpenEventDialog(
-                    VfsPathBuf::new("3DDATA/EVENT/OBJECT009.CON"),
-                ));
+        let Some(actions) = system_func_table.get(function_name) else {
+            log::warn!("Unimplemented system func function {}", function_name);
+            continue;
+        };
+
+        for action in actions {
+            match action {
+                SystemFuncAction::OpenConversation { path } => {
+                    conversation_dialog_events.send(ConversationDialogEvent::OpenEventDialog(
+                        VfsPathBuf::new(path),
+                    ));
+                }
+                SystemFuncAction::PlayEffect { path } => {
+                    // The first parameter is the client entity id of the caller
+                    if let Some(entity) = parameters
+                        .first()
+                        .and_then(|value| value.to_usize().ok())
+                        .and_then(|id| client_entity_list.get(ClientEntityId(id)))
+                    {
+                        spawn_effect_events.send(SpawnEffectEvent::OnEntity(
+                            entity,
+                            None,
+                            SpawnEffectData::with_path(VfsPathBuf::new(path)),
+                        ));
+                    }
+                }
+                SystemFuncAction::QuestTrigger { name } => {
+                    quest_trigger_events.send(QuestTriggerEvent::DoTrigger(
+                        QuestTriggerHash::from(name.as_str()),
+                    ));
+                }
+                SystemFuncAction::Warp { warp_gate_id } => {
+                    if let Some(game_connection) = game_connection.as_ref() {
+                        game_connection
+                            .client_message_tx
+                            .send(ClientMessage::WarpGateRequest {
+                                warp_gate_id: WarpGateId::new(*warp_gate_id),
+                            })
+                            .ok();
+                    }
+                }
             }
-            unimplemented => log::warn!("Unimplemented system func function {}", unimplemented),
         }
     }
 }
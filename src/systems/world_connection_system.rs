@@ -5,7 +5,9 @@ use rose_network_common::ConnectionError;
 
 use crate::{
     events::{NetworkEvent, WorldConnectionEvent},
-    resources::{Account, AppState, CharacterList, WorldConnection},
+    resources::{
+        Account, AppState, CharacterList, ConnectionState, ConnectionType, WorldConnection,
+    },
 };
 
 pub fn world_connection_system(
@@ -14,6 +16,7 @@ pub fn world_connection_system(
     account: Option<Res<Account>>,
     app_state_current: Res<State<AppState>>,
     mut app_state_next: ResMut<NextState<AppState>>,
+    mut connection_state: ResMut<ConnectionState>,
     mut network_events: EventWriter<NetworkEvent>,
     mut world_connection_events: EventWriter<WorldConnectionEvent>,
 ) {
@@ -85,7 +88,9 @@ pub fn world_connection_system(
             Ok(ServerMessage::DeleteCharacterError { name }) => {
                 world_connection_events.send(WorldConnectionEvent::DeleteCharacterError { name });
             }
-            // ServerMessage::ReturnToCharacterSelect
+            Ok(ServerMessage::ReturnToCharacterSelect) => {
+                world_connection_events.send(WorldConnectionEvent::ReturnToCharacterSelect);
+            }
             Ok(message) => {
                 log::warn!("Received unexpected world server message: {:#?}", message);
             }
@@ -97,8 +102,8 @@ pub fn world_connection_system(
     };
 
     if let Err(error) = result {
-        // TODO: Store error somewhere to display to user
         log::warn!("World server connection error: {}", error);
+        connection_state.connection_lost(ConnectionType::World, error.to_string());
         commands.remove_resource::<WorldConnection>();
     }
 }
@@ -0,0 +1,320 @@
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::{Query, Res, ResMut, Resource},
+};
+use std::{collections::BTreeMap, fmt::Write};
+
+use rose_data::SkillType;
+use rose_game_common::messages::client::ClientMessage;
+
+use crate::{
+    components::{ClientEntity, ClientEntityName, ClientEntityType},
+    protocol::ChatMessage,
+    resources::{ChatIgnoreList, GameConnection, GameData},
+};
+
+/// A line of text entered in the chatbox.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ChatInput<'a> {
+    Say(&'a str),
+    Shout(&'a str),
+    Whisper { target: &'a str, text: &'a str },
+    Party(&'a str),
+    Clan(&'a str),
+    Allied(&'a str),
+    Command { name: &'a str, args: Vec<&'a str> },
+}
+
+fn non_empty_message(text: &str) -> Result<&str, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        Err("Cannot send an empty message.".to_string())
+    } else {
+        Ok(text)
+    }
+}
+
+pub fn parse_chat_input(line: &str) -> Result<ChatInput<'_>, String> {
+    let line = line.trim();
+
+    let Some(prefix) = line.chars().next() else {
+        return Err("Cannot send an empty message.".to_string());
+    };
+    let rest = &line[prefix.len_utf8()..];
+
+    match prefix {
+        '/' => {
+            let mut words = rest.split_whitespace();
+            let Some(name) = words.next() else {
+                return Err("Type /help for a list of commands.".to_string());
+            };
+
+            Ok(ChatInput::Command {
+                name,
+                args: words.collect(),
+            })
+        }
+        '!' => Ok(ChatInput::Shout(non_empty_message(rest)?)),
+        '@' => {
+            let (target, text) = rest
+                .trim_start()
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| "Usage: @name message".to_string())?;
+
+            Ok(ChatInput::Whisper {
+                target,
+                text: non_empty_message(text)?,
+            })
+        }
+        '#' => Ok(ChatInput::Party(non_empty_message(rest)?)),
+        '&' => Ok(ChatInput::Clan(non_empty_message(rest)?)),
+        '~' => Ok(ChatInput::Allied(non_empty_message(rest)?)),
+        _ => Ok(ChatInput::Say(line)),
+    }
+}
+
+impl<'a> ChatInput<'a> {
+    /// Sends this chat input to the server, returns false if it is a command
+    /// which must be run with [`ChatCommands::run`].
+    pub fn send(&self, game_connection: &GameConnection) -> bool {
+        let message = match *self {
+            ChatInput::Say(text) => {
+                game_connection
+                    .client_message_tx
+                    .send(ClientMessage::Chat {
+                        text: text.to_string(),
+                    })
+                    .ok();
+                return true;
+            }
+            ChatInput::Shout(text) => ChatMessage::Shout {
+                text: text.to_string(),
+            },
+            ChatInput::Whisper { target, text } => ChatMessage::Whisper {
+                target: target.to_string(),
+                text: text.to_string(),
+            },
+            ChatInput::Party(text) => ChatMessage::Party {
+                text: text.to_string(),
+            },
+            ChatInput::Clan(text) => ChatMessage::Clan {
+                text: text.to_string(),
+            },
+            ChatInput::Allied(text) => ChatMessage::Allied {
+                text: text.to_string(),
+            },
+            ChatInput::Command { .. } => return false,
+        };
+
+        game_connection.chat_message_tx.send(message).ok();
+        true
+    }
+}
+
+#[derive(SystemParam)]
+pub struct ChatCommandContext<'w, 's> {
+    pub game_connection: Option<Res<'w, GameConnection>>,
+    pub game_data: Res<'w, GameData>,
+    pub ignore_list: ResMut<'w, ChatIgnoreList>,
+    pub query_client_entity: Query<'w, 's, (&'static ClientEntity, &'static ClientEntityName)>,
+}
+
+/// Runs a chat command, returning an optional message to show in the chatbox
+/// or an error message.
+pub type ChatCommandFn = fn(&mut ChatCommandContext, &[&str]) -> Result<Option<String>, String>;
+
+pub struct ChatCommand {
+    pub usage: &'static str,
+    pub description: &'static str,
+    pub run: ChatCommandFn,
+}
+
+/// The slash commands which can be entered in the chatbox, additional commands
+/// can be registered from `SystemsConfig.add_custom_systems`.
+#[derive(Resource)]
+pub struct ChatCommands {
+    commands: BTreeMap<String, ChatCommand>,
+}
+
+impl ChatCommands {
+    pub fn register(
+        &mut self,
+        name: &str,
+        usage: &'static str,
+        description: &'static str,
+        run: ChatCommandFn,
+    ) {
+        self.commands.insert(
+            name.to_lowercase(),
+            ChatCommand {
+                usage,
+                description,
+                run,
+            },
+        );
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ChatCommand> {
+        self.commands.get(&name.to_lowercase())
+    }
+
+    pub fn run(
+        &self,
+        context: &mut ChatCommandContext,
+        name: &str,
+        args: &[&str],
+    ) -> Result<Option<String>, String> {
+        if name.eq_ignore_ascii_case("help") {
+            let mut output = String::from("Commands:");
+            for (name, command) in self.commands.iter() {
+                write!(
+                    output,
+                    "\n/{} {} - {}",
+                    name, command.usage, command.description
+                )
+                .ok();
+            }
+            return Ok(Some(output));
+        }
+
+        let command = self.get(name).ok_or_else(|| {
+            format!(
+                "Unknown command /{}, type /help for a list of commands.",
+                name
+            )
+        })?;
+        (command.run)(context, args)
+    }
+}
+
+impl Default for ChatCommands {
+    fn default() -> Self {
+        let mut chat_commands = Self {
+            commands: BTreeMap::new(),
+        };
+
+        chat_commands.register("sit", "", "Sit down or stand up", chat_command_sit);
+        chat_commands.register("emote", "<name>", "Perform an emote", chat_command_emote);
+        chat_commands.register("who", "", "List nearby players", chat_command_who);
+        chat_commands.register(
+            "ignore",
+            "[name]",
+            "Hide chat from a player, or list ignored players",
+            chat_command_ignore,
+        );
+        chat_commands.register(
+            "unignore",
+            "<name>",
+            "Show chat from an ignored player",
+            chat_command_unignore,
+        );
+
+        chat_commands
+    }
+}
+
+fn chat_command_sit(
+    context: &mut ChatCommandContext,
+    _args: &[&str],
+) -> Result<Option<String>, String> {
+    if let Some(game_connection) = context.game_connection.as_ref() {
+        game_connection
+            .client_message_tx
+            .send(ClientMessage::SitToggle)
+            .ok();
+    }
+
+    Ok(None)
+}
+
+fn chat_command_emote(
+    context: &mut ChatCommandContext,
+    args: &[&str],
+) -> Result<Option<String>, String> {
+    let mut emotes = context
+        .game_data
+        .skills
+        .iter()
+        .filter(|skill_data| matches!(skill_data.skill_type, SkillType::Emote));
+
+    if args.is_empty() {
+        let names: Vec<&str> = emotes.map(|skill_data| skill_data.name).collect();
+        return Err(format!(
+            "Usage: /emote <name>, emotes: {}",
+            names.join(", ")
+        ));
+    }
+
+    let name = args.join(" ");
+    let skill_data = emotes
+        .find(|skill_data| skill_data.name.eq_ignore_ascii_case(&name))
+        .ok_or_else(|| format!("Unknown emote {}.", name))?;
+    let motion_id = skill_data
+        .action_motion_id
+        .ok_or_else(|| format!("Unknown emote {}.", name))?;
+
+    if let Some(game_connection) = context.game_connection.as_ref() {
+        game_connection
+            .client_message_tx
+            .send(ClientMessage::UseEmote {
+                motion_id,
+                is_stop: true,
+            })
+            .ok();
+    }
+
+    Ok(None)
+}
+
+fn chat_command_who(
+    context: &mut ChatCommandContext,
+    _args: &[&str],
+) -> Result<Option<String>, String> {
+    let mut names: Vec<&str> = context
+        .query_client_entity
+        .iter()
+        .filter(|(client_entity, _)| client_entity.entity_type == ClientEntityType::Character)
+        .map(|(_, name)| name.name.as_str())
+        .collect();
+    names.sort_unstable();
+
+    Ok(Some(format!(
+        "{} players nearby: {}",
+        names.len(),
+        names.join(", ")
+    )))
+}
+
+fn chat_command_ignore(
+    context: &mut ChatCommandContext,
+    args: &[&str],
+) -> Result<Option<String>, String> {
+    let Some(name) = args.first() else {
+        let names: Vec<&str> = context.ignore_list.iter().collect();
+        if names.is_empty() {
+            return Ok(Some("You are not ignoring anyone.".to_string()));
+        }
+        return Ok(Some(format!("Ignoring: {}", names.join(", "))));
+    };
+
+    if context.ignore_list.add(name) {
+        Ok(Some(format!("You are now ignoring {}.", name)))
+    } else {
+        Err(format!("You are already ignoring {}.", name))
+    }
+}
+
+fn chat_command_unignore(
+    context: &mut ChatCommandContext,
+    args: &[&str],
+) -> Result<Option<String>, String> {
+    let name = args
+        .first()
+        .ok_or_else(|| "Usage: /unignore <name>".to_string())?;
+
+    if context.ignore_list.remove(name) {
+        Ok(Some(format!("You are no longer ignoring {}.", name)))
+    } else {
+        Err(format!("You are not ignoring {}.", name))
+    }
+}
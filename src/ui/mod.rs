@@ -1,3 +1,4 @@
+mod chat_commands;
 mod dialog_loader;
 mod drag_and_drop_slot;
 mod tooltips;
@@ -8,17 +9,21 @@ mod ui_character_select_name_tag_system;
 mod ui_character_select_system;
 mod ui_chatbox_system;
 mod ui_clan_system;
+mod ui_community_system;
 mod ui_create_clan;
 mod ui_debug_camera_info_system;
 mod ui_debug_client_entity_list_system;
 mod ui_debug_command_viewer_system;
+mod ui_debug_conversation_trace_system;
 mod ui_debug_diagnostics_system;
 mod ui_debug_dialog_list;
 mod ui_debug_effect_list;
 mod ui_debug_entity_inspector_system;
 mod ui_debug_item_list_system;
 mod ui_debug_npc_list_system;
+mod ui_debug_packet_inspector_system;
 mod ui_debug_physics;
+mod ui_debug_quest_trigger_system;
 mod ui_debug_render_system;
 mod ui_debug_skill_list_system;
 mod ui_debug_window_system;
@@ -26,8 +31,11 @@ mod ui_debug_zone_lighting_system;
 mod ui_debug_zone_list_system;
 mod ui_debug_zone_time_system;
 mod ui_drag_and_drop_system;
+mod ui_exit_system;
 mod ui_game_menu_system;
+mod ui_help_system;
 mod ui_hotbar_system;
+mod ui_info_system;
 mod ui_inventory_system;
 mod ui_item_drop_name_system;
 mod ui_login_system;
@@ -40,14 +48,18 @@ mod ui_party_system;
 mod ui_personal_store_system;
 mod ui_player_info_system;
 mod ui_quest_list_system;
+mod ui_quest_tracker_system;
+mod ui_repair_system;
 mod ui_respawn_system;
 mod ui_selected_target_system;
+mod ui_separate_system;
 mod ui_server_select_system;
 mod ui_settings_system;
 mod ui_skill_list_system;
 mod ui_skill_tree_system;
 mod ui_sound_event_system;
 mod ui_status_effects_system;
+mod ui_upgrade_system;
 mod ui_window_sound_system;
 pub mod widgets;
 
@@ -55,6 +67,9 @@ pub mod widgets;
 pub struct UiStateWindows {
     pub character_info_open: bool,
     pub clan_open: bool,
+    pub community_open: bool,
+    pub help_open: bool,
+    pub info_open: bool,
     pub inventory_open: bool,
     pub skill_list_open: bool,
     pub skill_tree_open: bool,
@@ -63,19 +78,27 @@ pub struct UiStateWindows {
     pub menu_open: bool,
     pub party_open: bool,
     pub party_options_open: bool,
+    pub exit_open: bool,
 
     // Below are only opened via in game events rather than directly
     pub bank_open: bool,
     pub create_clan_open: bool,
+    pub separate_open: bool,
+    pub upgrade_open: bool,
 
     // Test ui
     pub selected_target_ui_open: bool,
 }
 
 use bevy::prelude::Resource;
+pub use chat_commands::{
+    parse_chat_input, ChatCommand, ChatCommandContext, ChatCommandFn, ChatCommands, ChatInput,
+};
 pub use dialog_loader::{load_dialog_sprites_system, DialogInstance, DialogLoader};
 pub use drag_and_drop_slot::{DragAndDropId, DragAndDropSlot};
-pub use tooltips::{get_item_name_color, ui_add_item_tooltip, ui_add_skill_tooltip};
+pub use tooltips::{
+    get_item_name_color, ui_add_item_compare_tooltip, ui_add_item_tooltip, ui_add_skill_tooltip,
+};
 pub use ui_bank_system::ui_bank_system;
 pub use ui_character_create_system::ui_character_create_system;
 pub use ui_character_info_system::ui_character_info_system;
@@ -83,17 +106,21 @@ pub use ui_character_select_name_tag_system::ui_character_select_name_tag_system
 pub use ui_character_select_system::ui_character_select_system;
 pub use ui_chatbox_system::ui_chatbox_system;
 pub use ui_clan_system::ui_clan_system;
+pub use ui_community_system::ui_community_system;
 pub use ui_create_clan::ui_create_clan_system;
 pub use ui_debug_camera_info_system::ui_debug_camera_info_system;
 pub use ui_debug_client_entity_list_system::ui_debug_client_entity_list_system;
 pub use ui_debug_command_viewer_system::ui_debug_command_viewer_system;
+pub use ui_debug_conversation_trace_system::ui_debug_conversation_trace_system;
 pub use ui_debug_diagnostics_system::ui_debug_diagnostics_system;
 pub use ui_debug_dialog_list::ui_debug_dialog_list_system;
 pub use ui_debug_effect_list::ui_debug_effect_list_system;
 pub use ui_debug_entity_inspector_system::ui_debug_entity_inspector_system;
 pub use ui_debug_item_list_system::ui_debug_item_list_system;
 pub use ui_debug_npc_list_system::ui_debug_npc_list_system;
+pub use ui_debug_packet_inspector_system::ui_debug_packet_inspector_system;
 pub use ui_debug_physics::ui_debug_physics_system;
+pub use ui_debug_quest_trigger_system::ui_debug_quest_trigger_system;
 pub use ui_debug_render_system::ui_debug_render_system;
 pub use ui_debug_skill_list_system::ui_debug_skill_list_system;
 pub use ui_debug_window_system::{ui_debug_menu_system, UiStateDebugWindows};
@@ -101,8 +128,11 @@ pub use ui_debug_zone_lighting_system::ui_debug_zone_lighting_system;
 pub use ui_debug_zone_list_system::ui_debug_zone_list_system;
 pub use ui_debug_zone_time_system::ui_debug_zone_time_system;
 pub use ui_drag_and_drop_system::{ui_drag_and_drop_system, UiStateDragAndDrop};
+pub use ui_exit_system::ui_exit_system;
 pub use ui_game_menu_system::ui_game_menu_system;
+pub use ui_help_system::ui_help_system;
 pub use ui_hotbar_system::ui_hotbar_system;
+pub use ui_info_system::ui_info_system;
 pub use ui_inventory_system::ui_inventory_system;
 pub use ui_item_drop_name_system::ui_item_drop_name_system;
 pub use ui_login_system::ui_login_system;
@@ -115,13 +145,17 @@ pub use ui_party_system::ui_party_system;
 pub use ui_personal_store_system::ui_personal_store_system;
 pub use ui_player_info_system::ui_player_info_system;
 pub use ui_quest_list_system::ui_quest_list_system;
+pub use ui_quest_tracker_system::{ui_quest_tracker_system, UiStateQuestTracker};
+pub use ui_repair_system::{get_repair_cost, ui_repair_system};
 pub use ui_respawn_system::ui_respawn_system;
 pub use ui_selected_target_system::ui_selected_target_system;
+pub use ui_separate_system::ui_separate_system;
 pub use ui_server_select_system::ui_server_select_system;
 pub use ui_settings_system::ui_settings_system;
 pub use ui_skill_list_system::ui_skill_list_system;
 pub use ui_skill_tree_system::ui_skill_tree_system;
 pub use ui_sound_event_system::{ui_sound_event_system, UiSoundEvent};
 pub use ui_status_effects_system::ui_status_effects_system;
+pub use ui_upgrade_system::ui_upgrade_system;
 pub use ui_window_sound_system::ui_window_sound_system;
 pub use widgets::DataBindings;
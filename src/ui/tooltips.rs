@@ -5,12 +5,14 @@ use bevy::ecs::query::WorldQuery;
 use bevy_egui::egui;
 
 use rose_data::{
-    AbilityType, BaseItemData, EquipmentItem, Item, ItemClass, ItemGradeData, ItemType, JobId,
-    SkillAddAbility, SkillData, SkillId, SkillType, StackableItem, StatusEffectType,
+    AbilityType, BaseItemData, EquipmentIndex, EquipmentItem, Item, ItemClass, ItemGradeData,
+    ItemType, JobId, SkillAddAbility, SkillData, SkillId, SkillType, StackableItem,
+    StatusEffectType, VehicleItemData, VehiclePartIndex,
 };
 use rose_game_common::components::{
-    AbilityValues, CharacterInfo, Equipment, ExperiencePoints, HealthPoints, Inventory, Level,
-    ManaPoints, MoveSpeed, SkillList, SkillPoints, Stamina, StatPoints, Team, UnionMembership,
+    AbilityValues, BasicStats, CharacterInfo, Equipment, ExperiencePoints, HealthPoints, Inventory,
+    Level, ManaPoints, MoveMode, MoveSpeed, SkillList, SkillPoints, Stamina, StatPoints,
+    StatusEffects, Team, UnionMembership,
 };
 
 use crate::{bundles::ability_values_get_value, resources::GameData};
@@ -20,6 +22,7 @@ const TOOLTIP_MAX_WIDTH: f32 = 300.0;
 #[derive(WorldQuery)]
 pub struct PlayerTooltipQuery<'w> {
     pub ability_values: &'w AbilityValues,
+    pub basic_stats: &'w BasicStats,
     pub character_info: &'w CharacterInfo,
     pub experience_points: &'w ExperiencePoints,
     pub health_points: &'w HealthPoints,
@@ -32,10 +35,33 @@ pub struct PlayerTooltipQuery<'w> {
     pub skill_points: &'w SkillPoints,
     pub stamina: &'w Stamina,
     pub stat_points: &'w StatPoints,
+    pub status_effects: &'w StatusEffects,
     pub team: &'w Team,
     pub union_membership: &'w UnionMembership,
 }
 
+fn get_player_ability_value(
+    player: &PlayerTooltipQueryItem,
+    ability_type: AbilityType,
+) -> Option<i32> {
+    ability_values_get_value(
+        ability_type,
+        player.ability_values,
+        Some(player.character_info),
+        Some(player.experience_points),
+        Some(player.health_points),
+        Some(player.inventory),
+        Some(player.level),
+        Some(player.mana_points),
+        Some(player.move_speed),
+        Some(player.skill_points),
+        Some(player.stamina),
+        Some(player.stat_points),
+        Some(player.team),
+        Some(player.union_membership),
+    )
+}
+
 pub fn get_item_name_color(item_type: ItemType, item_data: &BaseItemData) -> egui::Color32 {
     match item_type {
         ItemType::Head
@@ -248,22 +274,7 @@ fn add_item_equip_requirement(
         let mut color = egui::Color32::RED;
 
         if let Some(player) = player {
-            if let Some(current_value) = ability_values_get_value(
-                ability_type,
-                player.ability_values,
-                Some(player.character_info),
-                Some(player.experience_points),
-                Some(player.health_points),
-                Some(player.inventory),
-                Some(player.level),
-                Some(player.mana_points),
-                Some(player.move_speed),
-                Some(player.skill_points),
-                Some(player.stamina),
-                Some(player.stat_points),
-                Some(player.team),
-                Some(player.union_membership),
-            ) {
+            if let Some(current_value) = get_player_ability_value(player, ability_type) {
                 if current_value >= value as i32 {
                     color = egui::Color32::GREEN;
                 }
@@ -281,6 +292,146 @@ fn add_item_equip_requirement(
     }
 }
 
+#[derive(Default)]
+struct VehicleStats {
+    move_speed: i32,
+    max_fuel: i32,
+    fuel_use_rate: i32,
+    attack_power: i32,
+    attack_speed: i32,
+    attack_range: i32,
+}
+
+impl VehicleStats {
+    fn add(&mut self, vehicle_item_data: &VehicleItemData) {
+        self.move_speed += vehicle_item_data.move_speed as i32;
+        self.max_fuel += vehicle_item_data.max_fuel as i32;
+        self.fuel_use_rate += vehicle_item_data.fuel_use_rate as i32;
+        self.attack_power += vehicle_item_data.attack_power as i32;
+        self.attack_speed += vehicle_item_data.attack_speed as i32;
+        self.attack_range += vehicle_item_data.attack_range as i32;
+    }
+}
+
+fn get_vehicle_part_index(item_class: ItemClass) -> Option<VehiclePartIndex> {
+    match item_class {
+        ItemClass::CartBody | ItemClass::CastleGearBody => Some(VehiclePartIndex::Body),
+        ItemClass::CartEngine | ItemClass::CastleGearEngine => Some(VehiclePartIndex::Engine),
+        ItemClass::CartWheels | ItemClass::CastleGearLeg => Some(VehiclePartIndex::Leg),
+        ItemClass::CartAccessory | ItemClass::CastleGearWeapon => Some(VehiclePartIndex::Arms),
+        _ => None,
+    }
+}
+
+fn is_castle_gear_item_class(item_class: ItemClass) -> bool {
+    matches!(
+        item_class,
+        ItemClass::CastleGearBody
+            | ItemClass::CastleGearEngine
+            | ItemClass::CastleGearLeg
+            | ItemClass::CastleGearWeapon
+    )
+}
+
+fn add_vehicle_stats(ui: &mut egui::Ui, game_data: &GameData, stats: &VehicleStats) {
+    if stats.attack_power > 0 {
+        ui.label(format!(
+            "{}:{} {}:{} {}:{}M",
+            game_data
+                .string_database
+                .get_ability_type(AbilityType::Attack),
+            stats.attack_power,
+            game_data
+                .string_database
+                .get_ability_type(AbilityType::AttackSpeed),
+            stats.attack_speed,
+            game_data.client_strings.item_attack_range,
+            stats.attack_range / 100
+        ));
+    }
+
+    if stats.move_speed > 0 {
+        ui.label(format!(
+            "[{} {}]",
+            game_data.client_strings.item_move_speed, stats.move_speed
+        ));
+    }
+
+    if stats.max_fuel > 0 {
+        ui.label(format!("[Fuel Capacity {}]", stats.max_fuel));
+    }
+
+    if stats.fuel_use_rate > 0 {
+        ui.label(format!("[Fuel Usage {}]", stats.fuel_use_rate));
+    }
+}
+
+fn add_vehicle_item_stats(
+    ui: &mut egui::Ui,
+    game_data: &GameData,
+    player: Option<&PlayerTooltipQueryItem>,
+    equipment_item: &EquipmentItem,
+    item_data: &BaseItemData,
+) {
+    let Some(vehicle_item_data) = game_data
+        .items
+        .get_vehicle_item(equipment_item.item.item_number)
+    else {
+        return;
+    };
+
+    let mut part_stats = VehicleStats::default();
+    part_stats.add(vehicle_item_data);
+    add_vehicle_stats(ui, game_data, &part_stats);
+
+    // Show what the whole vehicle would be with this part swapped in
+    let (Some(player), Some(vehicle_part_index)) =
+        (player, get_vehicle_part_index(item_data.class))
+    else {
+        return;
+    };
+    let castle_gear = is_castle_gear_item_class(item_data.class);
+    let mut total_stats = VehicleStats::default();
+
+    for part_index in [
+        VehiclePartIndex::Body,
+        VehiclePartIndex::Engine,
+        VehiclePartIndex::Leg,
+        VehiclePartIndex::Arms,
+    ] {
+        let part_item = if part_index == vehicle_part_index {
+            Some(equipment_item)
+        } else {
+            player.equipment.get_vehicle_item(part_index)
+        };
+
+        if let Some(part_vehicle_item_data) = part_item
+            .filter(|part_item| {
+                game_data
+                    .items
+                    .get_base_item(part_item.item)
+                    .map_or(false, |part_item_data| {
+                        is_castle_gear_item_class(part_item_data.class) == castle_gear
+                    })
+            })
+            .and_then(|part_item| game_data.items.get_vehicle_item(part_item.item.item_number))
+        {
+            total_stats.add(part_vehicle_item_data);
+        }
+    }
+
+    ui.separator();
+    ui.colored_label(
+        egui::Color32::YELLOW,
+        if castle_gear {
+            "Castle Gear Total"
+        } else {
+            "Cart Total"
+        },
+    );
+    add_vehicle_stats(ui, game_data, &total_stats);
+}
+
 fn add_item_description(ui: &mut egui::Ui, game_data: &GameData, item_data: &BaseItemData) {
     ui.label(format!(
         "{}:{}",
@@ -516,7 +667,10 @@ pub fn ui_add_item_tooltip(
                         item_data.quality
                     ));
 
-                    // TODO: Vehicle tooltip
+                    add_equipment_item_life_durability(ui, game_data, equipment_item);
+                    add_vehicle_item_stats(ui, game_data, player, equipment_item, item_data);
+                    add_item_add_ability(ui, game_data, item_data);
+                    add_item_equip_requirement(ui, game_data, player, item_data);
                     add_item_description(ui, game_data, item_data);
                 }
                 _ => panic!("Unexpected item type"),
@@ -541,16 +695,75 @@ pub fn ui_add_item_tooltip(
 
                     match item_data.class {
                         ItemClass::EngineFuel => {
-                            // TODO: Tooltip for ItemClass::EngineFuel
+                            if let Some((_, value)) =
+                                use_item_data.and_then(|data| data.add_ability.as_ref())
+                            {
+                                ui.label(format!("[Fuel +{}]", value));
+                            }
+
+                            if let Some(max_fuel) = player
+                                .and_then(|player| {
+                                    player.equipment.get_vehicle_item(VehiclePartIndex::Engine)
+                                })
+                                .and_then(|engine| {
+                                    game_data.items.get_vehicle_item(engine.item.item_number)
+                                })
+                                .map(|engine_item_data| engine_item_data.max_fuel)
+                            {
+                                ui.label(format!("[Engine Fuel Capacity {}]", max_fuel));
+                            }
                         }
                         ItemClass::SkillBook => {
-                            // TODO: Tooltip for ItemClass::SkillBook
+                            if let Some(skill_id) =
+                                use_item_data.and_then(|data| data.learn_skill_id)
+                            {
+                                ui.separator();
+                                ui_add_skill_tooltip(
+                                    ui,
+                                    SkillTooltipType::Detailed,
+                                    game_data,
+                                    player,
+                                    skill_id,
+                                );
+                                ui.separator();
+                            }
                         }
                         ItemClass::MagicItem => {
-                            // TODO: Tooltip for ItemClass::MagicItem
+                            if let Some(skill_data) = use_item_data
+                                .and_then(|data| data.use_skill_id)
+                                .and_then(|skill_id| game_data.skills.get_skill(skill_id))
+                            {
+                                add_skill_type_and_target(ui, game_data, skill_data);
+                                if skill_data.power > 0 {
+                                    add_skill_power(ui, game_data, skill_data);
+                                }
+                                add_skill_cast_range(ui, game_data, skill_data);
+                                add_skill_aoe_range(ui, game_data, skill_data);
+                                add_skill_status_effects(ui, game_data, player, skill_data);
+                            }
                         }
                         ItemClass::RepairTool => {
-                            // TODO: Tooltip for ItemClass::RepairTool
+                            let damaged_items = player.map(|player| {
+                                player
+                                    .equipment
+                                    .equipped_items
+                                    .iter()
+                                    .filter_map(|(_, item)| item.as_ref())
+                                    .filter(|item| item.life < 1000)
+                                    .count()
+                            });
+
+                            ui.label("[Restores the life of a damaged equipment item]");
+                            if let Some(damaged_items) = damaged_items {
+                                ui.colored_label(
+                                    if damaged_items > 0 {
+                                        egui::Color32::GREEN
+                                    } else {
+                                        egui::Color32::GRAY
+                                    },
+                                    format!("[Damaged equipment: {}]", damaged_items),
+                                );
+                            }
                         }
                         _ => {
                             if let Some(use_item_data) = use_item_data {
@@ -616,6 +829,347 @@ pub fn ui_add_item_tooltip(
     }
 }
 
+/// The equipment slot an item would be equipped into.
+#[derive(Copy, Clone)]
+enum CompareSlot {
+    Equipment(EquipmentIndex),
+    Vehicle(VehiclePartIndex),
+}
+
+fn get_compare_slot(
+    equipment_item: &EquipmentItem,
+    item_data: &BaseItemData,
+) -> Option<CompareSlot> {
+    Some(CompareSlot::Equipment(
+        match equipment_item.item.item_type {
+            ItemType::Face => EquipmentIndex::Face,
+            ItemType::Head => EquipmentIndex::Head,
+            ItemType::Body => EquipmentIndex::Body,
+            ItemType::Hands => EquipmentIndex::Hands,
+            ItemType::Feet => EquipmentIndex::Feet,
+            ItemType::Back => EquipmentIndex::Back,
+            ItemType::Weapon => EquipmentIndex::Weapon,
+            ItemType::SubWeapon => EquipmentIndex::SubWeapon,
+            ItemType::Jewellery => match item_data.class {
+                ItemClass::Ring => EquipmentIndex::Ring,
+                ItemClass::Necklace => EquipmentIndex::Necklace,
+                ItemClass::Earring => EquipmentIndex::Earring,
+                _ => return None,
+            },
+            ItemType::Vehicle => {
+                return Some(CompareSlot::Vehicle(get_vehicle_part_index(
+                    item_data.class,
+                )?))
+            }
+            _ => return None,
+        },
+    ))
+}
+
+fn get_compare_equipped_item<'a>(
+    player: &'a PlayerTooltipQueryItem,
+    compare_slot: CompareSlot,
+) -> Option<&'a EquipmentItem> {
+    match compare_slot {
+        CompareSlot::Equipment(equipment_index) => {
+            player.equipment.get_equipment_item(equipment_index)
+        }
+        CompareSlot::Vehicle(vehicle_part_index) => {
+            player.equipment.get_vehicle_item(vehicle_part_index)
+        }
+    }
+}
+
+/// The player's ability values and move speed when wearing `equipment`.
+struct CompareAbilityValues {
+    ability_values: AbilityValues,
+    move_speed: f32,
+}
+
+impl CompareAbilityValues {
+    fn new(
+        game_data: &GameData,
+        player: &PlayerTooltipQueryItem,
+        equipment: &Equipment,
+        compare_slot: CompareSlot,
+    ) -> Self {
+        let mut ability_values = game_data.ability_value_calculator.calculate(
+            player.character_info,
+            player.level,
+            equipment,
+            player.basic_stats,
+            player.skill_list,
+            player.status_effects,
+        );
+
+        // Vehicle parts only affect our stats whilst driving
+        let move_mode = match compare_slot {
+            CompareSlot::Equipment(_) => MoveMode::Run,
+            CompareSlot::Vehicle(_) => MoveMode::Drive,
+        };
+        ability_values.is_driving = matches!(move_mode, MoveMode::Drive);
+        let move_speed = ability_values.get_move_speed(&move_mode);
+
+        Self {
+            ability_values,
+            move_speed,
+        }
+    }
+
+    /// The abilities which equipment can change, others are not compared.
+    fn get_value(&self, ability_type: AbilityType) -> Option<i32> {
+        let ability_values = &self.ability_values;
+        match ability_type {
+            AbilityType::Strength => Some(ability_values.get_strength()),
+            AbilityType::Dexterity => Some(ability_values.get_dexterity()),
+            AbilityType::Intelligence => Some(ability_values.get_intelligence()),
+            AbilityType::Concentration => Some(ability_values.get_concentration()),
+            AbilityType::Charm => Some(ability_values.get_charm()),
+            AbilityType::Sense => Some(ability_values.get_sense()),
+            AbilityType::Critical => Some(ability_values.get_critical()),
+            AbilityType::MaxHealth => Some(ability_values.get_max_health()),
+            AbilityType::MaxMana => Some(ability_values.get_max_mana()),
+            AbilityType::SaveMana => Some(ability_values.get_save_mana()),
+            _ => None,
+        }
+    }
+}
+
+fn add_compare_delta(ui: &mut egui::Ui, name: &str, current: i32, compare: i32) {
+    let delta = compare - current;
+    if delta == 0 {
+        return;
+    }
+
+    ui.colored_label(
+        if delta > 0 {
+            egui::Color32::GREEN
+        } else {
+            egui::Color32::RED
+        },
+        format!("[{} {} -> {} ({:+})]", name, current, compare, delta),
+    );
+}
+
+// Abilities added by the item, or by a socketed gem when it is visible.
+fn get_item_add_ability_types(
+    game_data: &GameData,
+    equipment_item: &EquipmentItem,
+    item_data: &BaseItemData,
+) -> Vec<AbilityType> {
+    let mut ability_types: Vec<AbilityType> = item_data
+        .add_ability
+        .iter()
+        .map(|&(ability_type, _)| ability_type)
+        .collect();
+
+    if equipment_item.gem != 0 && (equipment_item.gem > 300 || equipment_item.is_appraised) {
+        if let Some(gem_item_data) = game_data.items.get_gem_item(equipment_item.gem as usize) {
+            ability_types.extend(
+                gem_item_data
+                    .gem_add_ability
+                    .iter()
+                    .map(|&(ability_type, _)| ability_type),
+            );
+        }
+    }
+
+    ability_types
+}
+
+fn add_item_compare_deltas(
+    ui: &mut egui::Ui,
+    game_data: &GameData,
+    player: &PlayerTooltipQueryItem,
+    compare_slot: CompareSlot,
+    (equipment_item, item_data): (&EquipmentItem, &BaseItemData),
+    (equipped_item, equipped_item_data): (&EquipmentItem, &BaseItemData),
+) {
+    let string_database = &game_data.string_database;
+
+    let mut compare_equipment = player.equipment.clone();
+    match compare_slot {
+        CompareSlot::Equipment(equipment_index) => {
+            compare_equipment.equipped_items[equipment_index] = Some(equipment_item.clone());
+        }
+        CompareSlot::Vehicle(vehicle_part_index) => {
+            compare_equipment.equipped_vehicle[vehicle_part_index] = Some(equipment_item.clone());
+        }
+    }
+
+    let current = CompareAbilityValues::new(game_data, player, player.equipment, compare_slot);
+    let compare = CompareAbilityValues::new(game_data, player, &compare_equipment, compare_slot);
+
+    ui.separator();
+    ui.colored_label(egui::Color32::YELLOW, "Compared to equipped:");
+
+    add_compare_delta(
+        ui,
+        string_database.get_ability_type(AbilityType::Attack),
+        current.ability_values.get_attack_power(),
+        compare.ability_values.get_attack_power(),
+    );
+    add_compare_delta(
+        ui,
+        string_database.get_ability_type(AbilityType::Hit),
+        current.ability_values.get_hit(),
+        compare.ability_values.get_hit(),
+    );
+    add_compare_delta(
+        ui,
+        string_database.get_ability_type(AbilityType::AttackSpeed),
+        current.ability_values.get_attack_speed(),
+        compare.ability_values.get_attack_speed(),
+    );
+    add_compare_delta(
+        ui,
+        game_data.client_strings.item_attack_range,
+        current.ability_values.get_attack_range() / 100,
+        compare.ability_values.get_attack_range() / 100,
+    );
+    add_compare_delta(
+        ui,
+        string_database.get_ability_type(AbilityType::Defence),
+        current.ability_values.get_defence(),
+        compare.ability_values.get_defence(),
+    );
+    add_compare_delta(
+        ui,
+        string_database.get_ability_type(AbilityType::Resistance),
+        current.ability_values.get_resistance(),
+        compare.ability_values.get_resistance(),
+    );
+    add_compare_delta(
+        ui,
+        string_database.get_ability_type(AbilityType::Avoid),
+        current.ability_values.get_avoid(),
+        compare.ability_values.get_avoid(),
+    );
+    add_compare_delta(
+        ui,
+        game_data.client_strings.item_move_speed,
+        current.move_speed as i32,
+        compare.move_speed as i32,
+    );
+
+    let mut ability_types = Vec::new();
+    for ability_type in get_item_add_ability_types(game_data, equipment_item, item_data)
+        .into_iter()
+        .chain(get_item_add_ability_types(
+            game_data,
+            equipped_item,
+            equipped_item_data,
+        ))
+    {
+        if !ability_types.contains(&ability_type) {
+            ability_types.push(ability_type);
+        }
+    }
+
+    for ability_type in ability_types {
+        if let (Some(current_value), Some(compare_value)) = (
+            current.get_value(ability_type),
+            compare.get_value(ability_type),
+        ) {
+            add_compare_delta(
+                ui,
+                string_database.get_ability_type(ability_type),
+                current_value,
+                compare_value,
+            );
+        }
+    }
+
+    for &(ability_type, value) in item_data.equip_ability_requirement.iter() {
+        let equipped_value = equipped_item_data
+            .equip_ability_requirement
+            .iter()
+            .find(|(equipped_ability_type, _)| *equipped_ability_type == ability_type)
+            .map_or(0, |(_, value)| *value as i32);
+        let delta = value as i32 - equipped_value;
+        if delta == 0 {
+            continue;
+        }
+
+        ui.colored_label(
+            if delta < 0 {
+                egui::Color32::GREEN
+            } else {
+                egui::Color32::RED
+            },
+            format!(
+                "[{} {} {} ({:+})]",
+                game_data.client_strings.skill_require_ability,
+                string_database.get_ability_type(ability_type),
+                value,
+                delta
+            ),
+        );
+    }
+}
+
+/// Shows the item tooltip next to the tooltip for the item equipped in the same
+/// slot, along with how the player's stats would change by equipping it.
+pub fn ui_add_item_compare_tooltip(
+    ui: &mut egui::Ui,
+    game_data: &GameData,
+    player: Option<&PlayerTooltipQueryItem>,
+    item: &Item,
+) {
+    let compare = player.and_then(|player| {
+        let equipment_item = item.as_equipment()?;
+        let item_data = game_data.items.get_base_item(equipment_item.item)?;
+        let compare_slot = get_compare_slot(equipment_item, item_data)?;
+        let equipped_item = get_compare_equipped_item(player, compare_slot)?;
+        let equipped_item_data = game_data.items.get_base_item(equipped_item.item)?;
+
+        Some((
+            player,
+            compare_slot,
+            (equipment_item, item_data),
+            (equipped_item, equipped_item_data),
+        ))
+    });
+
+    let Some((
+        player,
+        compare_slot,
+        (equipment_item, item_data),
+        (equipped_item, equipped_item_data),
+    )) = compare
+    else {
+        ui_add_item_tooltip(ui, game_data, player, item);
+        return;
+    };
+
+    ui.set_max_width(TOOLTIP_MAX_WIDTH * 2.0 + ui.spacing().item_spacing.x * 2.0);
+    ui.horizontal_top(|ui| {
+        ui.vertical(|ui| {
+            ui_add_item_tooltip(ui, game_data, Some(player), item);
+            add_item_compare_deltas(
+                ui,
+                game_data,
+                player,
+                compare_slot,
+                (equipment_item, item_data),
+                (equipped_item, equipped_item_data),
+            );
+        });
+
+        ui.separator();
+
+        ui.vertical(|ui| {
+            ui.colored_label(egui::Color32::GRAY, "Currently Equipped");
+            ui_add_item_tooltip(
+                ui,
+                game_data,
+                Some(player),
+                &Item::Equipment(equipped_item.clone()),
+            );
+        });
+    });
+}
+
 fn add_skill_name(ui: &mut egui::Ui, game_data: &GameData, skill_data: &SkillData) {
     let text = if skill_data.name.is_empty() {
         format!("??? [Skill ID: {}]", skill_data.id.get())
@@ -745,22 +1299,7 @@ fn add_skill_require_ability(
         let mut color = egui::Color32::RED;
 
         if let Some(player) = player {
-            if let Some(current_value) = ability_values_get_value(
-                ability_type,
-                player.ability_values,
-                Some(player.character_info),
-                Some(player.experience_points),
-                Some(player.health_points),
-                Some(player.inventory),
-                Some(player.level),
-                Some(player.mana_points),
-                Some(player.move_speed),
-                Some(player.skill_points),
-                Some(player.stamina),
-                Some(player.stat_points),
-                Some(player.team),
-                Some(player.union_membership),
-            ) {
+            if let Some(current_value) = get_player_ability_value(player, ability_type) {
                 if current_value >= value {
                     color = egui::Color32::GREEN;
                 }
@@ -1109,22 +1648,7 @@ fn add_skill_use_ability_value(
                 value = (value as f32 * use_mana_rate) as i32;
             }
 
-            if let Some(current_value) = ability_values_get_value(
-                ability_type,
-                player.ability_values,
-                Some(player.character_info),
-                Some(player.experience_points),
-                Some(player.health_points),
-                Some(player.inventory),
-                Some(player.level),
-                Some(player.mana_points),
-                Some(player.move_speed),
-                Some(player.skill_points),
-                Some(player.stamina),
-                Some(player.stat_points),
-                Some(player.team),
-                Some(player.union_membership),
-            ) {
+            if let Some(current_value) = get_player_ability_value(player, ability_type) {
                 if current_value >= value {
                     color = egui::Color32::GREEN;
                 }
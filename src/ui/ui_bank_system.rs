@@ -16,7 +16,7 @@ use crate::{
     resources::{ClientEntityList, GameConnection, GameData, UiResources},
     ui::{
         tooltips::{PlayerTooltipQuery, PlayerTooltipQueryItem},
-        ui_add_item_tooltip,
+        ui_add_item_compare_tooltip,
         widgets::{DataBindings, Dialog},
         DragAndDropId, DragAndDropSlot, UiSoundEvent, UiStateDragAndDrop, UiStateWindows,
     },
@@ -100,7 +100,7 @@ fn ui_add_bank_slot(
 
     if let Some(item) = item {
         response.on_hover_ui(|ui| {
-            ui_add_item_tooltip(ui, game_data, player_tooltip_data, item);
+            ui_add_item_compare_tooltip(ui, game_data, player_tooltip_data, item);
         });
     }
 
@@ -1,12 +1,11 @@
 use bevy::prelude::{Assets, EventReader, EventWriter, Local, Res};
 use bevy_egui::{egui, EguiContexts};
 
-use rose_game_common::messages::client::ClientMessage;
-
 use crate::{
     events::ChatboxEvent,
-    resources::{GameConnection, UiResources},
+    resources::UiResources,
     ui::{
+        chat_commands::{parse_chat_input, ChatCommandContext, ChatCommands, ChatInput},
         widgets::{DataBindings, Dialog},
         UiSoundEvent,
     },
@@ -62,6 +61,7 @@ pub struct UiStateChatbox {
     textbox_layout_job: egui::text::LayoutJob,
     cleanup_layout_text_counter: usize,
     selected_channel: i32,
+    pending_system_messages: Vec<String>,
 }
 
 impl Default for UiStateChatbox {
@@ -71,6 +71,7 @@ impl Default for UiStateChatbox {
             textbox_layout_job: Default::default(),
             cleanup_layout_text_counter: 0,
             selected_channel: IID_BTN_ALL,
+            pending_system_messages: Vec::new(),
         }
     }
 }
@@ -79,7 +80,8 @@ pub fn ui_chatbox_system(
     mut egui_context: EguiContexts,
     mut ui_state_chatbox: Local<UiStateChatbox>,
     mut chatbox_events: EventReader<ChatboxEvent>,
-    game_connection: Option<Res<GameConnection>>,
+    mut chat_command_context: ChatCommandContext,
+    chat_commands: Res<ChatCommands>,
     ui_resources: Res<UiResources>,
     mut ui_sound_events: EventWriter<UiSoundEvent>,
     dialog_assets: Res<Assets<Dialog>>,
@@ -94,7 +96,23 @@ pub fn ui_chatbox_system(
     let local_time = chrono::Local::now();
     let timestamp = local_time.format("%H:%M:%S");
 
-    for event in chatbox_events.iter() {
+    // Command output and errors from the previous frame
+    let pending_events: Vec<ChatboxEvent> = ui_state_chatbox
+        .pending_system_messages
+        .drain(..)
+        .map(ChatboxEvent::System)
+        .collect();
+
+    for event in chatbox_events.iter().chain(pending_events.iter()) {
+        if let ChatboxEvent::Say(name, _)
+        | ChatboxEvent::Shout(name, _)
+        | ChatboxEvent::Whisper(name, _) = event
+        {
+            if chat_command_context.ignore_list.contains(name) {
+                continue;
+            }
+        }
+
         if ui_state_chatbox.textbox_layout_job.sections.len() == MAX_CHATBOX_ENTRIES {
             ui_state_chatbox.textbox_layout_job.sections.remove(0);
             ui_state_chatbox.cleanup_layout_text_counter += 1;
@@ -294,16 +312,31 @@ pub fn ui_chatbox_system(
             .input(|input| input.key_pressed(egui::Key::Enter))
         {
             if response.lost_focus() {
-                if !ui_state_chatbox.textbox_text.is_empty() {
-                    // TODO: Parse text line to decide whether its chat, shout, etc
-                    if let Some(game_connection) = game_connection.as_ref() {
-                        game_connection
-                            .client_message_tx
-                            .send(ClientMessage::Chat {
-                                text: ui_state_chatbox.textbox_text.clone(),
-                            })
-                            .ok();
-                        ui_state_chatbox.textbox_text.clear();
+                if !ui_state_chatbox.textbox_text.trim().is_empty() {
+                    let result = match parse_chat_input(&ui_state_chatbox.textbox_text) {
+                        Ok(ChatInput::Command { name, args }) => {
+                            chat_commands.run(&mut chat_command_context, name, &args)
+                        }
+                        Ok(chat_input) => {
+                            if let Some(game_connection) =
+                                chat_command_context.game_connection.as_ref()
+                            {
+                                chat_input.send(game_connection);
+                            }
+                            Ok(None)
+                        }
+                        Err(error) => Err(error),
+                    };
+
+                    match result {
+                        Ok(output) => {
+                            ui_state_chatbox.pending_system_messages.extend(output);
+                            ui_state_chatbox.textbox_text.clear();
+                        }
+                        Err(error) => {
+                            // Keep the text so the user can correct it
+                            ui_state_chatbox.pending_system_messages.push(error);
+                        }
                     }
                 }
             } else {
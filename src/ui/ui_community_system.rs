@@ -0,0 +1,193 @@
+use bevy::prelude::{Assets, EventWriter, Local, Query, Res, ResMut};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    components::{ClientEntity, ClientEntityName, ClientEntityType},
+    resources::{ChatIgnoreList, FriendList, UiResources},
+    ui::{
+        widgets::{DataBindings, Dialog},
+        UiSoundEvent, UiStateWindows,
+    },
+};
+
+const IID_BTN_CLOSE: i32 = 10;
+
+// Area of the dialog below the title bar which we draw the lists into
+const CONTENT_OFFSET: egui::Vec2 = egui::vec2(10.0, 45.0);
+const CONTENT_MARGIN_BOTTOM: f32 = 30.0;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum CommunityTab {
+    Friends,
+    Blocked,
+}
+
+pub struct UiStateCommunity {
+    tab: CommunityTab,
+    add_name: String,
+}
+
+impl Default for UiStateCommunity {
+    fn default() -> Self {
+        Self {
+            tab: CommunityTab::Friends,
+            add_name: String::new(),
+        }
+    }
+}
+
+pub fn ui_community_system(
+    mut egui_context: EguiContexts,
+    mut ui_state: Local<UiStateCommunity>,
+    mut ui_state_windows: ResMut<UiStateWindows>,
+    mut ui_sound_events: EventWriter<UiSoundEvent>,
+    ui_resources: Res<UiResources>,
+    dialog_assets: Res<Assets<Dialog>>,
+    mut friend_list: ResMut<FriendList>,
+    mut chat_ignore_list: ResMut<ChatIgnoreList>,
+    query_characters: Query<(&ClientEntity, &ClientEntityName)>,
+) {
+    let dialog = if let Some(dialog) = dialog_assets.get(&ui_resources.dialog_community) {
+        dialog
+    } else {
+        return;
+    };
+
+    let ui_state = &mut *ui_state;
+    let mut response_close_button = None;
+
+    egui::Window::new("Community")
+        .frame(egui::Frame::none())
+        .open(&mut ui_state_windows.community_open)
+        .title_bar(false)
+        .resizable(false)
+        .default_width(dialog.width)
+        .default_height(dialog.height)
+        .show(egui_context.ctx_mut(), |ui| {
+            dialog.draw(
+                ui,
+                DataBindings {
+                    sound_events: Some(&mut ui_sound_events),
+                    response: &mut [(IID_BTN_CLOSE, &mut response_close_button)],
+                    ..Default::default()
+                },
+                |ui, _| {
+                    let content_rect = egui::Rect::from_min_size(
+                        ui.min_rect().min + CONTENT_OFFSET,
+                        egui::vec2(
+                            dialog.width - CONTENT_OFFSET.x * 2.0,
+                            dialog.height - CONTENT_OFFSET.y - CONTENT_MARGIN_BOTTOM,
+                        ),
+                    );
+
+                    ui.allocate_ui_at_rect(content_rect, |ui| {
+                        ui.spacing_mut().item_spacing = egui::vec2(4.0, 4.0);
+
+                        ui.horizontal(|ui| {
+                            ui.selectable_value(
+                                &mut ui_state.tab,
+                                CommunityTab::Friends,
+                                "Friends",
+                            );
+                            ui.selectable_value(
+                                &mut ui_state.tab,
+                                CommunityTab::Blocked,
+                                "Blocked",
+                            );
+                        });
+                        ui.separator();
+
+                        let mut remove_name = None;
+                        egui::ScrollArea::vertical()
+                            .max_height(content_rect.height() - 60.0)
+                            .auto_shrink([false, false])
+                            .show(ui, |ui| match ui_state.tab {
+                                CommunityTab::Friends => {
+                                    for name in friend_list.iter() {
+                                        ui.horizontal(|ui| {
+                                            // We only know about characters which are nearby
+                                            let nearby = query_characters.iter().any(
+                                                |(client_entity, client_entity_name)| {
+                                                    client_entity.entity_type
+                                                        == ClientEntityType::Character
+                                                        && client_entity_name
+                                                            .name
+                                                            .eq_ignore_ascii_case(name)
+                                                },
+                                            );
+
+                                            if nearby {
+                                                ui.colored_label(egui::Color32::GREEN, name);
+                                            } else {
+                                                ui.colored_label(egui::Color32::GRAY, name);
+                                            }
+
+                                            if ui.small_button("Remove").clicked() {
+                                                remove_name = Some(name.to_string());
+                                            }
+                                        });
+                                    }
+                                }
+                                CommunityTab::Blocked => {
+                                    for name in chat_ignore_list.iter() {
+                                        ui.horizontal(|ui| {
+                                            ui.label(name);
+
+                                            if ui.small_button("Unblock").clicked() {
+                                                remove_name = Some(name.to_string());
+                                            }
+                                        });
+                                    }
+                                }
+                            });
+
+                        if let Some(name) = remove_name {
+                            match ui_state.tab {
+                                CommunityTab::Friends => {
+                                    friend_list.remove(&name);
+                                }
+                                CommunityTab::Blocked => {
+                                    chat_ignore_list.remove(&name);
+                                }
+                            }
+                        }
+
+                        ui.separator();
+                        ui.horizontal(|ui| {
+                            let response = ui.add(
+                                egui::TextEdit::singleline(&mut ui_state.add_name)
+                                    .desired_width(content_rect.width() - 60.0),
+                            );
+                            let submitted = response.lost_focus()
+                                && ui.input(|input| input.key_pressed(egui::Key::Enter));
+
+                            let add_text = match ui_state.tab {
+                                CommunityTab::Friends => "Add",
+                                CommunityTab::Blocked => "Block",
+                            };
+                            if ui.button(add_text).clicked() || submitted {
+                                let name = ui_state.add_name.trim();
+
+                                if !name.is_empty() {
+                                    match ui_state.tab {
+                                        CommunityTab::Friends => {
+                                            friend_list.add(name);
+                                        }
+                                        CommunityTab::Blocked => {
+                                            chat_ignore_list.add(name);
+                                        }
+                                    }
+                                }
+
+                                ui_state.add_name.clear();
+                            }
+                        });
+                    });
+                },
+            );
+        });
+
+    if response_close_button.map_or(false, |r| r.clicked()) {
+        ui_state_windows.community_open = false;
+    }
+}
@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+
+use bevy::prelude::{Local, ResMut};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    resources::ConversationTrace,
+    scripting::lua4::{Lua4TraceEntry, Lua4Value},
+    ui::UiStateDebugWindows,
+};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+enum ConversationTraceTab {
+    #[default]
+    Messages,
+    Functions,
+    Calls,
+    Instructions,
+}
+
+#[derive(Default)]
+pub struct UiStateDebugConversationTrace {
+    tab: ConversationTraceTab,
+    entry_index: usize,
+    show_rust_closure_globals: bool,
+}
+
+fn format_value(value: &Lua4Value) -> String {
+    match value {
+        Lua4Value::Nil => "nil".to_string(),
+        Lua4Value::UserData(_) => "userdata".to_string(),
+        Lua4Value::Number(number) => format!("{}", number),
+        Lua4Value::String(string) => format!("{:?}", string),
+        Lua4Value::Table(table) => format!("table ({} entries)", table.lock().unwrap().len()),
+        Lua4Value::Closure(function, _) => format!("function (line {})", function.line),
+        Lua4Value::RustClosure(name) => format!("rust function {}", name),
+    }
+}
+
+fn format_values(values: &[Lua4Value]) -> String {
+    values
+        .iter()
+        .map(format_value)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_results(results: &Result<Vec<Lua4Value>, String>) -> String {
+    match results {
+        Ok(results) => format!("[{}]", format_values(results)),
+        Err(error) => format!("error: {}", error),
+    }
+}
+
+fn format_trace_entry(entry: &Lua4TraceEntry) -> String {
+    match entry {
+        Lua4TraceEntry::CallGlobal { name, parameters } => {
+            format!("call {}({})", name, format_values(parameters))
+        }
+        Lua4TraceEntry::ReturnGlobal { name, results } => {
+            format!("return {} = {}", name, format_results(results))
+        }
+        Lua4TraceEntry::Instruction {
+            function_line,
+            pc,
+            instruction,
+            ..
+        } => format!("[line {} pc {:03}] {:?}", function_line, pc, instruction),
+        Lua4TraceEntry::RustClosure {
+            name,
+            parameters,
+            results,
+        } => format!(
+            "{}({}) = {}",
+            name,
+            format_values(parameters),
+            format_results(results)
+        ),
+    }
+}
+
+fn draw_values(ui: &mut egui::Ui, id: &str, values: &[Lua4Value]) {
+    if values.is_empty() {
+        ui.label("Empty");
+        return;
+    }
+
+    egui::Grid::new(id)
+        .num_columns(2)
+        .striped(true)
+        .show(ui, |ui| {
+            for (index, value) in values.iter().enumerate() {
+                ui.label(format!("{}", index));
+                ui.label(format_value(value));
+                ui.end_row();
+            }
+        });
+}
+
+pub fn ui_debug_conversation_trace_system(
+    mut egui_context: EguiContexts,
+    mut ui_state_debug_windows: ResMut<UiStateDebugWindows>,
+    mut ui_state: Local<UiStateDebugConversationTrace>,
+    mut conversation_trace: ResMut<ConversationTrace>,
+) {
+    let ui_state = &mut *ui_state;
+    let enabled =
+        ui_state_debug_windows.debug_ui_open && ui_state_debug_windows.conversation_trace_open;
+    if conversation_trace.enabled != enabled {
+        conversation_trace.enabled = enabled;
+    }
+
+    // Only copy the stack for every instruction whilst the trace can be looked at,
+    // a conversation which is still running keeps using the same trace.
+    {
+        let mut trace = conversation_trace.trace.lock().unwrap();
+        if trace.capture_stack != enabled {
+            trace.capture_stack = enabled;
+        }
+    }
+
+    if !ui_state_debug_windows.debug_ui_open {
+        return;
+    }
+
+    egui::Window::new("Conversation Trace")
+        .resizable(true)
+        .default_width(600.0)
+        .default_height(400.0)
+        .open(&mut ui_state_debug_windows.conversation_trace_open)
+        .show(egui_context.ctx_mut(), |ui| {
+            let trace = conversation_trace.trace.lock().unwrap();
+            let entries = trace.entries();
+
+            if conversation_trace.con_file_path.is_empty() {
+                ui.label("Open a conversation to record its script execution.");
+                return;
+            }
+
+            ui.label(format!(
+                "{}, {} trace entries ({} discarded)",
+                conversation_trace.con_file_path,
+                entries.len(),
+                trace.num_discarded()
+            ));
+
+            ui.horizontal(|ui| {
+                ui.selectable_value(
+                    &mut ui_state.tab,
+                    ConversationTraceTab::Messages,
+                    "Messages",
+                );
+                ui.selectable_value(
+                    &mut ui_state.tab,
+                    ConversationTraceTab::Functions,
+                    "Functions",
+                );
+                ui.selectable_value(&mut ui_state.tab, ConversationTraceTab::Calls, "Calls");
+                ui.selectable_value(
+                    &mut ui_state.tab,
+                    ConversationTraceTab::Instructions,
+                    "Instructions",
+                );
+            });
+            ui.separator();
+
+            match ui_state.tab {
+                ConversationTraceTab::Messages => {
+                    // The most recent result of each condition function, a message is
+                    // only shown when its condition function returns non-zero.
+                    let mut condition_results = HashMap::new();
+                    for entry in entries.iter() {
+                        if let Lua4TraceEntry::ReturnGlobal { name, results } = entry {
+                            condition_results.insert(name.as_str(), results);
+                        }
+                    }
+
+                    egui::ScrollArea::both().show(ui, |ui| {
+                        egui::Grid::new("conversation_trace_messages")
+                            .num_columns(7)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.label("Menu");
+                                ui.label("Type");
+                                ui.label("Text");
+                                ui.label("Condition");
+                                ui.label("Result");
+                                ui.label("Action");
+                                ui.label("Value");
+                                ui.end_row();
+
+                                for message in conversation_trace.messages.iter() {
+                                    if let Some(menu_index) = message.menu_index {
+                                        ui.label(format!("{}", menu_index));
+                                    } else {
+                                        ui.label("Initial");
+                                    }
+                                    ui.label(message.message_type);
+                                    ui.label(
+                                        message.text.as_deref().unwrap_or("Missing LTB string"),
+                                    )
+                                    .on_hover_text(format!("String {}", message.string_id));
+                                    ui.label(&message.condition_function);
+
+                                    if message.condition_function.is_empty() {
+                                        ui.label("");
+                                    } else {
+                                        match condition_results
+                                            .get(message.condition_function.as_str())
+                                        {
+                                            Some(Ok(results)) => {
+                                                let passed = results
+                                                    .first()
+                                                    .and_then(|value| value.to_i32().ok())
+                                                    .unwrap_or(0)
+                                                    != 0;
+                                                ui.colored_label(
+                                                    if passed {
+                                                        egui::Color32::GREEN
+                                                    } else {
+                                                        egui::Color32::RED
+                                                    },
+                                                    format!("[{}]", format_values(results)),
+                                                );
+                                            }
+                                            Some(Err(error)) => {
+                                                ui.colored_label(egui::Color32::RED, error);
+                                            }
+                                            None => {
+                                                ui.label("Not run");
+                                            }
+                                        }
+                                    }
+
+                                    ui.label(&message.action_function);
+                                    ui.label(format!("{}", message.message_value));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                }
+                ConversationTraceTab::Functions => {
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        for (name, function) in conversation_trace.functions.iter() {
+                            egui::CollapsingHeader::new(format!(
+                                "{} (line {}, {} parameters)",
+                                name, function.line, function.num_parameters
+                            ))
+                            .id_source(name)
+                            .show(ui, |ui| {
+                                for (pc, instruction) in function.instructions.iter().enumerate() {
+                                    ui.monospace(format!("[{:03}] {:?}", pc, instruction));
+                                }
+                            });
+                        }
+                    });
+                }
+                ConversationTraceTab::Calls => {
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        for (index, entry) in entries.iter().enumerate() {
+                            if matches!(entry, Lua4TraceEntry::Instruction { .. }) {
+                                continue;
+                            }
+
+                            ui.horizontal(|ui| {
+                                if ui.button("Show").clicked() {
+                                    ui_state.entry_index = index;
+                                    ui_state.tab = ConversationTraceTab::Instructions;
+                                }
+                                ui.monospace(format_trace_entry(entry));
+                            });
+                        }
+                    });
+                }
+                ConversationTraceTab::Instructions => {
+                    if entries.is_empty() {
+                        ui.label("Nothing has been recorded.");
+                        return;
+                    }
+                    let last_index = entries.len() - 1;
+                    ui_state.entry_index = ui_state.entry_index.min(last_index);
+
+                    ui.horizontal(|ui| {
+                        if ui.button("|<").clicked() {
+                            ui_state.entry_index = 0;
+                        }
+                        if ui.button("<").clicked() {
+                            ui_state.entry_index = ui_state.entry_index.saturating_sub(1);
+                        }
+                        if ui.button(">").clicked() {
+                            ui_state.entry_index = (ui_state.entry_index + 1).min(last_index);
+                        }
+                        if ui.button(">|").clicked() {
+                            ui_state.entry_index = last_index;
+                        }
+                        if ui.button("Next call").clicked() {
+                            if let Some(offset) = entries
+                                .iter()
+                                .skip(ui_state.entry_index + 1)
+                                .position(|entry| {
+                                    !matches!(entry, Lua4TraceEntry::Instruction { .. })
+                                })
+                            {
+                                ui_state.entry_index += offset + 1;
+                            }
+                        }
+                        ui.add(
+                            egui::DragValue::new(&mut ui_state.entry_index)
+                                .clamp_range(0..=last_index),
+                        );
+                    });
+
+                    let entry = &entries[ui_state.entry_index];
+                    ui.monospace(format_trace_entry(entry));
+                    ui.separator();
+
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        if let Lua4TraceEntry::Instruction {
+                            function_source,
+                            stack,
+                            upvalues,
+                            ..
+                        } = entry
+                        {
+                            ui.label(format!("Source: {}", function_source));
+
+                            if let (Some(stack), Some(upvalues)) = (stack, upvalues) {
+                                egui::CollapsingHeader::new("Stack")
+                                    .default_open(true)
+                                    .show(ui, |ui| {
+                                        draw_values(ui, "conversation_trace_stack", stack);
+                                    });
+
+                                egui::CollapsingHeader::new("Upvalues")
+                                    .default_open(true)
+                                    .show(ui, |ui| {
+                                        draw_values(ui, "conversation_trace_upvalues", upvalues);
+                                    });
+                            } else {
+                                ui.label("The stack was not recorded for this instruction.");
+                            }
+                        }
+
+                        egui::CollapsingHeader::new("Globals").show(ui, |ui| {
+                            ui.checkbox(
+                                &mut ui_state.show_rust_closure_globals,
+                                "Show rust functions",
+                            );
+
+                            let mut globals: Vec<_> = trace
+                                .globals
+                                .iter()
+                                .filter(|(_, value)| {
+                                    ui_state.show_rust_closure_globals
+                                        || !matches!(value, Lua4Value::RustClosure(_))
+                                })
+                                .collect();
+                            globals.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+                            egui::Grid::new("conversation_trace_globals")
+                                .num_columns(2)
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    for (name, value) in globals {
+                                        ui.label(name);
+                                        ui.label(format_value(value));
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                    });
+                }
+            }
+        });
+}
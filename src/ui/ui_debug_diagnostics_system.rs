@@ -4,7 +4,7 @@ use bevy::{
 };
 use bevy_egui::{egui, EguiContexts};
 
-use crate::ui::UiStateDebugWindows;
+use crate::{systems::NetworkDiagnosticsPlugin, ui::UiStateDebugWindows};
 
 pub fn ui_debug_diagnostics_system(
     mut egui_context: EguiContexts,
@@ -28,7 +28,9 @@ pub fn ui_debug_diagnostics_system(
                     ui.label("Average");
                     ui.end_row();
 
-                    for diagnostic in diagnostics.iter() {
+                    for diagnostic in diagnostics.iter().filter(|diagnostic| {
+                        !NetworkDiagnosticsPlugin::ALL.contains(&diagnostic.id)
+                    }) {
                         if let Some(value) = diagnostic.value() {
                             if let Some(average) = diagnostic.average() {
                                 ui.label(diagnostic.name.as_ref());
@@ -43,5 +45,36 @@ pub fn ui_debug_diagnostics_system(
                         }
                     }
                 });
+
+            ui.separator();
+
+            egui::Grid::new("network_diagnostics_grid")
+                .num_columns(3)
+                .show(ui, |ui| {
+                    ui.label("Network");
+                    ui.label("Value");
+                    ui.label("Average");
+                    ui.end_row();
+
+                    for diagnostic in NetworkDiagnosticsPlugin::ALL
+                        .iter()
+                        .filter_map(|id| diagnostics.get(*id))
+                    {
+                        ui.label(diagnostic.name.as_ref());
+
+                        if let Some(value) = diagnostic.value() {
+                            ui.label(format!("{:>11.1}{:1}", value, diagnostic.suffix));
+                        } else {
+                            ui.label("-");
+                        }
+
+                        if let Some(average) = diagnostic.average() {
+                            ui.label(format!("{:>.1}{}", average, diagnostic.suffix));
+                        } else {
+                            ui.label("-");
+                        }
+                        ui.end_row();
+                    }
+                });
         });
 }
@@ -0,0 +1,369 @@
+use bevy::prelude::{Local, Res, ResMut};
+use bevy_egui::{egui, EguiContexts};
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt::Write,
+};
+
+use crate::{
+    protocol::{PacketDirection, PacketInspector, PacketInspectorEntry},
+    ui::UiStateDebugWindows,
+};
+
+const MAX_ENTRIES: usize = 10000;
+
+pub struct UiStateDebugPacketInspector {
+    entries: VecDeque<PacketInspectorEntry>,
+    paused: bool,
+    show_sent: bool,
+    show_received: bool,
+    filter_opcodes: String,
+    selected: HashSet<u64>,
+    inspected: Option<u64>,
+    export_path: String,
+    export_result: Option<String>,
+}
+
+impl Default for UiStateDebugPacketInspector {
+    fn default() -> Self {
+        Self {
+            entries: VecDeque::new(),
+            paused: false,
+            show_sent: true,
+            show_received: true,
+            filter_opcodes: String::new(),
+            selected: HashSet::new(),
+            inspected: None,
+            export_path: "packets.txt".into(),
+            export_result: None,
+        }
+    }
+}
+
+fn parse_opcode_filter(filter: &str) -> Vec<u16> {
+    filter
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter_map(|opcode| {
+            let opcode = opcode.trim();
+            let opcode = opcode
+                .strip_prefix("0x")
+                .or_else(|| opcode.strip_prefix("0X"))
+                .unwrap_or(opcode);
+            u16::from_str_radix(opcode, 16).ok()
+        })
+        .collect()
+}
+
+fn format_hex_dump(data: &[u8]) -> String {
+    let mut output = String::new();
+
+    for (line_index, line) in data.chunks(16).enumerate() {
+        write!(output, "{:04X}  ", line_index * 16).ok();
+
+        for index in 0..16 {
+            if let Some(byte) = line.get(index) {
+                write!(output, "{:02X} ", byte).ok();
+            } else {
+                output.push_str("   ");
+            }
+        }
+
+        output.push(' ');
+        for &byte in line {
+            if byte.is_ascii_graphic() || byte == b' ' {
+                output.push(byte as char);
+            } else {
+                output.push('.');
+            }
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+fn format_entry_header(entry: &PacketInspectorEntry) -> String {
+    format!(
+        "[{:.3}] {:?} {} [{:03X}] {} bytes",
+        entry.time.as_secs_f64(),
+        entry.connection_type,
+        match entry.direction {
+            PacketDirection::Sent => "Sent",
+            PacketDirection::Received => "Received",
+        },
+        entry.command,
+        entry.data.len()
+    )
+}
+
+fn export_entries<'a>(
+    path: &str,
+    entries: impl Iterator<Item = &'a PacketInspectorEntry>,
+) -> Result<usize, std::io::Error> {
+    let mut output = String::new();
+    let mut count = 0;
+
+    for entry in entries {
+        writeln!(output, "{}", format_entry_header(entry)).ok();
+        output.push_str(&format_hex_dump(&entry.data));
+        for message in entry.messages.iter() {
+            writeln!(output, "{}", message).ok();
+        }
+        output.push('\n');
+        count += 1;
+    }
+
+    std::fs::write(path, output)?;
+    Ok(count)
+}
+
+pub fn ui_debug_packet_inspector_system(
+    mut egui_context: EguiContexts,
+    mut ui_state_debug_packet_inspector: Local<UiStateDebugPacketInspector>,
+    mut ui_state_debug_windows: ResMut<UiStateDebugWindows>,
+    packet_inspector: Res<PacketInspector>,
+) {
+    let ui_state = &mut *ui_state_debug_packet_inspector;
+
+    // Only capture packets whilst the window is visible
+    let capturing = ui_state_debug_windows.debug_ui_open
+        && ui_state_debug_windows.packet_inspector_open
+        && !ui_state.paused;
+    if packet_inspector.is_capturing() != capturing {
+        packet_inspector.set_capturing(capturing);
+    }
+
+    if !ui_state_debug_windows.debug_ui_open {
+        return;
+    }
+
+    for entry in packet_inspector.take_entries() {
+        if ui_state.entries.len() >= MAX_ENTRIES {
+            if let Some(removed) = ui_state.entries.pop_front() {
+                ui_state.selected.remove(&removed.id);
+            }
+        }
+        ui_state.entries.push_back(entry);
+    }
+
+    egui::Window::new("Packet Inspector")
+        .resizable(true)
+        .default_width(600.0)
+        .default_height(500.0)
+        .open(&mut ui_state_debug_windows.packet_inspector_open)
+        .show(egui_context.ctx_mut(), |ui| {
+            egui::Grid::new("packet_inspector_controls_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Opcode Filter:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut ui_state.filter_opcodes)
+                            .hint_text("e.g. 7EC, 0x79F"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Direction:");
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut ui_state.show_sent, "Sent");
+                        ui.checkbox(&mut ui_state.show_received, "Received");
+                    });
+                    ui.end_row();
+
+                    ui.label("Capture:");
+                    ui.horizontal(|ui| {
+                        ui.toggle_value(&mut ui_state.paused, "Pause");
+
+                        if ui.button("Clear").clicked() {
+                            ui_state.entries.clear();
+                            ui_state.selected.clear();
+                            ui_state.inspected = None;
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Export:");
+                    ui.horizontal(|ui| {
+                        ui.text_edit_singleline(&mut ui_state.export_path);
+
+                        if ui
+                            .add_enabled(
+                                !ui_state.selected.is_empty(),
+                                egui::Button::new("Export Selected"),
+                            )
+                            .clicked()
+                        {
+                            let selected = &ui_state.selected;
+                            ui_state.export_result = Some(
+                                match export_entries(
+                                    &ui_state.export_path,
+                                    ui_state
+                                        .entries
+                                        .iter()
+                                        .filter(|entry| selected.contains(&entry.id)),
+                                ) {
+                                    Ok(count) => format!(
+                                        "Exported {} packets to {}",
+                                        count, ui_state.export_path
+                                    ),
+                                    Err(error) => format!("Export failed: {}", error),
+                                },
+                            );
+                        }
+                    });
+                    ui.end_row();
+                });
+
+            if let Some(export_result) = ui_state.export_result.as_ref() {
+                ui.label(export_result);
+            }
+
+            ui.separator();
+
+            let filter_opcodes = parse_opcode_filter(&ui_state.filter_opcodes);
+            let filtered_entries: Vec<&PacketInspectorEntry> = ui_state
+                .entries
+                .iter()
+                .filter(|entry| match entry.direction {
+                    PacketDirection::Sent => ui_state.show_sent,
+                    PacketDirection::Received => ui_state.show_received,
+                })
+                .filter(|entry| {
+                    filter_opcodes.is_empty() || filter_opcodes.contains(&entry.command)
+                })
+                .collect();
+
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "{} / {} packets, {} selected",
+                    filtered_entries.len(),
+                    ui_state.entries.len(),
+                    ui_state.selected.len()
+                ));
+
+                if ui.button("Select All").clicked() {
+                    ui_state
+                        .selected
+                        .extend(filtered_entries.iter().map(|entry| entry.id));
+                }
+
+                if ui.button("Select None").clicked() {
+                    ui_state.selected.clear();
+                }
+            });
+
+            egui_extras::TableBuilder::new(ui)
+                .striped(true)
+                .stick_to_bottom(!ui_state.paused)
+                .max_scroll_height(250.0)
+                .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                .column(egui_extras::Column::exact(20.0))
+                .column(egui_extras::Column::initial(60.0).at_least(60.0))
+                .column(egui_extras::Column::initial(50.0).at_least(50.0))
+                .column(egui_extras::Column::initial(60.0).at_least(60.0))
+                .column(egui_extras::Column::initial(50.0).at_least(50.0))
+                .column(egui_extras::Column::initial(50.0).at_least(50.0))
+                .column(egui_extras::Column::remainder().at_least(120.0))
+                .header(20.0, |mut header| {
+                    header.col(|_| {});
+                    header.col(|ui| {
+                        ui.heading("Time");
+                    });
+                    header.col(|ui| {
+                        ui.heading("Conn");
+                    });
+                    header.col(|ui| {
+                        ui.heading("Dir");
+                    });
+                    header.col(|ui| {
+                        ui.heading("Cmd");
+                    });
+                    header.col(|ui| {
+                        ui.heading("Len");
+                    });
+                    header.col(|ui| {
+                        ui.heading("Message");
+                    });
+                })
+                .body(|body| {
+                    body.rows(20.0, filtered_entries.len(), |row_index, mut row| {
+                        let Some(entry) = filtered_entries.get(row_index) else {
+                            return;
+                        };
+
+                        row.col(|ui| {
+                            let mut selected = ui_state.selected.contains(&entry.id);
+                            if ui.checkbox(&mut selected, "").changed() {
+                                if selected {
+                                    ui_state.selected.insert(entry.id);
+                                } else {
+                                    ui_state.selected.remove(&entry.id);
+                                }
+                            }
+                        });
+
+                        row.col(|ui| {
+                            ui.label(format!("{:.3}", entry.time.as_secs_f64()));
+                        });
+
+                        row.col(|ui| {
+                            ui.label(format!("{:?}", entry.connection_type));
+                        });
+
+                        row.col(|ui| {
+                            ui.label(match entry.direction {
+                                PacketDirection::Sent => "Sent",
+                                PacketDirection::Received => "Recv",
+                            });
+                        });
+
+                        row.col(|ui| {
+                            if ui
+                                .selectable_label(
+                                    ui_state.inspected == Some(entry.id),
+                                    format!("{:03X}", entry.command),
+                                )
+                                .clicked()
+                            {
+                                ui_state.inspected = Some(entry.id);
+                            }
+                        });
+
+                        row.col(|ui| {
+                            ui.label(format!("{}", entry.data.len()));
+                        });
+
+                        row.col(|ui| {
+                            ui.label(
+                                entry
+                                    .messages
+                                    .first()
+                                    .and_then(|message| message.lines().next())
+                                    .unwrap_or(""),
+                            );
+                        });
+                    });
+                });
+
+            ui.separator();
+
+            if let Some(entry) = ui_state
+                .inspected
+                .and_then(|id| ui_state.entries.iter().find(|entry| entry.id == id))
+            {
+                ui.label(format_entry_header(entry));
+
+                egui::ScrollArea::vertical()
+                    .id_source("packet_inspector_details")
+                    .show(ui, |ui| {
+                        ui.monospace(format_hex_dump(&entry.data));
+
+                        for message in entry.messages.iter() {
+                            ui.separator();
+                            ui.monospace(message);
+                        }
+                    });
+            } else {
+                ui.label("Click a packet command to inspect it.");
+            }
+        });
+}
@@ -20,6 +20,7 @@ use rose_game_common::components::Npc;
 
 use crate::{
     components::{ColliderEntity, COLLISION_FILTER_CLICKABLE, COLLISION_GROUP_PHYSICS_TOY},
+    resources::{KeyAction, KeyBindings},
     ui::UiStateDebugWindows,
 };
 
@@ -99,6 +100,7 @@ pub fn ui_debug_physics_system(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     key_code_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
     rapier_context: Res<RapierContext>,
     time: Res<Time>,
     query_primary_window: Query<&Window, With<PrimaryWindow>>,
@@ -160,7 +162,7 @@ pub fn ui_debug_physics_system(
         });
 
     if ui_state_debug_physics.spawn_balls
-        && key_code_input.pressed(KeyCode::B)
+        && key_bindings.pressed(KeyAction::DebugPhysicsSpawnBalls, &key_code_input)
         && !egui_context.ctx_mut().wants_keyboard_input()
         && !egui_context.ctx_mut().wants_pointer_input()
     {
@@ -274,7 +276,7 @@ pub fn ui_debug_physics_system(
         }
     }
 
-    if !key_code_input.pressed(KeyCode::B) {
+    if !key_bindings.pressed(KeyAction::DebugPhysicsSpawnBalls, &key_code_input) {
         ui_state_debug_physics.spawn_timer = ui_state_debug_physics.spawn_interval;
     }
 }
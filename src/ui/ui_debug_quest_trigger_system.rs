@@ -0,0 +1,150 @@
+use bevy::prelude::{Local, ResMut};
+use bevy_egui::{egui, EguiContexts};
+
+use rose_data::QuestTriggerHash;
+
+use crate::{
+    scripting::{
+        quest_dry_run, QuestConditionStatus, QuestTriggerDryRun, ScriptFunctionContext,
+        ScriptFunctionResources,
+    },
+    ui::UiStateDebugWindows,
+};
+
+#[derive(Default)]
+pub struct UiStateDebugQuestTrigger {
+    trigger: String,
+    results: Vec<QuestTriggerDryRun>,
+    error: Option<String>,
+}
+
+/// Accepts either a trigger name, or a trigger hash in decimal or 0x hex.
+fn parse_trigger(trigger: &str) -> QuestTriggerHash {
+    let trigger = trigger.trim();
+
+    if let Some(hex) = trigger
+        .strip_prefix("0x")
+        .or_else(|| trigger.strip_prefix("0X"))
+    {
+        if let Ok(hash) = u32::from_str_radix(hex, 16) {
+            return QuestTriggerHash { hash };
+        }
+    } else if let Ok(hash) = trigger.parse::<u32>() {
+        return QuestTriggerHash { hash };
+    }
+
+    trigger.into()
+}
+
+pub fn ui_debug_quest_trigger_system(
+    mut egui_context: EguiContexts,
+    mut ui_state_debug_windows: ResMut<UiStateDebugWindows>,
+    mut ui_state: Local<UiStateDebugQuestTrigger>,
+    mut script_context: ScriptFunctionContext,
+    script_resources: ScriptFunctionResources,
+) {
+    let ui_state = &mut *ui_state;
+    if !ui_state_debug_windows.debug_ui_open {
+        return;
+    }
+
+    egui::Window::new("Quest Trigger Dry Run")
+        .resizable(true)
+        .default_width(500.0)
+        .default_height(300.0)
+        .open(&mut ui_state_debug_windows.quest_trigger_open)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Trigger name or hash:");
+                let response = ui.text_edit_singleline(&mut ui_state.trigger);
+
+                let evaluate = ui.button("Evaluate").clicked()
+                    || (response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)));
+                if evaluate && !ui_state.trigger.trim().is_empty() {
+                    if script_context.query_player.get_single().is_err() {
+                        ui_state.results.clear();
+                        ui_state.error = Some("No player character".to_string());
+                        return;
+                    }
+
+                    match quest_dry_run(
+                        &script_resources,
+                        &mut script_context,
+                        parse_trigger(&ui_state.trigger),
+                    ) {
+                        Ok(results) => {
+                            ui_state.results = results;
+                            ui_state.error = None;
+                        }
+                        Err(_) => {
+                            ui_state.results.clear();
+                            ui_state.error = Some("Quest trigger not found".to_string());
+                        }
+                    }
+                }
+            });
+
+            if let Some(error) = ui_state.error.as_ref() {
+                ui.colored_label(egui::Color32::RED, error);
+            }
+
+            ui.separator();
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for (index, result) in ui_state.results.iter().enumerate() {
+                    let header = egui::RichText::new(format!(
+                        "{} - {}",
+                        result.trigger_name,
+                        if result.passed { "Passed" } else { "Failed" }
+                    ))
+                    .color(if result.passed {
+                        egui::Color32::GREEN
+                    } else {
+                        egui::Color32::RED
+                    });
+
+                    egui::CollapsingHeader::new(header)
+                        .id_source(index)
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            egui::Grid::new(format!("quest_dry_run_conditions_{}", index))
+                                .num_columns(4)
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    ui.label("Condition");
+                                    ui.label("Status");
+                                    ui.label("Observed");
+                                    ui.label("Required");
+                                    ui.end_row();
+
+                                    for condition in result.conditions.iter() {
+                                        ui.label(&condition.condition);
+                                        let (color, status) = match condition.status {
+                                            QuestConditionStatus::Passed => {
+                                                (egui::Color32::GREEN, "Passed")
+                                            }
+                                            QuestConditionStatus::Failed => {
+                                                (egui::Color32::RED, "Failed")
+                                            }
+                                            QuestConditionStatus::ServerSide => {
+                                                (egui::Color32::GRAY, "Server side")
+                                            }
+                                        };
+                                        ui.colored_label(color, status);
+                                        ui.label(condition.observed.as_deref().unwrap_or(""));
+                                        ui.label(condition.required.as_deref().unwrap_or(""));
+                                        ui.end_row();
+                                    }
+                                });
+
+                            if !result.rewards.is_empty() {
+                                ui.label("Rewards (not applied):");
+                                for reward in result.rewards.iter() {
+                                    ui.label(reward);
+                                }
+                            }
+                        });
+                }
+            });
+        });
+}
@@ -11,7 +11,9 @@ use rose_game_common::messages::client::ClientMessage;
 
 use crate::{
     components::PlayerCharacter,
-    resources::{AppState, DebugInspector, GameConnection, WorldConnection},
+    resources::{
+        AppState, DebugInspector, GameConnection, KeyAction, KeyBindings, WorldConnection,
+    },
     systems::{FreeCamera, OrbitCamera},
 };
 
@@ -34,13 +36,16 @@ pub struct UiStateDebugWindows {
     pub camera_info_open: bool,
     pub client_entity_list_open: bool,
     pub command_viewer_open: bool,
+    pub conversation_trace_open: bool,
     pub debug_render_open: bool,
     pub dialog_list_open: bool,
     pub effect_list_open: bool,
     pub item_list_open: bool,
     pub npc_list_open: bool,
     pub object_inspector_open: bool,
+    pub packet_inspector_open: bool,
     pub physics_open: bool,
+    pub quest_trigger_open: bool,
     pub skill_list_open: bool,
     pub zone_list_open: bool,
     pub zone_lighting_open: bool,
@@ -63,10 +68,11 @@ pub fn ui_debug_menu_system(
     game_connection: Option<Res<GameConnection>>,
     world_connection: Option<Res<WorldConnection>>,
     keyboard: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
     mut debug_inspector: ResMut<DebugInspector>,
     mut app_state_next: ResMut<NextState<AppState>>,
 ) {
-    if keyboard.pressed(KeyCode::ControlLeft) && keyboard.just_pressed(KeyCode::D) {
+    if key_bindings.just_pressed(KeyAction::ToggleDebugUi, &keyboard) {
         ui_state_debug_windows.debug_ui_open = !ui_state_debug_windows.debug_ui_open;
     }
 
@@ -185,6 +191,10 @@ pub fn ui_debug_menu_system(
                     &mut ui_state_debug_windows.command_viewer_open,
                     "Command Viewer",
                 );
+                ui.checkbox(
+                    &mut ui_state_debug_windows.conversation_trace_open,
+                    "Conversation Trace",
+                );
                 ui.checkbox(
                     &mut ui_state_debug_windows.debug_render_open,
                     "Debug Render",
@@ -193,6 +203,14 @@ pub fn ui_debug_menu_system(
                 ui.checkbox(&mut ui_state_debug_windows.effect_list_open, "Effect List");
                 ui.checkbox(&mut ui_state_debug_windows.item_list_open, "Item List");
                 ui.checkbox(&mut ui_state_debug_windows.npc_list_open, "NPC List");
+                ui.checkbox(
+                    &mut ui_state_debug_windows.packet_inspector_open,
+                    "Packet Inspector",
+                );
+                ui.checkbox(
+                    &mut ui_state_debug_windows.quest_trigger_open,
+                    "Quest Trigger Dry Run",
+                );
                 ui.checkbox(&mut ui_state_debug_windows.skill_list_open, "Skill List");
                 ui.checkbox(&mut ui_state_debug_windows.zone_list_open, "Zone List");
                 ui.checkbox(
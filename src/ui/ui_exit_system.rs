@@ -0,0 +1,114 @@
+use bevy::prelude::{Assets, EventWriter, Res, ResMut};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    resources::{LogoutState, LogoutType, UiResources},
+    ui::{
+        widgets::{DataBindings, Dialog, DrawText},
+        UiSoundEvent, UiStateWindows,
+    },
+};
+
+const IID_BTN_CLOSE: i32 = 10;
+const IID_BTN_CHARACTER_SELECT: i32 = 11;
+const IID_BTN_EXIT: i32 = 12;
+
+// Area of the dialog below the title bar which we draw the logout message into
+const MESSAGE_OFFSET: egui::Vec2 = egui::vec2(10.0, 30.0);
+const MESSAGE_HEIGHT: f32 = 20.0;
+
+pub fn ui_exit_system(
+    mut egui_context: EguiContexts,
+    mut ui_state_windows: ResMut<UiStateWindows>,
+    mut ui_sound_events: EventWriter<UiSoundEvent>,
+    mut logout_state: ResMut<LogoutState>,
+    ui_resources: Res<UiResources>,
+    dialog_assets: Res<Assets<Dialog>>,
+) {
+    if !ui_state_windows.exit_open {
+        return;
+    }
+
+    let dialog = if let Some(dialog) = dialog_assets.get(&ui_resources.dialog_system) {
+        dialog
+    } else {
+        return;
+    };
+
+    let message = if let Some(logout_type) = logout_state.logout_type {
+        let text = match logout_type {
+            LogoutType::CharacterSelect => "Returning to character select",
+            LogoutType::Exit => "Exiting game",
+        };
+
+        match logout_state.remaining_wait() {
+            Some(remaining) if !remaining.is_zero() => format!(
+                "{} in {} seconds...",
+                text,
+                remaining.as_secs_f32().ceil() as u32
+            ),
+            _ => format!("{}...", text),
+        }
+    } else {
+        "Are you sure you want to leave the game?".to_string()
+    };
+    let is_pending = logout_state.is_pending();
+
+    let mut response_close_button = None;
+    let mut response_character_select_button = None;
+    let mut response_exit_button = None;
+
+    egui::Window::new("Exit")
+        .frame(egui::Frame::none())
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .title_bar(false)
+        .resizable(false)
+        .default_width(dialog.width)
+        .default_height(dialog.height)
+        .show(egui_context.ctx_mut(), |ui| {
+            dialog.draw(
+                ui,
+                DataBindings {
+                    sound_events: Some(&mut ui_sound_events),
+                    enabled: &mut [
+                        (IID_BTN_CHARACTER_SELECT, !is_pending),
+                        (IID_BTN_EXIT, !is_pending),
+                    ],
+                    response: &mut [
+                        (IID_BTN_CLOSE, &mut response_close_button),
+                        (
+                            IID_BTN_CHARACTER_SELECT,
+                            &mut response_character_select_button,
+                        ),
+                        (IID_BTN_EXIT, &mut response_exit_button),
+                    ],
+                    ..Default::default()
+                },
+                |ui, _| {
+                    ui.add_label_in(
+                        egui::Rect::from_min_size(
+                            MESSAGE_OFFSET.to_pos2(),
+                            egui::vec2(dialog.width - MESSAGE_OFFSET.x * 2.0, MESSAGE_HEIGHT),
+                        ),
+                        message.as_str(),
+                    );
+                },
+            );
+        });
+
+    if response_character_select_button.map_or(false, |r| r.clicked()) {
+        logout_state.request(LogoutType::CharacterSelect);
+    }
+
+    if response_exit_button.map_or(false, |r| r.clicked()) {
+        logout_state.request(LogoutType::Exit);
+    }
+
+    // Closing the dialog whilst waiting to logout cancels the logout
+    if response_close_button.map_or(false, |r| r.clicked()) {
+        if is_pending {
+            logout_state.cancel();
+        }
+        ui_state_windows.exit_open = false;
+    }
+}
@@ -1,8 +1,8 @@
-use bevy::prelude::{Assets, EventWriter, Local, Res, ResMut};
+use bevy::prelude::{Assets, EventWriter, Input, KeyCode, Local, Res, ResMut};
 use bevy_egui::{egui, EguiContexts};
 
 use crate::{
-    resources::UiResources,
+    resources::{KeyAction, KeyBindings, UiResources},
     ui::{
         widgets::{DataBindings, Dialog},
         UiSoundEvent, UiStateWindows,
@@ -33,6 +33,8 @@ pub fn ui_game_menu_system(
     ui_resources: Res<UiResources>,
     mut ui_sound_events: EventWriter<UiSoundEvent>,
     dialog_assets: Res<Assets<Dialog>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
 ) {
     let dialog = if let Some(dialog) = dialog_assets.get(&ui_resources.dialog_game_menu) {
         dialog
@@ -127,7 +129,7 @@ pub fn ui_game_menu_system(
     }
 
     if response_button_community.map_or(false, |r| r.clicked()) {
-        // TODO: Community dialog
+        ui_state_windows.community_open = !ui_state_windows.community_open;
         ui_state_windows.menu_open = false;
     }
 
@@ -137,47 +139,36 @@ pub fn ui_game_menu_system(
     }
 
     if response_button_help.map_or(false, |r| r.clicked()) {
-        // TODO: Help dialog
+        ui_state_windows.help_open = !ui_state_windows.help_open;
         ui_state_windows.menu_open = false;
     }
 
     if response_button_info.map_or(false, |r| r.clicked()) {
-        // TODO: Info dialog
+        ui_state_windows.info_open = !ui_state_windows.info_open;
         ui_state_windows.menu_open = false;
     }
 
     if response_button_exit.map_or(false, |r| r.clicked()) {
-        // TODO: Exit dialog
+        ui_state_windows.exit_open = true;
         ui_state_windows.menu_open = false;
     }
 
     if !egui_context.ctx_mut().wants_keyboard_input() {
-        egui_context.ctx_mut().input_mut(|input| {
-            if input.consume_key(egui::Modifiers::ALT, egui::Key::A) {
-                ui_state_windows.character_info_open = !ui_state_windows.character_info_open;
+        let windows = &mut *ui_state_windows;
+        for (action, open) in [
+            (
+                KeyAction::ToggleCharacterInfo,
+                &mut windows.character_info_open,
+            ),
+            (KeyAction::ToggleInventory, &mut windows.inventory_open),
+            (KeyAction::ToggleClan, &mut windows.clan_open),
+            (KeyAction::ToggleSkillList, &mut windows.skill_list_open),
+            (KeyAction::ToggleQuestList, &mut windows.quest_list_open),
+            (KeyAction::ToggleSettings, &mut windows.settings_open),
+        ] {
+            if key_bindings.just_pressed(action, &keyboard_input) {
+                *open = !*open;
             }
-
-            if input.consume_key(egui::Modifiers::ALT, egui::Key::I)
-                || input.consume_key(egui::Modifiers::ALT, egui::Key::V)
-            {
-                ui_state_windows.inventory_open = !ui_state_windows.inventory_open;
-            }
-
-            if input.consume_key(egui::Modifiers::ALT, egui::Key::N) {
-                ui_state_windows.clan_open = !ui_state_windows.clan_open;
-            }
-
-            if input.consume_key(egui::Modifiers::ALT, egui::Key::S) {
-                ui_state_windows.skill_list_open = !ui_state_windows.skill_list_open;
-            }
-
-            if input.consume_key(egui::Modifiers::ALT, egui::Key::Q) {
-                ui_state_windows.quest_list_open = !ui_state_windows.quest_list_open;
-            }
-
-            if input.consume_key(egui::Modifiers::ALT, egui::Key::O) {
-                ui_state_windows.settings_open = !ui_state_windows.settings_open;
-            }
-        });
+        }
     }
 }
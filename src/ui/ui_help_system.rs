@@ -0,0 +1,113 @@
+use bevy::prelude::{Assets, EventWriter, Local, Res, ResMut};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    resources::{HelpIndex, UiResources},
+    ui::{
+        widgets::{DataBindings, Dialog},
+        UiSoundEvent, UiStateWindows,
+    },
+};
+
+const IID_BTN_CLOSE: i32 = 10;
+
+// Area of the dialog below the title bar which we draw the help index into
+const CONTENT_OFFSET: egui::Vec2 = egui::vec2(10.0, 45.0);
+const CONTENT_MARGIN_BOTTOM: f32 = 30.0;
+const TOPIC_LIST_WIDTH: f32 = 140.0;
+
+#[derive(Default)]
+pub struct UiStateHelp {
+    selected_topic: usize,
+}
+
+pub fn ui_help_system(
+    mut egui_context: EguiContexts,
+    mut ui_state: Local<UiStateHelp>,
+    mut ui_state_windows: ResMut<UiStateWindows>,
+    mut ui_sound_events: EventWriter<UiSoundEvent>,
+    ui_resources: Res<UiResources>,
+    dialog_assets: Res<Assets<Dialog>>,
+    help_index: Res<HelpIndex>,
+) {
+    let dialog = if let Some(dialog) = dialog_assets.get(&ui_resources.dialog_help) {
+        dialog
+    } else {
+        return;
+    };
+
+    let ui_state = &mut *ui_state;
+    let mut response_close_button = None;
+
+    egui::Window::new("Help")
+        .frame(egui::Frame::none())
+        .open(&mut ui_state_windows.help_open)
+        .title_bar(false)
+        .resizable(false)
+        .default_width(dialog.width)
+        .default_height(dialog.height)
+        .show(egui_context.ctx_mut(), |ui| {
+            dialog.draw(
+                ui,
+                DataBindings {
+                    sound_events: Some(&mut ui_sound_events),
+                    response: &mut [(IID_BTN_CLOSE, &mut response_close_button)],
+                    ..Default::default()
+                },
+                |ui, _| {
+                    let content_rect = egui::Rect::from_min_size(
+                        ui.min_rect().min + CONTENT_OFFSET,
+                        egui::vec2(
+                            dialog.width - CONTENT_OFFSET.x * 2.0,
+                            dialog.height - CONTENT_OFFSET.y - CONTENT_MARGIN_BOTTOM,
+                        ),
+                    );
+
+                    ui.allocate_ui_at_rect(content_rect, |ui| {
+                        ui.spacing_mut().item_spacing = egui::vec2(4.0, 4.0);
+
+                        if help_index.topics.is_empty() {
+                            ui.label("No help is available.");
+                            return;
+                        }
+
+                        ui.horizontal_top(|ui| {
+                            egui::ScrollArea::vertical()
+                                .id_source("help_topics")
+                                .max_width(TOPIC_LIST_WIDTH)
+                                .max_height(content_rect.height())
+                                .auto_shrink([false, false])
+                                .show(ui, |ui| {
+                                    for (index, topic) in help_index.topics.iter().enumerate() {
+                                        ui.selectable_value(
+                                            &mut ui_state.selected_topic,
+                                            index,
+                                            &topic.title,
+                                        );
+                                    }
+                                });
+
+                            ui.separator();
+
+                            egui::ScrollArea::vertical()
+                                .id_source("help_text")
+                                .max_height(content_rect.height())
+                                .auto_shrink([false, false])
+                                .show(ui, |ui| {
+                                    if let Some(topic) =
+                                        help_index.topics.get(ui_state.selected_topic)
+                                    {
+                                        ui.heading(&topic.title);
+                                        ui.add(egui::Label::new(&topic.text).wrap(true));
+                                    }
+                                });
+                        });
+                    });
+                },
+            );
+        });
+
+    if response_close_button.map_or(false, |r| r.clicked()) {
+        ui_state_windows.help_open = false;
+    }
+}
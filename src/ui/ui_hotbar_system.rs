@@ -12,7 +12,7 @@ use rose_game_common::components::{
 use crate::{
     components::{Cooldowns, PlayerCharacter},
     events::PlayerCommandEvent,
-    resources::{GameData, UiResources},
+    resources::{GameData, KeyAction, KeyBindings, UiResources},
     ui::{
         tooltips::{PlayerTooltipQuery, PlayerTooltipQueryItem, SkillTooltipType},
         ui_add_item_tooltip, ui_add_skill_tooltip,
@@ -205,6 +205,7 @@ pub fn ui_hotbar_system(
     query_player_tooltip: Query<PlayerTooltipQuery, With<PlayerCharacter>>,
     mut player_command_events: EventWriter<PlayerCommandEvent>,
     keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
     game_data: Res<GameData>,
     ui_resources: Res<UiResources>,
     dialog_assets: Res<Assets<Dialog>>,
@@ -227,25 +228,9 @@ pub fn ui_hotbar_system(
     let player_tooltip_data = query_player_tooltip.get_single().ok();
 
     let use_hotbar_index = if !egui_context.ctx_mut().wants_keyboard_input() {
-        if keyboard_input.just_pressed(KeyCode::F1) {
-            Some(0)
-        } else if keyboard_input.just_pressed(KeyCode::F2) {
-            Some(1)
-        } else if keyboard_input.just_pressed(KeyCode::F3) {
-            Some(2)
-        } else if keyboard_input.just_pressed(KeyCode::F4) {
-            Some(3)
-        } else if keyboard_input.just_pressed(KeyCode::F5) {
-            Some(4)
-        } else if keyboard_input.just_pressed(KeyCode::F6) {
-            Some(5)
-        } else if keyboard_input.just_pressed(KeyCode::F7) {
-            Some(6)
-        } else if keyboard_input.just_pressed(KeyCode::F8) {
-            Some(7)
-        } else {
-            None
-        }
+        KeyAction::HOTBAR
+            .iter()
+            .position(|action| key_bindings.just_pressed(*action, &keyboard_input))
     } else {
         None
     };
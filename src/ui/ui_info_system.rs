@@ -0,0 +1,169 @@
+use bevy::prelude::{Assets, EventWriter, Query, Res, ResMut, Time, With};
+use bevy_egui::{egui, EguiContexts};
+
+use rose_game_common::components::{
+    AbilityValues, CharacterInfo, ExperiencePoints, HealthPoints, Level, ManaPoints,
+};
+
+use crate::{
+    components::PlayerCharacter,
+    resources::{ConnectionState, CurrentZone, GameData, ServerConfiguration, UiResources},
+    ui::{
+        widgets::{DataBindings, Dialog, DrawText},
+        UiSoundEvent, UiStateWindows,
+    },
+};
+
+const IID_GAUGE_HP: i32 = 6;
+const IID_GAUGE_MP: i32 = 7;
+const IID_GAUGE_EXP: i32 = 8;
+const IID_BTN_MENU: i32 = 11;
+
+#[allow(clippy::too_many_arguments)]
+pub fn ui_info_system(
+    mut egui_context: EguiContexts,
+    mut ui_state_windows: ResMut<UiStateWindows>,
+    mut ui_sound_events: EventWriter<UiSoundEvent>,
+    query_player: Query<
+        (
+            &AbilityValues,
+            &CharacterInfo,
+            &Level,
+            &HealthPoints,
+            &ManaPoints,
+            &ExperiencePoints,
+        ),
+        With<PlayerCharacter>,
+    >,
+    connection_state: Res<ConnectionState>,
+    server_configuration: Res<ServerConfiguration>,
+    current_zone: Option<Res<CurrentZone>>,
+    game_data: Res<GameData>,
+    ui_resources: Res<UiResources>,
+    dialog_assets: Res<Assets<Dialog>>,
+    time: Res<Time>,
+) {
+    let dialog = if let Some(dialog) = dialog_assets.get(&ui_resources.dialog_player_info) {
+        dialog
+    } else {
+        return;
+    };
+
+    let Ok((ability_values, character_info, level, health_points, mana_points, experience_points)) =
+        query_player.get_single()
+    else {
+        return;
+    };
+
+    let hp = health_points.hp as f32 / ability_values.get_max_health() as f32;
+    let mp = mana_points.mp as f32 / ability_values.get_max_mana() as f32;
+    let need_xp = game_data
+        .ability_value_calculator
+        .calculate_levelup_require_xp(level.level);
+    let xp = experience_points.xp as f32 / need_xp as f32;
+
+    egui::Window::new("Info")
+        .frame(egui::Frame::none())
+        .open(&mut ui_state_windows.info_open)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .title_bar(false)
+        .resizable(false)
+        .default_width(dialog.width)
+        .show(egui_context.ctx_mut(), |ui| {
+            dialog.draw(
+                ui,
+                DataBindings {
+                    sound_events: Some(&mut ui_sound_events),
+                    visible: &mut [(IID_BTN_MENU, false)],
+                    gauge: &mut [
+                        (
+                            IID_GAUGE_HP,
+                            &hp,
+                            &format!("{}/{}", health_points.hp, ability_values.get_max_health()),
+                        ),
+                        (
+                            IID_GAUGE_MP,
+                            &mp,
+                            &format!("{}/{}", mana_points.mp, ability_values.get_max_mana()),
+                        ),
+                        (IID_GAUGE_EXP, &xp, &format!("{:.2}%", xp * 100.0)),
+                    ],
+                    ..Default::default()
+                },
+                |ui, _| {
+                    ui.add_label_in(
+                        egui::Rect::from_min_max(egui::pos2(15.0, 8.0), egui::pos2(150.0, 25.0)),
+                        egui::RichText::new(&character_info.name)
+                            .color(egui::Color32::from_rgb(0, 255, 42))
+                            .font(egui::FontId::new(
+                                14.0,
+                                egui::FontFamily::Name("Ubuntu-M".into()),
+                            )),
+                    );
+
+                    ui.add_label_in(
+                        egui::Rect::from_min_max(egui::pos2(180.0, 8.0), egui::pos2(230.0, 25.0)),
+                        egui::RichText::new(format!("{}", level.level))
+                            .color(egui::Color32::YELLOW)
+                            .font(egui::FontId::new(
+                                14.0,
+                                egui::FontFamily::Name("Ubuntu-M".into()),
+                            )),
+                    );
+                },
+            );
+
+            // The connection details are shown below the avatar info
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.set_width(dialog.width - ui.spacing().window_margin.sum().x);
+
+                egui::Grid::new("info_grid").num_columns(2).show(ui, |ui| {
+                    ui.label("Client Version:");
+                    ui.label(env!("CARGO_PKG_VERSION"));
+                    ui.end_row();
+
+                    ui.label("Server:");
+                    ui.label(format!(
+                        "{} ({}:{})",
+                        connection_state.server_name.as_deref().unwrap_or("Unknown"),
+                        server_configuration.ip,
+                        server_configuration.port
+                    ));
+                    ui.end_row();
+
+                    ui.label("Channel:");
+                    ui.label(
+                        connection_state
+                            .channel_name
+                            .clone()
+                            .or_else(|| connection_state.channel_id.map(|id| format!("{}", id)))
+                            .unwrap_or_else(|| "Unknown".to_string()),
+                    );
+                    ui.end_row();
+
+                    ui.label("Zone:");
+                    ui.label(current_zone.as_ref().map_or_else(
+                        || "Unknown".to_string(),
+                        |current_zone| {
+                            let zone_name = game_data
+                                .zone_list
+                                .get_zone(current_zone.id)
+                                .map_or("???", |zone_data| zone_data.name);
+                            format!("{} ({})", zone_name, current_zone.id.get())
+                        },
+                    ));
+                    ui.end_row();
+
+                    let uptime = time.elapsed().as_secs();
+                    ui.label("Uptime:");
+                    ui.label(format!(
+                        "{:02}:{:02}:{:02}",
+                        uptime / 3600,
+                        (uptime / 60) % 60,
+                        uptime % 60
+                    ));
+                    ui.end_row();
+                });
+            });
+        });
+}
@@ -13,10 +13,11 @@ use rose_game_common::components::{
 use crate::{
     components::{Cooldowns, PlayerCharacter},
     events::{NumberInputDialogEvent, PlayerCommandEvent},
-    resources::{GameData, UiResources},
+    resources::{GameData, RepairMode, RepairState, UiResources},
     ui::{
+        get_repair_cost,
         tooltips::{PlayerTooltipQuery, PlayerTooltipQueryItem},
-        ui_add_item_tooltip,
+        ui_add_item_compare_tooltip, ui_add_item_tooltip,
         widgets::{DataBindings, Dialog, Widget},
         DialogInstance, DragAndDropId, DragAndDropSlot, UiSoundEvent, UiStateDragAndDrop,
         UiStateWindows,
@@ -148,6 +149,14 @@ fn drag_accepts_equipment(drag_source: &DragAndDropId) -> bool {
     )
 }
 
+fn drag_accepts_equipment_or_gems(drag_source: &DragAndDropId) -> bool {
+    drag_accepts_equipment(drag_source)
+        || matches!(
+            drag_source,
+            DragAndDropId::Inventory(ItemSlot::Inventory(InventoryPageType::Materials, _))
+        )
+}
+
 fn drag_accepts_equipment_or_bank(drag_source: &DragAndDropId) -> bool {
     drag_accepts_equipment(drag_source) || matches!(drag_source, DragAndDropId::Bank(_))
 }
@@ -225,6 +234,7 @@ fn ui_add_inventory_slot(
     item_slot_map: &mut EnumMap<InventoryPageType, Vec<ItemSlot>>,
     ui_state_dnd: &mut UiStateDragAndDrop,
     player_command_events: &mut EventWriter<PlayerCommandEvent>,
+    repair_state: &RepairState,
 ) {
     let drag_accepts = match inventory_slot {
         ItemSlot::Inventory(page_type, _) => match page_type {
@@ -233,7 +243,7 @@ fn ui_add_inventory_slot(
             InventoryPageType::Materials => drag_accepts_materials_or_bank,
             InventoryPageType::Vehicles => drag_accepts_vehicles_or_bank,
         },
-        ItemSlot::Equipment(_) => drag_accepts_equipment,
+        ItemSlot::Equipment(_) => drag_accepts_equipment_or_gems,
         ItemSlot::Ammo(_) => drag_accepts_materials,
         ItemSlot::Vehicle(_) => drag_accepts_vehicles,
     };
@@ -271,8 +281,19 @@ fn ui_add_inventory_slot(
     let mut use_inventory_slot = None;
     let mut drop_inventory_slot = None;
     let mut swap_inventory_slots = None;
+    let mut insert_gem_inventory_slot = None;
+    let mut repair_inventory_slot = None;
 
-    if response.double_clicked() {
+    let can_repair = matches!(
+        inventory_slot,
+        ItemSlot::Equipment(_) | ItemSlot::Inventory(InventoryPageType::Equipment, _)
+    );
+
+    if repair_state.is_active() {
+        if can_repair && response.clicked() {
+            repair_inventory_slot = Some(inventory_slot);
+        }
+    } else if response.double_clicked() {
         match inventory_slot {
             ItemSlot::Inventory(InventoryPageType::Equipment, _) => {
                 equip_equipment_inventory_slot = Some(inventory_slot);
@@ -344,7 +365,22 @@ fn ui_add_inventory_slot(
         });
 
         response.on_hover_ui(|ui| {
-            ui_add_item_tooltip(ui, game_data, player_tooltip_data, &item);
+            if matches!(inventory_slot, ItemSlot::Inventory(_, _)) {
+                ui_add_item_compare_tooltip(ui, game_data, player_tooltip_data, &item);
+            } else {
+                ui_add_item_tooltip(ui, game_data, player_tooltip_data, &item);
+            }
+
+            if let (Some(RepairMode::UsingNpc { .. }), Some(equipment_item)) =
+                (repair_state.mode, item.as_equipment())
+            {
+                if let Some(repair_cost) = get_repair_cost(game_data, equipment_item) {
+                    ui.colored_label(
+                        egui::Color32::YELLOW,
+                        format!("Estimated Repair Cost: {}", repair_cost),
+                    );
+                }
+            }
         });
     }
 
@@ -364,14 +400,15 @@ fn ui_add_inventory_slot(
                     unequip_vehicle_part_index = Some(vehicle_part_index);
                 }
             },
-            ItemSlot::Equipment(_) => {
-                if matches!(
-                    dropped_inventory_slot,
-                    ItemSlot::Inventory(InventoryPageType::Equipment, _)
-                ) {
+            ItemSlot::Equipment(equipment_index) => match dropped_inventory_slot {
+                ItemSlot::Inventory(InventoryPageType::Equipment, _) => {
                     equip_equipment_inventory_slot = Some(dropped_inventory_slot);
                 }
-            }
+                ItemSlot::Inventory(InventoryPageType::Materials, _) => {
+                    insert_gem_inventory_slot = Some((equipment_index, dropped_inventory_slot));
+                }
+                _ => {}
+            },
             ItemSlot::Ammo(_) => {
                 if matches!(
                     dropped_inventory_slot,
@@ -397,6 +434,14 @@ fn ui_add_inventory_slot(
         ));
     }
 
+    if let Some(item_slot) = repair_inventory_slot {
+        player_command_events.send(PlayerCommandEvent::RepairItem(item_slot));
+    }
+
+    if let Some((equipment_index, item_slot)) = insert_gem_inventory_slot {
+        player_command_events.send(PlayerCommandEvent::InsertGem(equipment_index, item_slot));
+    }
+
     if let Some(item_slot) = equip_equipment_inventory_slot {
         player_command_events.send(PlayerCommandEvent::EquipEquipment(item_slot));
     }
@@ -468,6 +513,7 @@ pub fn ui_inventory_system(
     ui_resources: Res<UiResources>,
     mut player_command_events: EventWriter<PlayerCommandEvent>,
     mut number_input_dialog_events: EventWriter<NumberInputDialogEvent>,
+    repair_state: Res<RepairState>,
 ) {
     let ui_state_inventory = &mut *ui_state_inventory;
     let dialog = if let Some(dialog) = ui_state_inventory
@@ -550,6 +596,7 @@ pub fn ui_inventory_system(
                                         &mut ui_state_inventory.item_slot_map,
                                         &mut ui_state_dnd,
                                         &mut player_command_events,
+                                        &repair_state,
                                     );
                                 }
                             }
@@ -581,6 +628,7 @@ pub fn ui_inventory_system(
                                         &mut ui_state_inventory.item_slot_map,
                                         &mut ui_state_dnd,
                                         &mut player_command_events,
+                                        &repair_state,
                                     );
                                 }
                             }
@@ -615,6 +663,7 @@ pub fn ui_inventory_system(
                                 &mut ui_state_inventory.item_slot_map,
                                 &mut ui_state_dnd,
                                 &mut player_command_events,
+                                &repair_state,
                             );
                         }
 
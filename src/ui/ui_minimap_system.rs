@@ -10,14 +10,14 @@ use bevy::{
 use bevy_egui::{egui, EguiContexts};
 
 use rose_data::ZoneId;
-use rose_game_common::components::{CharacterInfo, Team};
+use rose_game_common::components::{CharacterInfo, Npc, Team};
 
 use crate::{
-    components::{PartyInfo, PlayerCharacter, Position},
-    resources::{CurrentZone, GameData, UiResources, UiSpriteSheetType},
+    components::{ClientEntity, PartyInfo, PlayerCharacter, Position},
+    resources::{CurrentZone, GameData, QuestObjectiveTable, UiResources, UiSpriteSheetType},
     ui::{
         widgets::{DataBindings, Dialog, Widget},
-        UiSoundEvent,
+        UiSoundEvent, UiStateQuestTracker,
     },
     zone_loader::{ZoneLoaderAsset, ZoneNpc},
 };
@@ -89,6 +89,7 @@ pub fn ui_minimap_system(
     mut ui_sound_events: EventWriter<UiSoundEvent>,
     query_player: Query<(&Position, &Team, Option<&PartyInfo>), With<PlayerCharacter>>,
     query_characters: Query<(&CharacterInfo, &Position, &Team), Without<PlayerCharacter>>,
+    query_npcs: Query<(&Npc, &Position), With<ClientEntity>>,
     asset_server: Res<AssetServer>,
     query_camera: Query<&Transform, With<Camera3d>>,
     images: Res<Assets<Image>>,
@@ -97,6 +98,8 @@ pub fn ui_minimap_system(
     game_data: Res<GameData>,
     ui_resources: Res<UiResources>,
     dialog_assets: Res<Assets<Dialog>>,
+    ui_state_quest_tracker: Res<UiStateQuestTracker>,
+    quest_objective_table: Res<QuestObjectiveTable>,
 ) {
     let ui_state = &mut *ui_state;
     let dialog = if let Some(dialog) = dialog_assets.get(&ui_resources.dialog_minimap) {
@@ -417,6 +420,55 @@ pub fn ui_minimap_system(
                     }
                 }
 
+                // Draw objective markers for tracked quests
+                for &quest_id in ui_state_quest_tracker.tracked_quests.iter() {
+                    let Some(objectives) = quest_objective_table.get(quest_id) else {
+                        continue;
+                    };
+                    let quest_name = game_data
+                        .quests
+                        .get_quest_data(quest_id)
+                        .map_or("???", |quest_data| quest_data.name);
+
+                    let npc_positions = current_zone_data
+                        .npcs
+                        .iter()
+                        .filter(|zone_npc| objectives.has_npc(zone_npc.npc_id.get()))
+                        .map(|zone_npc| zone_npc.position);
+                    let monster_positions = query_npcs
+                        .iter()
+                        .filter(|(npc, _)| objectives.has_npc(npc.id.get()))
+                        .map(|(_, position)| position.position);
+                    let objective_positions = objectives
+                        .positions
+                        .iter()
+                        .filter(|position| position.zone == current_zone.id.get())
+                        .map(|position| Vec3::new(position.x, position.y, 0.0));
+
+                    for objective_position in npc_positions
+                        .chain(monster_positions)
+                        .chain(objective_positions)
+                    {
+                        let marker_position = map_absolute_position(ui_state, objective_position);
+                        let marker_rect = egui::Rect::from_center_size(
+                            marker_position.to_array().into(),
+                            egui::vec2(10.0, 10.0),
+                        );
+
+                        if minimap_rect.contains_rect(marker_rect) {
+                            ui.painter().circle(
+                                marker_rect.center(),
+                                4.0,
+                                egui::Color32::YELLOW,
+                                egui::Stroke::new(1.0, egui::Color32::BLACK),
+                            );
+
+                            let response = ui.allocate_rect(marker_rect, egui::Sense::hover());
+                            response.on_hover_text(quest_name);
+                        }
+                    }
+                }
+
                 // Draw player position arrow texture on a rotated rectangle to face camera position
                 if let Some(minimap_player_pos) = minimap_player_pos {
                     let minimap_player_sprite = ui_resources.get_minimap_player_sprite().unwrap();
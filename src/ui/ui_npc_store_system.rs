@@ -7,9 +7,11 @@ use bevy::{
 };
 use bevy_egui::{egui, EguiContexts};
 
-use rose_data::{Item, NpcData, NpcStoreTabData, NpcStoreTabId};
+use enum_map::EnumMap;
+
+use rose_data::{Item, ItemReference, NpcData, NpcStoreTabData, NpcStoreTabId};
 use rose_game_common::{
-    components::{AbilityValues, Inventory, ItemSlot, Npc},
+    components::{AbilityValues, Inventory, InventoryPageType, ItemSlot, Npc, INVENTORY_PAGE_SIZE},
     messages::{
         client::{ClientMessage, NpcStoreBuyItem},
         ClientEntityId,
@@ -24,7 +26,7 @@ use crate::{
     },
     ui::{
         tooltips::{PlayerTooltipQuery, PlayerTooltipQueryItem},
-        ui_add_item_tooltip,
+        ui_add_item_compare_tooltip, ui_add_item_tooltip,
         ui_drag_and_drop_system::UiStateDragAndDrop,
         widgets::{DataBindings, Dialog, DrawText},
         DragAndDropId, DragAndDropSlot, UiSoundEvent,
@@ -78,6 +80,144 @@ impl Default for UiNpcStoreState {
     }
 }
 
+fn get_pending_buy_item_reference(
+    game_data: &GameData,
+    npc_data: &NpcData,
+    pending_buy_item: &PendingBuyItem,
+) -> Option<ItemReference> {
+    npc_data
+        .store_tabs
+        .get(pending_buy_item.store_tab_index)
+        .and_then(|x| x.as_ref())
+        .and_then(|store_tab| game_data.npcs.get_store_tab(*store_tab))
+        .and_then(|store_tab| {
+            store_tab
+                .items
+                .get(&(pending_buy_item.store_tab_slot as u16))
+        })
+        .copied()
+}
+
+fn get_buy_item_price(
+    game_data: &GameData,
+    item_reference: ItemReference,
+    player: Option<&NpcStorePlayerWorldQueryItem>,
+    world_rates: Option<&Res<WorldRates>>,
+) -> i64 {
+    game_data
+        .ability_value_calculator
+        .calculate_npc_store_item_buy_price(
+            &game_data.items,
+            item_reference,
+            player.map_or(0, |player| player.ability_values.get_npc_store_buy_rate()),
+            world_rates.map_or(100, |x| x.item_price_rate),
+            world_rates.map_or(100, |x| x.town_price_rate),
+        )
+        .unwrap_or(0) as i64
+}
+
+fn get_sell_item_price(
+    game_data: &GameData,
+    item: &Item,
+    player: Option<&NpcStorePlayerWorldQueryItem>,
+    world_rates: Option<&Res<WorldRates>>,
+) -> i64 {
+    game_data
+        .ability_value_calculator
+        .calculate_npc_store_item_sell_price(
+            &game_data.items,
+            item,
+            player.map_or(0, |player| player.ability_values.get_npc_store_sell_rate()),
+            world_rates.map_or(0, |x| x.world_price_rate),
+            world_rates.map_or(0, |x| x.item_price_rate),
+            world_rates.map_or(0, |x| x.town_price_rate),
+        )
+        .unwrap_or(0) as i64
+}
+
+/// The result of applying the pending buy and sell lists to a copy of the
+/// player's inventory, used to check the transaction before sending it.
+struct TransactionPreview {
+    money: i64,
+    free_slots: EnumMap<InventoryPageType, usize>,
+    error: Option<&'static str>,
+}
+
+fn preview_transaction(
+    game_data: &GameData,
+    npc_data: &NpcData,
+    buy_list: &[Option<PendingBuyItem>; NUM_BUY_ITEMS],
+    sell_list: &[Option<PendingSellItem>; NUM_SELL_ITEMS],
+    player: &NpcStorePlayerWorldQueryItem,
+    world_rates: Option<&Res<WorldRates>>,
+) -> TransactionPreview {
+    let mut inventory = player.inventory.clone();
+    let mut money = player.inventory.money.0;
+    let mut error = None;
+
+    for pending_sell_item in sell_list.iter().filter_map(|x| x.as_ref()) {
+        if let Some(item) = player.inventory.get_item(pending_sell_item.item_slot) {
+            money += get_sell_item_price(game_data, item, Some(player), world_rates)
+                * pending_sell_item.quantity as i64;
+        }
+
+        inventory.try_take_quantity(
+            pending_sell_item.item_slot,
+            pending_sell_item.quantity as u32,
+        );
+    }
+
+    for pending_buy_item in buy_list.iter().filter_map(|x| x.as_ref()) {
+        let Some(item_reference) =
+            get_pending_buy_item_reference(game_data, npc_data, pending_buy_item)
+        else {
+            continue;
+        };
+        let Some(item) = game_data
+            .items
+            .get_base_item(item_reference)
+            .and_then(|item_data| {
+                Item::from_item_data(item_data, pending_buy_item.quantity as u32)
+            })
+        else {
+            continue;
+        };
+
+        let quantity = if item.is_stackable_item() {
+            pending_buy_item.quantity as i64
+        } else {
+            1
+        };
+        money -=
+            get_buy_item_price(game_data, item_reference, Some(player), world_rates) * quantity;
+
+        if inventory.try_add_item(item).is_err() && error.is_none() {
+            error = Some("You do not have enough inventory space for this transaction.");
+        }
+    }
+
+    if money < 0 {
+        error = Some("You do not have enough Zuly for this transaction.");
+    }
+
+    let mut free_slots = EnumMap::default();
+    for (page_type, count) in free_slots.iter_mut() {
+        *count = (0..INVENTORY_PAGE_SIZE)
+            .filter(|&index| {
+                inventory
+                    .get_item(ItemSlot::Inventory(page_type, index))
+                    .is_none()
+            })
+            .count();
+    }
+
+    TransactionPreview {
+        money,
+        free_slots,
+        error,
+    }
+}
+
 fn ui_add_store_item_slot(
     ui: &mut egui::Ui,
     ui_state_dnd: &mut UiStateDragAndDrop,
@@ -109,20 +249,9 @@ fn ui_add_store_item_slot(
         }
     });
 
-    let item_price = if let Some(item_reference) = item_reference {
-        game_data
-            .ability_value_calculator
-            .calculate_npc_store_item_buy_price(
-                &game_data.items,
-                *item_reference,
-                player.map_or(0, |x| x.ability_values.get_npc_store_buy_rate()),
-                world_rates.map_or(100, |x| x.item_price_rate),
-                world_rates.map_or(100, |x| x.town_price_rate),
-            )
-            .unwrap_or(0) as i64
-    } else {
-        0
-    };
+    let item_price = item_reference.map_or(0, |item_reference| {
+        get_buy_item_price(game_data, *item_reference, player, world_rates)
+    });
 
     let mut dropped_item = None;
     let response = ui
@@ -182,7 +311,7 @@ fn ui_add_store_item_slot(
         }
 
         response.on_hover_ui(|ui| {
-            ui_add_item_tooltip(ui, game_data, player_tooltip_data, item);
+            ui_add_item_compare_tooltip(ui, game_data, player_tooltip_data, item);
 
             ui.colored_label(egui::Color32::YELLOW, format!("Buy Price: {}", item_price));
         });
@@ -215,19 +344,10 @@ fn ui_add_buy_item_slot(
 ) -> i64 {
     let pending_buy_item = &mut buy_list[buy_slot_index];
     let item_reference = pending_buy_item.as_ref().and_then(|pending_buy_item| {
-        npc_data
-            .store_tabs
-            .get(pending_buy_item.store_tab_index)
-            .and_then(|x| x.as_ref())
-            .and_then(|store_tab| game_data.npcs.get_store_tab(*store_tab))
-            .and_then(|store_tab| {
-                store_tab
-                    .items
-                    .get(&(pending_buy_item.store_tab_slot as u16))
-            })
+        get_pending_buy_item_reference(game_data, npc_data, pending_buy_item)
     });
     let item_data =
-        item_reference.and_then(|item_reference| game_data.items.get_base_item(*item_reference));
+        item_reference.and_then(|item_reference| game_data.items.get_base_item(item_reference));
     let item = item_data.and_then(|item_data| Item::from_item_data(item_data, 999));
     let sprite = item_data.and_then(|item_data| {
         ui_resources.get_sprite_by_index(UiSpriteSheetType::Item, item_data.icon_index as usize)
@@ -240,21 +360,10 @@ fn ui_add_buy_item_slot(
         }
     });
 
-    let item_price = if let Some(item_reference) = item_reference {
-        game_data
-            .ability_value_calculator
-            .calculate_npc_store_item_buy_price(
-                &game_data.items,
-                *item_reference,
-                player.map_or(0, |player| player.ability_values.get_npc_store_buy_rate()),
-                world_rates.map_or(100, |x| x.item_price_rate),
-                world_rates.map_or(100, |x| x.town_price_rate),
-            )
-            .unwrap_or(0) as i64
+    let item_price = item_reference.map_or(0, |item_reference| {
+        get_buy_item_price(game_data, item_reference, player, world_rates)
             * quantity.unwrap_or(1) as i64
-    } else {
-        0
-    };
+    });
 
     let mut dropped_item = None;
     let response = ui
@@ -286,7 +395,7 @@ fn ui_add_buy_item_slot(
 
     if let Some(item) = item {
         response.on_hover_ui(|ui| {
-            ui_add_item_tooltip(ui, game_data, player_tooltip_data, &item);
+            ui_add_item_compare_tooltip(ui, game_data, player_tooltip_data, &item);
 
             ui.colored_label(egui::Color32::YELLOW, format!("Buy Price: {}", item_price));
         });
@@ -322,22 +431,12 @@ fn ui_add_sell_item_slot(
             .and_then(|pending_sell_item| player.inventory.get_item(pending_sell_item.item_slot))
     });
 
-    let item_price = if let Some(item) = item {
-        game_data
-            .ability_value_calculator
-            .calculate_npc_store_item_sell_price(
-                &game_data.items,
-                item,
-                player.map_or(0, |player| player.ability_values.get_npc_store_sell_rate()),
-                world_rates.map_or(0, |x| x.world_price_rate),
-                world_rates.map_or(0, |x| x.item_price_rate),
-                world_rates.map_or(0, |x| x.town_price_rate),
-            )
-            .unwrap_or(0) as i64
-            * item.get_quantity() as i64
-    } else {
-        0
-    };
+    let item_price = item
+        .zip(pending_sell_item.as_ref())
+        .map_or(0, |(item, pending_sell_item)| {
+            get_sell_item_price(game_data, item, player, world_rates)
+                * pending_sell_item.quantity as i64
+        });
 
     let mut dropped_item = None;
     let response = ui
@@ -590,7 +689,19 @@ pub fn ui_npc_store_system(
             );
         });
 
-    let mut transaction_cost = 0;
+    let transaction_preview = player.as_ref().map(|player| {
+        preview_transaction(
+            &game_data,
+            npc_data,
+            &ui_state.buy_list,
+            &ui_state.sell_list,
+            player,
+            world_rates.as_ref(),
+        )
+    });
+    let transaction_error = transaction_preview
+        .as_ref()
+        .and_then(|preview| preview.error);
 
     egui::Window::new("NPC Transaction")
         .frame(egui::Frame::none())
@@ -609,6 +720,7 @@ pub fn ui_npc_store_system(
                         (IID_TRANSACTION_CANCEL, &mut response_cancel),
                         (IID_TRANSACTION_OK, &mut response_ok),
                     ],
+                    enabled: &mut [(IID_TRANSACTION_OK, transaction_error.is_none())],
                     ..Default::default()
                 },
                 |ui, _bindings| {
@@ -632,7 +744,6 @@ pub fn ui_npc_store_system(
                         );
                     }
                     ui.add_label_at(egui::pos2(39.0, 139.0), format!("{}", buy_item_price));
-                    transaction_cost += buy_item_price;
 
                     let mut sell_item_value = 0;
                     for i in 0..NUM_SELL_ITEMS {
@@ -653,17 +764,45 @@ pub fn ui_npc_store_system(
                         );
                     }
                     ui.add_label_at(egui::pos2(39.0, 272.0), format!("{}", sell_item_value));
-                    transaction_cost -= sell_item_value;
                 },
             );
+
+            if let Some(preview) = transaction_preview.as_ref() {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_width(transaction_dialog.width - 12.0);
+
+                    ui.colored_label(
+                        if preview.money < 0 {
+                            egui::Color32::RED
+                        } else {
+                            egui::Color32::WHITE
+                        },
+                        format!("Zuly after transaction: {}", preview.money),
+                    );
+                    ui.label(format!(
+                        "Free slots: Equip {} / Use {} / Etc {} / Pat {}",
+                        preview.free_slots[InventoryPageType::Equipment],
+                        preview.free_slots[InventoryPageType::Consumables],
+                        preview.free_slots[InventoryPageType::Materials],
+                        preview.free_slots[InventoryPageType::Vehicles],
+                    ));
+
+                    if let Some(error) = preview.error {
+                        ui.colored_label(egui::Color32::RED, error);
+                    }
+                });
+            }
         });
 
     if response_ok.map_or(false, |x| x.clicked()) {
-        let can_afford_transaction =
-            player.map_or(true, |player| transaction_cost <= player.inventory.money.0);
-        // TODO: Check inventory space
-
-        if can_afford_transaction {
+        if let Some(error) = transaction_error {
+            message_box_events.send(MessageBoxEvent::Show {
+                message: error.to_string(),
+                modal: true,
+                ok: Some(Box::new(|_| {})),
+                cancel: None,
+            });
+        } else {
             let mut buy_items = Vec::new();
             let mut sell_items = Vec::new();
 
@@ -689,13 +828,6 @@ pub fn ui_npc_store_system(
                     })
                     .ok();
             }
-        } else {
-            message_box_events.send(MessageBoxEvent::Show {
-                message: "You do not have enough Zuly for this transaction.".to_string(),
-                modal: true,
-                ok: Some(Box::new(|_| {})),
-                cancel: None,
-            });
         }
     }
 
@@ -14,7 +14,7 @@ use crate::{
     resources::{GameConnection, GameData, UiResources},
     ui::{
         tooltips::{PlayerTooltipQuery, PlayerTooltipQueryItem},
-        ui_add_item_tooltip,
+        ui_add_item_compare_tooltip,
         widgets::{DataBindings, Dialog},
         DragAndDropId, DragAndDropSlot, UiSoundEvent, UiStateDragAndDrop,
     },
@@ -109,7 +109,7 @@ fn ui_add_store_item_slot(
     }
 
     response.on_hover_ui(|ui| {
-        ui_add_item_tooltip(ui, game_data, player_tooltip_data, item);
+        ui_add_item_compare_tooltip(ui, game_data, player_tooltip_data, item);
 
         if is_sell_item {
             ui.colored_label(egui::Color32::YELLOW, format!("Price: {}", price.0));
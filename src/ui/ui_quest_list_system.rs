@@ -11,7 +11,7 @@ use crate::{
         tooltips::{PlayerTooltipQuery, PlayerTooltipQueryItem},
         ui_add_item_tooltip,
         widgets::{DataBindings, Dialog, DrawText, Widget},
-        DragAndDropId, DragAndDropSlot, UiSoundEvent, UiStateWindows,
+        DragAndDropId, DragAndDropSlot, UiSoundEvent, UiStateQuestTracker, UiStateWindows,
     },
 };
 
@@ -90,6 +90,7 @@ pub fn ui_quest_list_system(
     mut ui_state: Local<UiQuestListState>,
     mut egui_context: EguiContexts,
     mut ui_state_windows: ResMut<UiStateWindows>,
+    mut ui_state_quest_tracker: ResMut<UiStateQuestTracker>,
     mut ui_sound_events: EventWriter<UiSoundEvent>,
     query_player: Query<&QuestState, With<PlayerCharacter>>,
     query_player_tooltip: Query<PlayerTooltipQuery, With<PlayerCharacter>>,
@@ -243,6 +244,13 @@ pub fn ui_quest_list_system(
                                             egui::RichText::new(quest_data.name)
                                                 .color(egui::Color32::YELLOW),
                                         ));
+
+                                        let mut tracked = ui_state_quest_tracker
+                                            .is_tracked(selected_quest.quest_id);
+                                        if ui.checkbox(&mut tracked, "Track").changed() {
+                                            ui_state_quest_tracker
+                                                .set_tracked(selected_quest.quest_id, tracked);
+                                        }
                                     })
                                 },
                             );
@@ -0,0 +1,162 @@
+use bevy::prelude::{Query, Res, ResMut, Resource, With};
+use bevy_egui::{egui, EguiContexts};
+
+use rose_data::{ItemReference, NpcId};
+use rose_game_common::components::QuestState;
+
+use crate::{
+    components::PlayerCharacter,
+    resources::{GameData, QuestObjectiveTable},
+};
+
+#[derive(Default, Resource)]
+pub struct UiStateQuestTracker {
+    pub tracked_quests: Vec<usize>,
+}
+
+impl UiStateQuestTracker {
+    pub fn is_tracked(&self, quest_id: usize) -> bool {
+        self.tracked_quests.contains(&quest_id)
+    }
+
+    pub fn set_tracked(&mut self, quest_id: usize, tracked: bool) {
+        if tracked {
+            if !self.is_tracked(quest_id) {
+                self.tracked_quests.push(quest_id);
+            }
+        } else {
+            self.tracked_quests.retain(|id| *id != quest_id);
+        }
+    }
+}
+
+fn get_item_name(game_data: &GameData, item_reference: ItemReference) -> &str {
+    game_data
+        .items
+        .get_base_item(item_reference)
+        .map_or("???", |item_data| item_data.name)
+}
+
+fn get_npc_name(game_data: &GameData, npc_id: u16) -> &str {
+    NpcId::new(npc_id)
+        .and_then(|npc_id| game_data.npcs.get_npc(npc_id))
+        .map_or("???", |npc_data| npc_data.name)
+}
+
+pub fn ui_quest_tracker_system(
+    mut egui_context: EguiContexts,
+    mut ui_state: ResMut<UiStateQuestTracker>,
+    query_player: Query<&QuestState, With<PlayerCharacter>>,
+    game_data: Res<GameData>,
+    quest_objective_table: Res<QuestObjectiveTable>,
+) {
+    let Ok(quest_state) = query_player.get_single() else {
+        return;
+    };
+
+    // Stop tracking quests which have been completed or abandoned
+    if ui_state
+        .tracked_quests
+        .iter()
+        .any(|quest_id| quest_state.find_active_quest(*quest_id).is_none())
+    {
+        ui_state
+            .tracked_quests
+            .retain(|quest_id| quest_state.find_active_quest(*quest_id).is_some());
+    }
+
+    if ui_state.tracked_quests.is_empty() {
+        return;
+    }
+
+    egui::Window::new("Quest Tracker")
+        .anchor(egui::Align2::RIGHT_TOP, [-4.0, 240.0])
+        .frame(egui::Frame::none().fill(egui::Color32::from_rgba_unmultiplied(0, 0, 0, 160)))
+        .title_bar(false)
+        .resizable(false)
+        .default_width(200.0)
+        .show(egui_context.ctx_mut(), |ui| {
+            for &quest_id in ui_state.tracked_quests.iter() {
+                let Some(active_quest) = quest_state.find_active_quest(quest_id) else {
+                    continue;
+                };
+                let quest_name = game_data
+                    .quests
+                    .get_quest_data(quest_id)
+                    .map_or("???", |quest_data| quest_data.name);
+                ui.colored_label(egui::Color32::YELLOW, quest_name);
+
+                let objectives = quest_objective_table.get(quest_id);
+                if let Some(objectives) = objectives {
+                    for objective_item in objectives.items.iter() {
+                        // Same lookup as QF_getQuestItemQuantity
+                        let quantity = active_quest
+                            .find_item(objective_item.item)
+                            .map_or(0, |item| item.get_quantity());
+
+                        ui.colored_label(
+                            if quantity >= objective_item.quantity {
+                                egui::Color32::GREEN
+                            } else {
+                                egui::Color32::WHITE
+                            },
+                            format!(
+                                "  {}: {} / {}",
+                                get_item_name(&game_data, objective_item.item),
+                                quantity,
+                                objective_item.quantity
+                            ),
+                        );
+                    }
+
+                    // Quest variables counted by monster death triggers, labelled with
+                    // the monsters which count towards them
+                    let mut kills = objectives.kills.iter().peekable();
+                    while let Some(kill) = kills.next() {
+                        let mut npc_names = vec![get_npc_name(&game_data, kill.npc)];
+                        while let Some(next_kill) =
+                            kills.next_if(|next_kill| next_kill.variable == kill.variable)
+                        {
+                            npc_names.push(get_npc_name(&game_data, next_kill.npc));
+                        }
+
+                        let value = active_quest
+                            .variables
+                            .get(kill.variable)
+                            .map_or(0, |value| *value as u32);
+                        let npc_names = npc_names.join(", ");
+                        match objectives.required_variables.get(&kill.variable) {
+                            Some(&required) => ui.colored_label(
+                                if value >= required {
+                                    egui::Color32::GREEN
+                                } else {
+                                    egui::Color32::WHITE
+                                },
+                                format!("  {}: {} / {}", npc_names, value, required),
+                            ),
+                            None => ui.label(format!("  {}: {}", npc_names, value)),
+                        };
+                    }
+                }
+
+                // Quest items which are not already shown as an objective
+                for item in active_quest.items.iter().flatten() {
+                    let item_reference = item.get_item_reference();
+                    if objectives.map_or(false, |objectives| {
+                        objectives
+                            .items
+                            .iter()
+                            .any(|objective_item| objective_item.item == item_reference)
+                    }) {
+                        continue;
+                    }
+
+                    ui.label(format!(
+                        "  {}: {}",
+                        get_item_name(&game_data, item_reference),
+                        item.get_quantity()
+                    ));
+                }
+            }
+        });
+}
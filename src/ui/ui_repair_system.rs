@@ -0,0 +1,115 @@
+use bevy::{
+    math::Vec3Swizzles,
+    prelude::{Input, KeyCode, MouseButton, Query, Res, ResMut, With},
+};
+use bevy_egui::{egui, EguiContexts};
+
+use rose_data::EquipmentItem;
+use rose_game_common::components::Inventory;
+
+use crate::{
+    components::{PlayerCharacter, Position},
+    resources::{
+        ClientEntityList, GameData, RepairMode, RepairState, UiCursorType, UiRequestedCursor,
+    },
+    ui::UiStateWindows,
+};
+
+/// Estimates the cost of repairing an item at an NPC from its price, durability
+/// and remaining life.
+///
+/// This is only an approximation, it is not taken from the original client's
+/// ability value calculator. The server decides the actual cost, which is
+/// reported in the chatbox once the item has been repaired.
+pub fn get_repair_cost(game_data: &GameData, equipment_item: &EquipmentItem) -> Option<u64> {
+    let item_data = game_data.items.get_base_item(equipment_item.item)?;
+    let missing_life = 1000u64.saturating_sub(equipment_item.life as u64);
+
+    Some(
+        item_data.base_price as u64 * (equipment_item.durability as u64 + 10) * missing_life
+            / 400000,
+    )
+}
+
+// Other systems may also be using the override cursor, so only clear it if it
+// is the repair cursor we set.
+fn clear_repair_cursor(ui_requested_cursor: &mut UiRequestedCursor) {
+    if ui_requested_cursor.override_cursor == Some(UiCursorType::Repair) {
+        ui_requested_cursor.override_cursor = None;
+    }
+}
+
+pub fn ui_repair_system(
+    mut egui_context: EguiContexts,
+    mut repair_state: ResMut<RepairState>,
+    mut ui_requested_cursor: ResMut<UiRequestedCursor>,
+    mut ui_state_windows: ResMut<UiStateWindows>,
+    client_entity_list: Res<ClientEntityList>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    query_player: Query<(&Position, &Inventory), With<PlayerCharacter>>,
+    query_position: Query<&Position>,
+) {
+    let Some(repair_mode) = repair_state.mode else {
+        clear_repair_cursor(&mut ui_requested_cursor);
+        return;
+    };
+
+    let Ok((player_position, player_inventory)) = query_player.get_single() else {
+        repair_state.cancel();
+        clear_repair_cursor(&mut ui_requested_cursor);
+        return;
+    };
+
+    if repair_state.is_changed() {
+        ui_state_windows.inventory_open = true;
+    }
+
+    let mut cancel = keyboard_input.just_pressed(KeyCode::Escape)
+        || mouse_button_input.just_pressed(MouseButton::Right);
+
+    match repair_mode {
+        RepairMode::UsingNpc { npc_entity_id } => {
+            // If player has moved away from the npc, cancel repairing
+            if client_entity_list
+                .get(npc_entity_id)
+                .and_then(|npc_entity| query_position.get(npc_entity).ok())
+                .map_or(true, |npc_position| {
+                    player_position
+                        .position
+                        .xy()
+                        .distance(npc_position.position.xy())
+                        > 1000.0
+                })
+            {
+                cancel = true;
+            }
+        }
+        RepairMode::UsingItem { use_item_slot } => {
+            if player_inventory.get_item(use_item_slot).is_none() {
+                cancel = true;
+            }
+        }
+    }
+
+    egui::Window::new("Repair")
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 80.0])
+        .title_bar(false)
+        .resizable(false)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Click an equipment item in your inventory to repair it.");
+
+                if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+
+    if cancel {
+        repair_state.cancel();
+        clear_repair_cursor(&mut ui_requested_cursor);
+    } else if ui_requested_cursor.override_cursor != Some(UiCursorType::Repair) {
+        ui_requested_cursor.override_cursor = Some(UiCursorType::Repair);
+    }
+}
@@ -0,0 +1,294 @@
+use bevy::{
+    ecs::query::WorldQuery,
+    math::Vec3Swizzles,
+    prelude::{Assets, Entity, EventReader, EventWriter, Local, Query, Res, ResMut, With},
+};
+use bevy_egui::{egui, EguiContexts};
+
+use rose_data::ItemReference;
+use rose_game_common::{
+    components::{Inventory, ItemSlot},
+    messages::{client::ClientMessage, ClientEntityId},
+};
+
+use crate::{
+    components::{PlayerCharacter, Position},
+    events::CraftingEvent,
+    resources::{ClientEntityList, GameConnection, GameData, UiResources},
+    ui::{
+        tooltips::{PlayerTooltipQuery, PlayerTooltipQueryItem},
+        ui_add_item_tooltip,
+        widgets::{DataBindings, Dialog},
+        DragAndDropId, DragAndDropSlot, UiSoundEvent, UiStateDragAndDrop, UiStateWindows,
+    },
+};
+
+const IID_BTN_CLOSE: i32 = 10;
+const IID_BTN_OK: i32 = 11;
+
+struct PendingSeparate {
+    item_slot: ItemSlot,
+    item_reference: ItemReference,
+    quantity: u32,
+}
+
+#[derive(Default)]
+pub struct UiStateSeparate {
+    npc_entity: Option<Entity>,
+    npc_client_entity_id: Option<ClientEntityId>,
+    item_slot: Option<ItemSlot>,
+    pending: Option<PendingSeparate>,
+    result: Option<String>,
+}
+
+#[derive(WorldQuery)]
+pub struct PlayerQuery<'w> {
+    inventory: &'w Inventory,
+    position: &'w Position,
+}
+
+fn drag_accepts_inventory(drag_source: &DragAndDropId) -> bool {
+    matches!(
+        drag_source,
+        DragAndDropId::Inventory(ItemSlot::Inventory(_, _))
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn ui_add_separate_slot(
+    ui: &mut egui::Ui,
+    pos: egui::Pos2,
+    item_slot: Option<ItemSlot>,
+    player: &PlayerQueryItem,
+    player_tooltip_data: Option<&PlayerTooltipQueryItem>,
+    game_data: &GameData,
+    ui_resources: &UiResources,
+    ui_state_dnd: &mut UiStateDragAndDrop,
+) -> (egui::Response, Option<ItemSlot>) {
+    let item = item_slot.and_then(|item_slot| player.inventory.get_item(item_slot));
+
+    let mut dropped_item = None;
+    let response = ui
+        .allocate_ui_at_rect(
+            egui::Rect::from_min_size(ui.min_rect().min + pos.to_vec2(), egui::vec2(40.0, 40.0)),
+            |ui| {
+                egui::Widget::ui(
+                    DragAndDropSlot::with_item(
+                        DragAndDropId::NotDraggable,
+                        item,
+                        None,
+                        game_data,
+                        ui_resources,
+                        drag_accepts_inventory,
+                        &mut ui_state_dnd.dragged_item,
+                        &mut dropped_item,
+                        [40.0, 40.0],
+                    ),
+                    ui,
+                )
+            },
+        )
+        .inner;
+
+    let response = if let Some(item) = item {
+        response.on_hover_ui(|ui| {
+            ui_add_item_tooltip(ui, game_data, player_tooltip_data, item);
+        })
+    } else {
+        response
+    };
+
+    if let Some(DragAndDropId::Inventory(dropped_inventory_slot)) = dropped_item {
+        (response, Some(dropped_inventory_slot))
+    } else {
+        (response, None)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn ui_separate_system(
+    mut egui_context: EguiContexts,
+    mut ui_state: Local<UiStateSeparate>,
+    mut ui_state_dnd: ResMut<UiStateDragAndDrop>,
+    mut ui_state_windows: ResMut<UiStateWindows>,
+    mut ui_sound_events: EventWriter<UiSoundEvent>,
+    ui_resources: Res<UiResources>,
+    dialog_assets: Res<Assets<Dialog>>,
+    mut crafting_events: EventReader<CraftingEvent>,
+    client_entity_list: Res<ClientEntityList>,
+    game_connection: Option<Res<GameConnection>>,
+    game_data: Res<GameData>,
+    query_player: Query<PlayerQuery, With<PlayerCharacter>>,
+    query_player_tooltip: Query<PlayerTooltipQuery, With<PlayerCharacter>>,
+    query_position: Query<&Position>,
+) {
+    let ui_state = &mut *ui_state;
+    let dialog = if let Some(dialog) = dialog_assets.get(&ui_resources.dialog_separate) {
+        dialog
+    } else {
+        return;
+    };
+
+    for event in crafting_events.iter() {
+        if let CraftingEvent::OpenSeparateFromClientEntity { client_entity_id } = *event {
+            if let Some(entity) = client_entity_list.get(client_entity_id) {
+                *ui_state = UiStateSeparate {
+                    npc_entity: Some(entity),
+                    npc_client_entity_id: Some(client_entity_id),
+                    ..Default::default()
+                };
+                ui_state_windows.separate_open = true;
+                ui_state_windows.inventory_open = true;
+            }
+        }
+    }
+
+    if !ui_state_windows.separate_open {
+        return;
+    }
+
+    let player = if let Ok(player) = query_player.get_single() {
+        player
+    } else {
+        return;
+    };
+    let player_tooltip_data = query_player_tooltip.get_single().ok();
+
+    if let Some(npc_position) = ui_state
+        .npc_entity
+        .and_then(|npc_entity| query_position.get(npc_entity).ok())
+    {
+        // If player has moved away from the npc, close the dialog
+        if player
+            .position
+            .position
+            .xy()
+            .distance(npc_position.position.xy())
+            > 1000.0
+        {
+            ui_state_windows.separate_open = false;
+            ui_state.npc_entity = None;
+            return;
+        }
+    }
+
+    // The server does not send a disassemble result, so we wait for the
+    // inventory update which removes the disassembled item.
+    if let Some(pending) = ui_state.pending.as_ref() {
+        let item_changed = player
+            .inventory
+            .get_item(pending.item_slot)
+            .map_or(true, |item| {
+                item.get_item_reference() != pending.item_reference
+                    || item.get_quantity() != pending.quantity
+            });
+
+        if item_changed {
+            ui_state.result = Some("The item was successfully disassembled.".to_string());
+            ui_state.item_slot = None;
+            ui_state.pending = None;
+        }
+    }
+
+    if ui_state.pending.is_none() {
+        if let Some(item_slot) = ui_state.item_slot {
+            if player.inventory.get_item(item_slot).is_none() {
+                ui_state.item_slot = None;
+            }
+        }
+    }
+
+    let mut response_close_button = None;
+    let mut response_ok_button = None;
+
+    egui::Window::new("Separate")
+        .frame(egui::Frame::none())
+        .title_bar(false)
+        .resizable(false)
+        .default_width(dialog.width)
+        .default_height(dialog.height)
+        .show(egui_context.ctx_mut(), |ui| {
+            dialog.draw(
+                ui,
+                DataBindings {
+                    sound_events: Some(&mut ui_sound_events),
+                    enabled: &mut [(
+                        IID_BTN_OK,
+                        ui_state.pending.is_none() && ui_state.item_slot.is_some(),
+                    )],
+                    response: &mut [
+                        (IID_BTN_CLOSE, &mut response_close_button),
+                        (IID_BTN_OK, &mut response_ok_button),
+                    ],
+                    ..Default::default()
+                },
+                |ui, _| {
+                    let (response, dropped_item_slot) = ui_add_separate_slot(
+                        ui,
+                        egui::pos2(88.0, 60.0),
+                        ui_state.item_slot,
+                        &player,
+                        player_tooltip_data.as_ref(),
+                        &game_data,
+                        &ui_resources,
+                        &mut ui_state_dnd,
+                    );
+
+                    if ui_state.pending.is_none() {
+                        if let Some(dropped_item_slot) = dropped_item_slot {
+                            ui_state.item_slot = Some(dropped_item_slot);
+                            ui_state.result = None;
+                        } else if response.secondary_clicked() {
+                            ui_state.item_slot = None;
+                        }
+                    }
+
+                    let status = if ui_state.pending.is_some() {
+                        Some("Disassembling...")
+                    } else {
+                        ui_state.result.as_deref()
+                    };
+
+                    if let Some(text) = status {
+                        ui.put(
+                            egui::Rect::from_min_size(
+                                ui.min_rect().min + egui::vec2(10.0, 120.0),
+                                egui::vec2(dialog.width - 20.0, 20.0),
+                            ),
+                            egui::Label::new(egui::RichText::new(text).color(egui::Color32::WHITE))
+                                .wrap(true),
+                        );
+                    }
+                },
+            );
+        });
+
+    if response_ok_button.map_or(false, |r| r.clicked()) {
+        if let (Some(item_slot), Some(npc_entity_id), Some(game_connection)) = (
+            ui_state.item_slot,
+            ui_state.npc_client_entity_id,
+            game_connection.as_ref(),
+        ) {
+            if let Some(item) = player.inventory.get_item(item_slot) {
+                game_connection
+                    .client_message_tx
+                    .send(ClientMessage::CraftNpcDisassemble {
+                        npc_entity_id,
+                        item_slot,
+                    })
+                    .ok();
+
+                ui_state.pending = Some(PendingSeparate {
+                    item_slot,
+                    item_reference: item.get_item_reference(),
+                    quantity: item.get_quantity(),
+                });
+                ui_state.result = None;
+            }
+        }
+    }
+
+    if response_close_button.map_or(false, |r| r.clicked()) {
+        ui_state_windows.separate_open = false;
+    }
+}
@@ -1,25 +1,77 @@
-use bevy::prelude::{Local, Query, ResMut};
-use bevy_egui::{egui, EguiContexts};
+use bevy::{
+    math::Vec2,
+    prelude::{Input, KeyCode, Local, Query, Res, ResMut, With},
+    window::{PresentMode, PrimaryWindow, Window, WindowMode},
+};
+use bevy_egui::{egui, EguiContexts, EguiSettings};
 
 use crate::{
-    audio::SoundGain, components::SoundCategory, resources::SoundSettings, ui::UiStateWindows,
+    audio::SoundGain,
+    components::{NameTagType, SoundCategory},
+    resources::{
+        is_modifier_key, GameplaySettings, KeyAction, KeyBinding, KeyBindings, NameTagSettings,
+        RenderConfiguration, SoundSettings,
+    },
+    ui::UiStateWindows,
 };
 
+const WINDOW_RESOLUTIONS: [(f32, f32); 7] = [
+    (1024.0, 768.0),
+    (1280.0, 720.0),
+    (1366.0, 768.0),
+    (1600.0, 900.0),
+    (1920.0, 1080.0),
+    (2560.0, 1440.0),
+    (3840.0, 2160.0),
+];
+
+const UI_SCALES: [f32; 6] = [0.75, 1.0, 1.25, 1.5, 1.75, 2.0];
+
 #[derive(Copy, Clone, PartialEq, Debug)]
 enum SettingsPage {
+    Graphics,
     Sound,
+    Interface,
+    Gameplay,
+    KeyBindings,
 }
 
 pub struct UiStateSettings {
     page: SettingsPage,
+    rebinding_action: Option<KeyAction>,
 }
 
 impl Default for UiStateSettings {
     fn default() -> Self {
         Self {
-            page: SettingsPage::Sound,
+            page: SettingsPage::Graphics,
+            rebinding_action: None,
+        }
+    }
+}
+
+/// Returns the binding for the key pressed while waiting for a new binding.
+///
+/// Modifier keys are only bound on their own when released without pressing
+/// another key, otherwise they are used as modifiers for the next key.
+fn capture_key_binding(keyboard_input: &Input<KeyCode>) -> Option<KeyBinding> {
+    if let Some(key) = keyboard_input
+        .get_just_pressed()
+        .find(|key| !is_modifier_key(**key))
+    {
+        return Some(KeyBinding::from_input(*key, keyboard_input));
+    }
+
+    if keyboard_input.get_pressed().next().is_none() {
+        if let Some(key) = keyboard_input
+            .get_just_released()
+            .find(|key| is_modifier_key(**key))
+        {
+            return Some(KeyBinding::new(*key));
         }
     }
+
+    None
 }
 
 pub fn ui_settings_system(
@@ -28,62 +80,352 @@ pub fn ui_settings_system(
     mut ui_state_settings: Local<UiStateSettings>,
     mut sound_settings: ResMut<SoundSettings>,
     mut query_sounds: Query<(&SoundCategory, &mut SoundGain)>,
+    mut key_bindings: ResMut<KeyBindings>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut render_configuration: ResMut<RenderConfiguration>,
+    mut name_tag_settings: ResMut<NameTagSettings>,
+    mut gameplay_settings: ResMut<GameplaySettings>,
+    mut egui_settings: ResMut<EguiSettings>,
+    mut query_window: Query<&mut Window, With<PrimaryWindow>>,
 ) {
+    let ui_state_settings = &mut *ui_state_settings;
+
+    if let Some(action) = ui_state_settings.rebinding_action {
+        if keyboard_input.just_pressed(KeyCode::Escape) {
+            ui_state_settings.rebinding_action = None;
+        } else if let Some(binding) = capture_key_binding(&keyboard_input) {
+            if !key_bindings.bindings[action].contains(&binding) {
+                key_bindings.bindings[action].push(binding);
+            }
+            ui_state_settings.rebinding_action = None;
+        }
+    }
+
     egui::Window::new("Settings")
         .open(&mut ui_state_windows.settings_open)
         .resizable(false)
         .show(egui_context.ctx_mut(), |ui| {
             ui.horizontal(|ui| {
+                ui.selectable_value(
+                    &mut ui_state_settings.page,
+                    SettingsPage::Graphics,
+                    "Graphics",
+                );
                 ui.selectable_value(&mut ui_state_settings.page, SettingsPage::Sound, "Sound");
+                ui.selectable_value(
+                    &mut ui_state_settings.page,
+                    SettingsPage::Interface,
+                    "Interface",
+                );
+                ui.selectable_value(
+                    &mut ui_state_settings.page,
+                    SettingsPage::Gameplay,
+                    "Gameplay",
+                );
+                ui.selectable_value(
+                    &mut ui_state_settings.page,
+                    SettingsPage::KeyBindings,
+                    "Key Bindings",
+                );
             });
 
-            egui::Grid::new("sound_settings_gain")
-                .num_columns(2)
-                .show(ui, |ui| {
-                    let mut gain_changed = false;
+            match ui_state_settings.page {
+                SettingsPage::Graphics => {
+                    if ui_settings_graphics(ui, &mut render_configuration) {
+                        if let Ok(mut window) = query_window.get_single_mut() {
+                            apply_window_settings(&render_configuration, &mut window);
+                        }
+                    }
+                }
+                SettingsPage::Sound => {
+                    egui::Grid::new("sound_settings_gain")
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            let mut gain_changed = false;
+
+                            ui.label("Sound:");
+                            gain_changed |= ui
+                                .checkbox(&mut sound_settings.enabled, "Enabled")
+                                .changed();
+                            ui.end_row();
 
-                    ui.label("Sound:");
-                    gain_changed |= ui
-                        .checkbox(&mut sound_settings.enabled, "Enabled")
+                            ui.label("Global Volume:");
+                            gain_changed |= ui
+                                .add(
+                                    egui::Slider::new(&mut sound_settings.global_gain, 0.0..=1.0)
+                                        .show_value(true),
+                                )
+                                .changed();
+                            ui.end_row();
+
+                            let mut add_category_slider = |text: &str, category| {
+                                ui.label(text);
+                                gain_changed |= ui
+                                    .add(
+                                        egui::Slider::new(
+                                            &mut sound_settings.gains[category],
+                                            0.0..=1.0,
+                                        )
+                                        .show_value(true),
+                                    )
+                                    .changed();
+                                ui.end_row();
+                            };
+
+                            add_category_slider(
+                                "Background Music:",
+                                SoundCategory::BackgroundMusic,
+                            );
+                            add_category_slider("Player Footsteps:", SoundCategory::PlayerFootstep);
+                            add_category_slider("Other Footsteps:", SoundCategory::OtherFootstep);
+                            add_category_slider("Player Combat:", SoundCategory::PlayerCombat);
+                            add_category_slider("Other Combat:", SoundCategory::OtherCombat);
+                            add_category_slider("NPC Sounds:", SoundCategory::NpcSounds);
+
+                            if gain_changed {
+                                for (category, mut gain) in query_sounds.iter_mut() {
+                                    let target_gain = sound_settings.gain(*category);
+
+                                    if target_gain != *gain {
+                                        *gain = target_gain;
+                                    }
+                                }
+                            }
+                        });
+                }
+                SettingsPage::Interface => {
+                    ui_settings_interface(ui, &mut name_tag_settings, &mut egui_settings);
+                }
+                SettingsPage::Gameplay => {
+                    ui_settings_gameplay(ui, &mut gameplay_settings);
+                }
+                SettingsPage::KeyBindings => {
+                    ui_settings_key_bindings(ui, ui_state_settings, &mut key_bindings);
+                }
+            }
+        });
+}
+
+/// Returns true if a setting which affects the window was changed.
+fn ui_settings_graphics(ui: &mut egui::Ui, render_configuration: &mut RenderConfiguration) -> bool {
+    let mut window_changed = false;
+
+    egui::Grid::new("settings_graphics")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Window Mode:");
+            egui::ComboBox::from_id_source("settings_window_mode")
+                .selected_text(if render_configuration.fullscreen {
+                    "Fullscreen"
+                } else {
+                    "Windowed"
+                })
+                .show_ui(ui, |ui| {
+                    window_changed |= ui
+                        .selectable_value(&mut render_configuration.fullscreen, false, "Windowed")
                         .changed();
-                    ui.end_row();
+                    window_changed |= ui
+                        .selectable_value(&mut render_configuration.fullscreen, true, "Fullscreen")
+                        .changed();
+                });
+            ui.end_row();
+
+            ui.label("Resolution:");
+            ui.add_enabled_ui(!render_configuration.fullscreen, |ui| {
+                let window_size = render_configuration.window_size;
+                egui::ComboBox::from_id_source("settings_window_resolution")
+                    .selected_text(format!("{} x {}", window_size.x, window_size.y))
+                    .show_ui(ui, |ui| {
+                        for (width, height) in WINDOW_RESOLUTIONS {
+                            window_changed |= ui
+                                .selectable_value(
+                                    &mut render_configuration.window_size,
+                                    Vec2::new(width, height),
+                                    format!("{} x {}", width, height),
+                                )
+                                .changed();
+                        }
+                    });
+            });
+            ui.end_row();
+
+            ui.label("V-Sync:");
+            let mut vsync = !render_configuration.disable_vsync;
+            if ui.checkbox(&mut vsync, "Enabled").changed() {
+                render_configuration.disable_vsync = !vsync;
+                window_changed = true;
+            }
+            ui.end_row();
+
+            ui.label("Shadow Distance:");
+            ui.add(egui::Slider::new(
+                &mut render_configuration.shadow_distance,
+                10.0..=200.0,
+            ));
+            ui.end_row();
+
+            ui.label("Fog Distance:");
+            ui.add(
+                egui::Slider::new(&mut render_configuration.fog_distance_multiplier, 0.5..=3.0)
+                    .suffix("x"),
+            );
+            ui.end_row();
+
+            ui.label("Trail Effect Duration:");
+            ui.add(
+                egui::Slider::new(
+                    &mut render_configuration.trail_effect_duration_multiplier,
+                    0.0..=3.0,
+                )
+                .suffix("x"),
+            );
+            ui.end_row();
+        });
+
+    window_changed
+}
+
+fn apply_window_settings(render_configuration: &RenderConfiguration, window: &mut Window) {
+    if render_configuration.fullscreen {
+        window.mode = WindowMode::BorderlessFullscreen;
+    } else {
+        window.mode = WindowMode::Windowed;
+        window.resolution.set(
+            render_configuration.window_size.x,
+            render_configuration.window_size.y,
+        );
+    }
 
-                    ui.label("Global Volume:");
-                    gain_changed |= ui
-                        .add(
-                            egui::Slider::new(&mut sound_settings.global_gain, 0.0..=1.0)
-                                .show_value(true),
+    window.present_mode = if render_configuration.disable_vsync {
+        PresentMode::Immediate
+    } else {
+        PresentMode::Fifo
+    };
+}
+
+fn ui_settings_interface(
+    ui: &mut egui::Ui,
+    name_tag_settings: &mut NameTagSettings,
+    egui_settings: &mut EguiSettings,
+) {
+    egui::Grid::new("settings_interface")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("UI Scale:");
+            let ui_scale = egui_settings.scale_factor as f32;
+            egui::ComboBox::from_id_source("settings_ui_scale")
+                .selected_text(format!("{:.0}%", ui_scale * 100.0))
+                .show_ui(ui, |ui| {
+                    for scale in UI_SCALES {
+                        if ui
+                            .selectable_label(ui_scale == scale, format!("{:.0}%", scale * 100.0))
+                            .clicked()
+                        {
+                            egui_settings.scale_factor = scale as f64;
+                        }
+                    }
+                });
+            ui.end_row();
+
+            let mut add_name_tag_settings = |text: &str, name_tag_type| {
+                ui.label(text);
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut name_tag_settings.show_all[name_tag_type], "Show All");
+                    ui.add(
+                        egui::Slider::new(
+                            &mut name_tag_settings.font_size[name_tag_type],
+                            8.0..=32.0,
                         )
-                        .changed();
-                    ui.end_row();
-
-                    let mut add_category_slider = |text: &str, category| {
-                        ui.label(text);
-                        gain_changed |= ui
-                            .add(
-                                egui::Slider::new(&mut sound_settings.gains[category], 0.0..=1.0)
-                                    .show_value(true),
-                            )
-                            .changed();
-                        ui.end_row();
-                    };
+                        .text("Font Size"),
+                    );
+                });
+                ui.end_row();
+            };
 
-                    add_category_slider("Background Music:", SoundCategory::BackgroundMusic);
-                    add_category_slider("Player Footsteps:", SoundCategory::PlayerFootstep);
-                    add_category_slider("Other Footsteps:", SoundCategory::OtherFootstep);
-                    add_category_slider("Player Combat:", SoundCategory::PlayerCombat);
-                    add_category_slider("Other Combat:", SoundCategory::OtherCombat);
-                    add_category_slider("NPC Sounds:", SoundCategory::NpcSounds);
+            add_name_tag_settings("Character Names:", NameTagType::Character);
+            add_name_tag_settings("NPC Names:", NameTagType::Npc);
+            add_name_tag_settings("Monster Names:", NameTagType::Monster);
+        });
+}
 
-                    if gain_changed {
-                        for (category, mut gain) in query_sounds.iter_mut() {
-                            let target_gain = sound_settings.gain(*category);
+fn ui_settings_gameplay(ui: &mut egui::Ui, gameplay_settings: &mut GameplaySettings) {
+    egui::Grid::new("settings_gameplay")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Auto Pickup:");
+            ui.checkbox(&mut gameplay_settings.auto_pickup, "Enabled");
+            ui.end_row();
 
-                            if target_gain != *gain {
-                                *gain = target_gain;
+            ui.label("Camera Sensitivity:");
+            ui.add(
+                egui::Slider::new(&mut gameplay_settings.camera_sensitivity, 0.1..=3.0).suffix("x"),
+            );
+            ui.end_row();
+        });
+}
+
+fn ui_settings_key_bindings(
+    ui: &mut egui::Ui,
+    ui_state_settings: &mut UiStateSettings,
+    key_bindings: &mut KeyBindings,
+) {
+    ui.label("Click a binding to remove it.");
+
+    egui::ScrollArea::vertical()
+        .max_height(400.0)
+        .show(ui, |ui| {
+            egui::Grid::new("settings_key_bindings")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    for action in KeyAction::iter() {
+                        ui.label(action.display_name());
+
+                        ui.horizontal(|ui| {
+                            let mut remove_binding = None;
+
+                            for binding in key_bindings.bindings[action].iter() {
+                                let mut text = egui::RichText::new(binding.to_string());
+                                if key_bindings.is_conflicting(action, binding) {
+                                    text = text.color(egui::Color32::RED);
+                                }
+
+                                if ui.button(text).clicked() {
+                                    remove_binding = Some(*binding);
+                                }
                             }
-                        }
+
+                            if let Some(binding) = remove_binding {
+                                key_bindings.bindings[action].retain(|other| *other != binding);
+                            }
+
+                            if ui_state_settings.rebinding_action == Some(action) {
+                                if ui.button("Press a key...").clicked() {
+                                    ui_state_settings.rebinding_action = None;
+                                }
+                            } else if ui.button("+").clicked() {
+                                ui_state_settings.rebinding_action = Some(action);
+                            }
+                        });
+                        ui.end_row();
                     }
                 });
         });
+
+    for (binding, action_a, action_b) in key_bindings.conflicts() {
+        ui.colored_label(
+            egui::Color32::RED,
+            format!(
+                "{} is bound to both {} and {}",
+                binding,
+                action_a.display_name(),
+                action_b.display_name()
+            ),
+        );
+    }
+
+    if ui.button("Reset to Defaults").clicked() {
+        *key_bindings = KeyBindings::default();
+        ui_state_settings.rebinding_action = None;
+    }
 }
@@ -0,0 +1,427 @@
+use std::time::{Duration, Instant};
+
+use bevy::{
+    ecs::query::WorldQuery,
+    math::Vec3Swizzles,
+    prelude::{Assets, Entity, EventReader, EventWriter, Local, Query, Res, ResMut, With},
+};
+use bevy_egui::{egui, EguiContexts};
+
+use rose_game_common::{
+    components::{Inventory, InventoryPageType, ItemSlot},
+    messages::{client::ClientMessage, ClientEntityId},
+};
+
+use crate::{
+    components::{PlayerCharacter, Position},
+    events::CraftingEvent,
+    resources::{ClientEntityList, GameConnection, GameData, UiResources},
+    ui::{
+        tooltips::{PlayerTooltipQuery, PlayerTooltipQueryItem},
+        ui_add_item_tooltip,
+        widgets::{DataBindings, Dialog},
+        DragAndDropId, DragAndDropSlot, UiSoundEvent, UiStateDragAndDrop, UiStateWindows,
+    },
+};
+
+const IID_BTN_CLOSE: i32 = 10;
+const IID_BTN_OK: i32 = 11;
+
+const NUM_INGREDIENTS: usize = 3;
+
+// How long to wait for the inventory updates of an upgrade before giving up
+const UPGRADE_RESULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+struct PendingUpgrade {
+    item_slot: ItemSlot,
+    grade: u8,
+    ingredient_quantities: [Option<(ItemSlot, u32)>; NUM_INGREDIENTS],
+    requested_at: Instant,
+}
+
+#[derive(Default)]
+pub struct UiStateUpgrade {
+    npc_entity: Option<Entity>,
+    npc_client_entity_id: Option<ClientEntityId>,
+    item_slot: Option<ItemSlot>,
+    ingredients: [Option<(ItemSlot, u32)>; NUM_INGREDIENTS],
+    pending: Option<PendingUpgrade>,
+    result: Option<(bool, String)>,
+}
+
+#[derive(WorldQuery)]
+pub struct PlayerQuery<'w> {
+    inventory: &'w Inventory,
+    position: &'w Position,
+}
+
+fn drag_accepts_equipment(drag_source: &DragAndDropId) -> bool {
+    matches!(
+        drag_source,
+        DragAndDropId::Inventory(ItemSlot::Inventory(InventoryPageType::Equipment, _))
+    )
+}
+
+fn drag_accepts_materials(drag_source: &DragAndDropId) -> bool {
+    matches!(
+        drag_source,
+        DragAndDropId::Inventory(ItemSlot::Inventory(InventoryPageType::Materials, _))
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn ui_add_upgrade_slot(
+    ui: &mut egui::Ui,
+    pos: egui::Pos2,
+    item_slot: Option<ItemSlot>,
+    drag_accepts: fn(&DragAndDropId) -> bool,
+    player: &PlayerQueryItem,
+    player_tooltip_data: Option<&PlayerTooltipQueryItem>,
+    game_data: &GameData,
+    ui_resources: &UiResources,
+    ui_state_dnd: &mut UiStateDragAndDrop,
+) -> (egui::Response, Option<ItemSlot>) {
+    let item = item_slot.and_then(|item_slot| player.inventory.get_item(item_slot));
+
+    let mut dropped_item = None;
+    let response = ui
+        .allocate_ui_at_rect(
+            egui::Rect::from_min_size(ui.min_rect().min + pos.to_vec2(), egui::vec2(40.0, 40.0)),
+            |ui| {
+                egui::Widget::ui(
+                    DragAndDropSlot::with_item(
+                        DragAndDropId::NotDraggable,
+                        item,
+                        None,
+                        game_data,
+                        ui_resources,
+                        drag_accepts,
+                        &mut ui_state_dnd.dragged_item,
+                        &mut dropped_item,
+                        [40.0, 40.0],
+                    ),
+                    ui,
+                )
+            },
+        )
+        .inner;
+
+    let response = if let Some(item) = item {
+        response.on_hover_ui(|ui| {
+            ui_add_item_tooltip(ui, game_data, player_tooltip_data, item);
+        })
+    } else {
+        response
+    };
+
+    if let Some(DragAndDropId::Inventory(dropped_inventory_slot)) = dropped_item {
+        (response, Some(dropped_inventory_slot))
+    } else {
+        (response, None)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn ui_upgrade_system(
+    mut egui_context: EguiContexts,
+    mut ui_state: Local<UiStateUpgrade>,
+    mut ui_state_dnd: ResMut<UiStateDragAndDrop>,
+    mut ui_state_windows: ResMut<UiStateWindows>,
+    mut ui_sound_events: EventWriter<UiSoundEvent>,
+    ui_resources: Res<UiResources>,
+    dialog_assets: Res<Assets<Dialog>>,
+    mut crafting_events: EventReader<CraftingEvent>,
+    client_entity_list: Res<ClientEntityList>,
+    game_connection: Option<Res<GameConnection>>,
+    game_data: Res<GameData>,
+    query_player: Query<PlayerQuery, With<PlayerCharacter>>,
+    query_player_tooltip: Query<PlayerTooltipQuery, With<PlayerCharacter>>,
+    query_position: Query<&Position>,
+) {
+    let ui_state = &mut *ui_state;
+    let dialog = if let Some(dialog) = dialog_assets.get(&ui_resources.dialog_upgrade) {
+        dialog
+    } else {
+        return;
+    };
+
+    for event in crafting_events.iter() {
+        if let CraftingEvent::OpenUpgradeFromClientEntity { client_entity_id } = *event {
+            if let Some(entity) = client_entity_list.get(client_entity_id) {
+                *ui_state = UiStateUpgrade {
+                    npc_entity: Some(entity),
+                    npc_client_entity_id: Some(client_entity_id),
+                    ..Default::default()
+                };
+                ui_state_windows.upgrade_open = true;
+                ui_state_windows.inventory_open = true;
+            }
+        }
+    }
+
+    if !ui_state_windows.upgrade_open {
+        return;
+    }
+
+    let player = if let Ok(player) = query_player.get_single() {
+        player
+    } else {
+        return;
+    };
+    let player_tooltip_data = query_player_tooltip.get_single().ok();
+
+    if let Some(npc_position) = ui_state
+        .npc_entity
+        .and_then(|npc_entity| query_position.get(npc_entity).ok())
+    {
+        // If player has moved away from the npc, close the dialog
+        if player
+            .position
+            .position
+            .xy()
+            .distance(npc_position.position.xy())
+            > 1000.0
+        {
+            ui_state_windows.upgrade_open = false;
+            ui_state.npc_entity = None;
+            return;
+        }
+    }
+
+    // The server does not send an upgrade result, so we determine it from the
+    // inventory updates for the upgraded item and its ingredients.
+    if let Some(pending) = ui_state.pending.as_ref() {
+        let item_grade = player
+            .inventory
+            .get_item(pending.item_slot)
+            .and_then(|item| item.as_equipment())
+            .map(|equipment_item| equipment_item.grade);
+        let ingredients_changed =
+            pending
+                .ingredient_quantities
+                .iter()
+                .flatten()
+                .any(|&(item_slot, quantity)| {
+                    player
+                        .inventory
+                        .get_item(item_slot)
+                        .map_or(0, |item| item.get_quantity())
+                        != quantity
+                });
+
+        let result = match item_grade {
+            None => Some((
+                false,
+                "The upgrade failed and the item was destroyed.".to_string(),
+            )),
+            Some(grade) if grade > pending.grade => Some((
+                true,
+                format!("The upgrade succeeded, the item is now +{}.", grade),
+            )),
+            Some(grade) if grade < pending.grade => Some((
+                false,
+                format!("The upgrade failed, the item has dropped to +{}.", grade),
+            )),
+            Some(_) if ingredients_changed => Some((false, "The upgrade failed.".to_string())),
+            Some(_) if game_connection.is_none() => Some((
+                false,
+                "The connection to the server was lost during the upgrade.".to_string(),
+            )),
+            Some(_) if pending.requested_at.elapsed() > UPGRADE_RESULT_TIMEOUT => Some((
+                false,
+                "The server did not respond to the upgrade request.".to_string(),
+            )),
+            Some(_) => None,
+        };
+
+        if let Some(result) = result {
+            ui_state.result = Some(result);
+            ui_state.ingredients = Default::default();
+            ui_state.pending = None;
+        }
+    }
+
+    // Clear any slots whose item has since been moved or used
+    if ui_state.pending.is_none() {
+        if let Some(item_slot) = ui_state.item_slot {
+            if player
+                .inventory
+                .get_item(item_slot)
+                .and_then(|item| item.as_equipment())
+                .is_none()
+            {
+                ui_state.item_slot = None;
+            }
+        }
+
+        for ingredient in ui_state.ingredients.iter_mut() {
+            if let Some((item_slot, quantity)) = ingredient.as_mut() {
+                match player.inventory.get_item(*item_slot) {
+                    Some(item) => *quantity = (*quantity).min(item.get_quantity()).max(1),
+                    None => *ingredient = None,
+                }
+            }
+        }
+    }
+
+    let mut response_close_button = None;
+    let mut response_ok_button = None;
+
+    egui::Window::new("Upgrade")
+        .frame(egui::Frame::none())
+        .title_bar(false)
+        .resizable(false)
+        .default_width(dialog.width)
+        .default_height(dialog.height)
+        .show(egui_context.ctx_mut(), |ui| {
+            dialog.draw(
+                ui,
+                DataBindings {
+                    sound_events: Some(&mut ui_sound_events),
+                    enabled: &mut [(
+                        IID_BTN_OK,
+                        ui_state.pending.is_none()
+                            && ui_state.item_slot.is_some()
+                            && ui_state.ingredients.iter().any(|x| x.is_some()),
+                    )],
+                    response: &mut [
+                        (IID_BTN_CLOSE, &mut response_close_button),
+                        (IID_BTN_OK, &mut response_ok_button),
+                    ],
+                    ..Default::default()
+                },
+                |ui, _| {
+                    let (response, dropped_item_slot) = ui_add_upgrade_slot(
+                        ui,
+                        egui::pos2(88.0, 60.0),
+                        ui_state.item_slot,
+                        drag_accepts_equipment,
+                        &player,
+                        player_tooltip_data.as_ref(),
+                        &game_data,
+                        &ui_resources,
+                        &mut ui_state_dnd,
+                    );
+
+                    if ui_state.pending.is_none() {
+                        if let Some(dropped_item_slot) = dropped_item_slot {
+                            ui_state.item_slot = Some(dropped_item_slot);
+                            ui_state.result = None;
+                        } else if response.secondary_clicked() {
+                            ui_state.item_slot = None;
+                        }
+                    }
+
+                    for (index, ingredient) in ui_state.ingredients.iter_mut().enumerate() {
+                        let pos = egui::pos2(25.0 + index as f32 * 63.0, 140.0);
+                        let (response, dropped_item_slot) = ui_add_upgrade_slot(
+                            ui,
+                            pos,
+                            ingredient.map(|(item_slot, _)| item_slot),
+                            drag_accepts_materials,
+                            &player,
+                            player_tooltip_data.as_ref(),
+                            &game_data,
+                            &ui_resources,
+                            &mut ui_state_dnd,
+                        );
+
+                        if ui_state.pending.is_some() {
+                            continue;
+                        }
+
+                        if let Some(dropped_item_slot) = dropped_item_slot {
+                            *ingredient = Some((dropped_item_slot, 1));
+                            ui_state.result = None;
+                        } else if response.secondary_clicked() {
+                            *ingredient = None;
+                        }
+
+                        if let Some((item_slot, quantity)) = ingredient.as_mut() {
+                            let max_quantity = player
+                                .inventory
+                                .get_item(*item_slot)
+                                .map_or(1, |item| item.get_quantity());
+
+                            ui.put(
+                                egui::Rect::from_min_size(
+                                    ui.min_rect().min + pos.to_vec2() + egui::vec2(0.0, 44.0),
+                                    egui::vec2(40.0, 18.0),
+                                ),
+                                egui::DragValue::new(quantity).clamp_range(1..=max_quantity),
+                            );
+                        }
+                    }
+
+                    let status = if ui_state.pending.is_some() {
+                        Some((egui::Color32::WHITE, "Upgrading..."))
+                    } else {
+                        ui_state.result.as_ref().map(|(success, message)| {
+                            if *success {
+                                (egui::Color32::GREEN, message.as_str())
+                            } else {
+                                (egui::Color32::RED, message.as_str())
+                            }
+                        })
+                    };
+
+                    if let Some((color, text)) = status {
+                        ui.put(
+                            egui::Rect::from_min_size(
+                                ui.min_rect().min + egui::vec2(10.0, 210.0),
+                                egui::vec2(dialog.width - 20.0, 20.0),
+                            ),
+                            egui::Label::new(egui::RichText::new(text).color(color)).wrap(true),
+                        );
+                    }
+                },
+            );
+        });
+
+    if response_ok_button.map_or(false, |r| r.clicked()) {
+        if let (Some(item_slot), Some(npc_entity_id), Some(game_connection)) = (
+            ui_state.item_slot,
+            ui_state.npc_client_entity_id,
+            game_connection.as_ref(),
+        ) {
+            if let Some(equipment_item) = player
+                .inventory
+                .get_item(item_slot)
+                .and_then(|item| item.as_equipment())
+            {
+                let ingredients = ui_state.ingredients.iter().flatten().copied().collect();
+
+                game_connection
+                    .client_message_tx
+                    .send(ClientMessage::CraftNpcUpgradeItem {
+                        npc_entity_id,
+                        item_slot,
+                        ingredients,
+                    })
+                    .ok();
+
+                ui_state.pending = Some(PendingUpgrade {
+                    item_slot,
+                    grade: equipment_item.grade,
+                    ingredient_quantities: ui_state.ingredients.map(|ingredient| {
+                        ingredient.map(|(item_slot, _)| {
+                            (
+                                item_slot,
+                                player
+                                    .inventory
+                                    .get_item(item_slot)
+                                    .map_or(0, |item| item.get_quantity()),
+                            )
+                        })
+                    }),
+                    requested_at: Instant::now(),
+                });
+                ui_state.result = None;
+            }
+        }
+    }
+
+    if response_close_button.map_or(false, |r| r.clicked()) {
+        ui_state_windows.upgrade_open = false;
+    }
+}
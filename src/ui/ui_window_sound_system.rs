@@ -41,6 +41,21 @@ pub fn ui_window_sound_system(
         next.clan_open,
         &ui_resources.dialog_clan,
     );
+    play_dialog_sound(
+        &mut state.community_open,
+        next.community_open,
+        &ui_resources.dialog_community,
+    );
+    play_dialog_sound(
+        &mut state.help_open,
+        next.help_open,
+        &ui_resources.dialog_help,
+    );
+    play_dialog_sound(
+        &mut state.info_open,
+        next.info_open,
+        &ui_resources.dialog_player_info,
+    );
     play_dialog_sound(
         &mut state.inventory_open,
         next.inventory_open,
@@ -62,6 +77,11 @@ pub fn ui_window_sound_system(
         &ui_resources.dialog_quest_list,
     );
     // play_dialog_sound(&mut state.settings_open, next.settings_open, &ui_resources.dialog_..);
+    play_dialog_sound(
+        &mut state.exit_open,
+        next.exit_open,
+        &ui_resources.dialog_system,
+    );
     play_dialog_sound(
         &mut state.menu_open,
         next.menu_open,
@@ -88,4 +108,14 @@ pub fn ui_window_sound_system(
         next.create_clan_open,
         &ui_resources.dialog_create_clan,
     );
+    play_dialog_sound(
+        &mut state.separate_open,
+        next.separate_open,
+        &ui_resources.dialog_separate,
+    );
+    play_dialog_sound(
+        &mut state.upgrade_open,
+        next.upgrade_open,
+        &ui_resources.dialog_upgrade,
+    );
 }
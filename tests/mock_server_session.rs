@@ -0,0 +1,485 @@
+use bevy::{
+    math::Vec3,
+    prelude::{
+        App, EventReader, EventWriter, IntoSystemConfigs, Local, MinimalPlugins, PostUpdate,
+        PreUpdate, Res, State, Update,
+    },
+};
+use std::{path::Path, time::Duration};
+
+use rose_file_readers::{VfsIndex, VirtualFilesystem, VirtualFilesystemDevice};
+use rose_game_common::{
+    data::Password,
+    messages::{client::ClientMessage, server::ServerMessage},
+};
+
+use rose_offline_client::{
+    components::{ClientEntity, Command, CommandAttack, CommandMove, NextCommand},
+    events::{
+        BankEvent, CharacterSelectEvent, ChatboxEvent, ClientEntityEvent, GameConnectionEvent,
+        LoadZoneEvent, LoginEvent, MessageBoxEvent, NetworkEvent, PartyEvent, PersonalStoreEvent,
+        QuestTriggerEvent, UseItemEvent, WorldConnectionEvent, ZoneEvent,
+    },
+    get_game_data_irose,
+    protocol::{
+        irose::{
+            self, MockClientPackets, MockServer, MockServerScript, MOCK_CHARACTER_NAME,
+            MOCK_ENTITY_ID, MOCK_SERVER_ID,
+        },
+        NetworkProtocolClient, NetworkStatistics, PacketInspector, ProtocolClient,
+        ProtocolRegistry, SessionConnectionType,
+    },
+    resources::{
+        run_network_thread, AppState, CharacterList, ClientEntityList, ConnectionState,
+        GameConnection, LoginConnection, LoginState, NetworkSession, NetworkThread,
+        ServerConfiguration, WorldConnection,
+    },
+    systems::{
+        auto_login_system, game_connection_system, game_zone_change_system,
+        login_connection_system, login_event_system, network_thread_system,
+        world_connection_system,
+    },
+};
+
+const PACKET_CODEC_SEED: u32 = 0x1234;
+
+fn run_client(mut client: Box<dyn NetworkProtocolClient>) {
+    tokio::spawn(async move {
+        if let Err(error) = client.run_connection().await {
+            log::debug!("Mock session client disconnected: {}", error);
+        }
+    });
+}
+
+async fn recv_server_message(
+    server_message_rx: &crossbeam_channel::Receiver<ServerMessage>,
+) -> ServerMessage {
+    for _ in 0..500 {
+        if let Ok(message) = server_message_rx.try_recv() {
+            return message;
+        }
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    panic!("Timed out waiting for server message");
+}
+
+/// Runs the login, server select, character select and join zone flow of the
+/// irose protocol clients against the default mock server script, sending the
+/// same client messages as the login, character select and game systems.
+#[test]
+fn login_to_join_zone() {
+    let mock_server = MockServer::bind().unwrap();
+    let addresses = mock_server.addresses();
+    let mock_server = mock_server.spawn(
+        MockServerScript::default_session(PACKET_CODEC_SEED),
+        PACKET_CODEC_SEED,
+    );
+    let network_protocol = irose::network_protocol();
+
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async move {
+            // Login server
+            let (client_message_tx, client_message_rx) = tokio::sync::mpsc::unbounded_channel();
+            let (server_message_tx, server_message_rx) = crossbeam_channel::unbounded();
+            run_client((network_protocol.login_client)(
+                addresses.login,
+                client_message_rx,
+                server_message_tx,
+            ));
+            let login_connection = LoginConnection::new(client_message_tx, server_message_rx);
+
+            assert!(matches!(
+                recv_server_message(&login_connection.server_message_rx).await,
+                ServerMessage::ConnectionRequestSuccess { .. }
+            ));
+            login_connection
+                .client_message_tx
+                .send(ClientMessage::LoginRequest {
+                    username: "mock".to_string(),
+                    password: Password::Plaintext("mock".to_string()),
+                })
+                .unwrap();
+
+            let ServerMessage::LoginSuccess { server_list } =
+                recv_server_message(&login_connection.server_message_rx).await
+            else {
+                panic!("Expected LoginSuccess");
+            };
+            let server_id = server_list[0].0;
+            assert_eq!(server_id as usize, MOCK_SERVER_ID);
+            login_connection
+                .client_message_tx
+                .send(ClientMessage::GetChannelList {
+                    server_id: server_id as usize,
+                })
+                .unwrap();
+
+            let ServerMessage::ChannelList { channels, .. } =
+                recv_server_message(&login_connection.server_message_rx).await
+            else {
+                panic!("Expected ChannelList");
+            };
+            let channel_id = channels[0].0;
+            login_connection
+                .client_message_tx
+                .send(ClientMessage::JoinServer {
+                    server_id: server_id as usize,
+                    channel_id: channel_id as usize,
+                })
+                .unwrap();
+
+            let ServerMessage::JoinServerSuccess {
+                login_token,
+                packet_codec_seed,
+                port,
+                ..
+            } = recv_server_message(&login_connection.server_message_rx).await
+            else {
+                panic!("Expected JoinServerSuccess");
+            };
+            assert_eq!(packet_codec_seed, PACKET_CODEC_SEED);
+            assert_eq!(port, addresses.world.port());
+
+            // World server
+            let (client_message_tx, client_message_rx) = tokio::sync::mpsc::unbounded_channel();
+            let (server_message_tx, server_message_rx) = crossbeam_channel::unbounded();
+            run_client((network_protocol.world_client)(
+                addresses.world,
+                packet_codec_seed,
+                client_message_rx,
+                server_message_tx,
+            ));
+            let world_connection = WorldConnection::new(
+                client_message_tx,
+                server_message_rx,
+                login_token,
+                Password::Plaintext("mock".to_string()),
+            );
+
+            assert!(matches!(
+                recv_server_message(&world_connection.server_message_rx).await,
+                ServerMessage::ConnectionRequestSuccess { .. }
+            ));
+            world_connection
+                .client_message_tx
+                .send(ClientMessage::GetCharacterList)
+                .unwrap();
+
+            let ServerMessage::CharacterList { character_list } =
+                recv_server_message(&world_connection.server_message_rx).await
+            else {
+                panic!("Expected CharacterList");
+            };
+            assert_eq!(character_list[0].info.name, MOCK_CHARACTER_NAME);
+            world_connection
+                .client_message_tx
+                .send(ClientMessage::SelectCharacter {
+                    slot: 0,
+                    name: character_list[0].info.name.clone(),
+                })
+                .unwrap();
+
+            let ServerMessage::SelectCharacterSuccess {
+                login_token,
+                packet_codec_seed,
+                port,
+                ..
+            } = recv_server_message(&world_connection.server_message_rx).await
+            else {
+                panic!("Expected SelectCharacterSuccess");
+            };
+            assert_eq!(port, addresses.game.port());
+
+            // Game server
+            let (client_message_tx, client_message_rx) = tokio::sync::mpsc::unbounded_channel();
+            let (chat_message_tx, chat_message_rx) = tokio::sync::mpsc::unbounded_channel();
+            let (server_message_tx, server_message_rx) = crossbeam_channel::unbounded();
+            run_client((network_protocol.game_client)(
+                addresses.game,
+                packet_codec_seed,
+                client_message_rx,
+                chat_message_rx,
+                server_message_tx,
+            ));
+            let game_connection = GameConnection::new(
+                client_message_tx,
+                chat_message_tx,
+                server_message_rx,
+                login_token,
+                Password::Plaintext("mock".to_string()),
+            );
+
+            assert!(matches!(
+                recv_server_message(&game_connection.server_message_rx).await,
+                ServerMessage::ConnectionRequestSuccess { .. }
+            ));
+            let ServerMessage::CharacterData { data } =
+                recv_server_message(&game_connection.server_message_rx).await
+            else {
+                panic!("Expected CharacterData");
+            };
+            assert_eq!(data.character_info.name, MOCK_CHARACTER_NAME);
+            assert!(matches!(
+                recv_server_message(&game_connection.server_message_rx).await,
+                ServerMessage::CharacterDataItems { .. }
+            ));
+            assert!(matches!(
+                recv_server_message(&game_connection.server_message_rx).await,
+                ServerMessage::CharacterDataQuest { .. }
+            ));
+            game_connection
+                .client_message_tx
+                .send(ClientMessage::JoinZoneRequest)
+                .unwrap();
+
+            assert!(matches!(
+                recv_server_message(&game_connection.server_message_rx).await,
+                ServerMessage::JoinZone { .. }
+            ));
+
+            game_connection
+                .client_message_tx
+                .send(ClientMessage::Move {
+                    target_entity_id: None,
+                    x: 521000.0,
+                    y: 522000.0,
+                    z: 10,
+                })
+                .unwrap();
+            let ServerMessage::MoveEntity {
+                entity_id, x, y, z, ..
+            } = recv_server_message(&game_connection.server_message_rx).await
+            else {
+                panic!("Expected MoveEntity");
+            };
+            assert_eq!(entity_id, MOCK_ENTITY_ID);
+            assert_eq!((x, y, z), (521000.0, 522000.0, 10));
+
+            game_connection
+                .client_message_tx
+                .send(ClientMessage::Attack {
+                    target_entity_id: MOCK_ENTITY_ID,
+                })
+                .unwrap();
+            let ServerMessage::AttackEntity {
+                entity_id,
+                target_entity_id,
+                ..
+            } = recv_server_message(&game_connection.server_message_rx).await
+            else {
+                panic!("Expected AttackEntity");
+            };
+            assert_eq!(entity_id, MOCK_ENTITY_ID);
+            assert_eq!(target_entity_id, MOCK_ENTITY_ID);
+        });
+
+    assert!(mock_server.has_received(
+        SessionConnectionType::Game,
+        MockClientPackets::JoinZone as u16
+    ));
+}
+
+/// Stands in for the character select screen and zone loader, which both need
+/// a window and the game assets: plays the character chosen by auto login and
+/// reports the zone as loaded as soon as the game server has connected.
+fn headless_character_select_system(
+    mut selected_character: Local<Option<usize>>,
+    mut character_select_events: EventReader<CharacterSelectEvent>,
+    mut game_connection_events: EventReader<GameConnectionEvent>,
+    mut zone_events: EventWriter<ZoneEvent>,
+    character_list: Option<Res<CharacterList>>,
+    world_connection: Option<Res<WorldConnection>>,
+) {
+    for event in character_select_events.iter() {
+        match *event {
+            CharacterSelectEvent::SelectCharacter(index) => *selected_character = Some(index),
+            CharacterSelectEvent::PlaySelected => {
+                let (Some(index), Some(character_list), Some(world_connection)) = (
+                    *selected_character,
+                    character_list.as_ref(),
+                    world_connection.as_ref(),
+                ) else {
+                    continue;
+                };
+
+                world_connection
+                    .client_message_tx
+                    .send(ClientMessage::SelectCharacter {
+                        slot: index as u8,
+                        name: character_list.characters[index].info.name.clone(),
+                    })
+                    .ok();
+            }
+            _ => {}
+        }
+    }
+
+    for event in game_connection_events.iter() {
+        if let &GameConnectionEvent::Connected(zone_id) = event {
+            zone_events.send(ZoneEvent::Loaded(zone_id));
+        }
+    }
+}
+
+fn create_headless_app(mock_server: &MockServer) -> App {
+    let vfs_path = std::env::var("ROSE_VFS_PATH")
+        .expect("ROSE_VFS_PATH must be set to the path of data.idx to run this test");
+    let vfs_devices: Vec<Box<dyn VirtualFilesystemDevice + Send + Sync>> =
+        vec![Box::new(VfsIndex::load(Path::new(&vfs_path)).unwrap())];
+    let game_data = get_game_data_irose(&VirtualFilesystem::new(vfs_devices));
+
+    let addresses = mock_server.addresses();
+    let mut protocol_registry = ProtocolRegistry::default();
+    protocol_registry.register("irose", irose::network_protocol());
+    assert!(protocol_registry.select("irose"));
+
+    let (network_thread_tx, network_thread_rx) = tokio::sync::mpsc::unbounded_channel();
+    std::thread::spawn(move || run_network_thread(network_thread_rx));
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_state::<AppState>()
+        .add_event::<BankEvent>()
+        .add_event::<CharacterSelectEvent>()
+        .add_event::<ChatboxEvent>()
+        .add_event::<ClientEntityEvent>()
+        .add_event::<GameConnectionEvent>()
+        .add_event::<LoadZoneEvent>()
+        .add_event::<LoginEvent>()
+        .add_event::<MessageBoxEvent>()
+        .add_event::<NetworkEvent>()
+        .add_event::<PartyEvent>()
+        .add_event::<PersonalStoreEvent>()
+        .add_event::<QuestTriggerEvent>()
+        .add_event::<UseItemEvent>()
+        .add_event::<WorldConnectionEvent>()
+        .add_event::<ZoneEvent>()
+        .insert_resource(game_data)
+        .insert_resource(ServerConfiguration {
+            ip: addresses.login.ip().to_string(),
+            port: addresses.login.port().to_string(),
+            preset_username: Some("mock".to_string()),
+            preset_password: Some("mock".to_string()),
+            preset_server_id: None,
+            preset_channel_id: None,
+            preset_character_name: Some(MOCK_CHARACTER_NAME.to_string()),
+            auto_login: true,
+        })
+        .insert_resource(LoginState::Input)
+        .insert_resource(NetworkThread::new(network_thread_tx))
+        .insert_resource(NetworkSession::Live)
+        .insert_resource(protocol_registry)
+        .init_resource::<ClientEntityList>()
+        .init_resource::<ConnectionState>()
+        .init_resource::<NetworkStatistics>()
+        .init_resource::<PacketInspector>()
+        .add_systems(
+            PreUpdate,
+            (
+                login_connection_system,
+                world_connection_system,
+                game_connection_system,
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                auto_login_system,
+                login_event_system,
+                headless_character_select_system,
+                game_zone_change_system,
+            )
+                .chain(),
+        )
+        .add_systems(PostUpdate, network_thread_system);
+    app
+}
+
+/// Updates the app until `condition` is true, the network clients run on
+/// their own thread so we sleep between updates.
+fn update_until(app: &mut App, what: &str, condition: impl Fn(&mut App) -> bool) {
+    for _ in 0..500 {
+        app.update();
+        if condition(app) {
+            return;
+        }
+
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    panic!("Timed out waiting for {}", what);
+}
+
+fn player_next_command(app: &mut App) -> Option<Command> {
+    let player_entity = app.world.resource::<ClientEntityList>().player_entity?;
+    (**app.world.get::<NextCommand>(player_entity)?).clone()
+}
+
+/// Runs the login to in zone flow through the same connection, auto login and
+/// zone change systems as the client against the default mock server script,
+/// then checks the player follows the mock server move and attack replies.
+#[test]
+#[ignore = "requires game data, set ROSE_VFS_PATH to the path of data.idx"]
+fn headless_app_login_to_in_zone() {
+    let mock_server = MockServer::bind().unwrap();
+    let mut app = create_headless_app(&mock_server);
+    let mock_server = mock_server.spawn(
+        MockServerScript::default_session(PACKET_CODEC_SEED),
+        PACKET_CODEC_SEED,
+    );
+
+    update_until(&mut app, "join zone", |app| {
+        matches!(
+            app.world.resource::<State<AppState>>().get(),
+            AppState::Game
+        )
+    });
+    let client_entity_list = app.world.resource::<ClientEntityList>();
+    assert_eq!(client_entity_list.player_entity_id, Some(MOCK_ENTITY_ID));
+    let player_entity = client_entity_list.player_entity.unwrap();
+    assert_eq!(
+        app.world.get::<ClientEntity>(player_entity).unwrap().id,
+        MOCK_ENTITY_ID
+    );
+    assert!(mock_server.has_received(
+        SessionConnectionType::Game,
+        MockClientPackets::JoinZone as u16
+    ));
+
+    let game_connection = app.world.resource::<GameConnection>();
+    game_connection
+        .client_message_tx
+        .send(ClientMessage::Move {
+            target_entity_id: None,
+            x: 521000.0,
+            y: 522000.0,
+            z: 10,
+        })
+        .unwrap();
+    update_until(&mut app, "move", |app| {
+        matches!(
+            player_next_command(app),
+            Some(Command::Move(CommandMove { destination, .. }))
+                if destination == Vec3::new(521000.0, 522000.0, 10.0)
+        )
+    });
+
+    let game_connection = app.world.resource::<GameConnection>();
+    game_connection
+        .client_message_tx
+        .send(ClientMessage::Attack {
+            target_entity_id: MOCK_ENTITY_ID,
+        })
+        .unwrap();
+    update_until(&mut app, "attack", |app| {
+        player_next_command(app)
+            == Some(Command::Attack(CommandAttack {
+                target: player_entity,
+            }))
+    });
+}